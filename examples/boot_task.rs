@@ -14,6 +14,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .action(Action::new("test_time_action", "notepad.exe", "", ""))?
         .start_boundary("2022-04-28T02:14:08.660633427+00:00")?
         .build()?
-        .register("TimeTaskName", TaskCreationFlags::CreateOrUpdate as i32)?;
+        .register("TimeTaskName", TaskCreationFlags::CreateOrUpdate)?;
     Ok(())
 }
@@ -14,9 +14,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .trigger("test_trigger", true)?
         .days_interval(1)?
         .action(Action::new("test", "notepad.exe", "", ""))?
-        .start_boundary(&Local::now().to_rfc3339())?
+        .start_boundary(Local::now().to_rfc3339())?
         .build()?
-        .register("TaskName", TaskCreationFlags::CreateOrUpdate as i32)?;
+        .register("TaskName", TaskCreationFlags::CreateOrUpdate)?;
 
     Ok(())
 }
@@ -15,13 +15,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .trigger("test_time_folder_trigger", true)?
         .action(Action::new("test_time_action", "notepad.exe", "", ""))?
         .start_boundary(
-            &Local::now()
+            Local::now()
                 .checked_add_signed(Duration::seconds(5))
                 .unwrap()
                 .to_rfc3339(),
         )?
         .build()?
-        .register("Time Folder Task", TaskCreationFlags::CreateOrUpdate as i32)?;
+        .register("Time Folder Task", TaskCreationFlags::CreateOrUpdate)?;
 
     Ok(())
 }
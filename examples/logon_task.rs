@@ -15,6 +15,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .start_boundary("2022-04-28T02:14:08.660633427+00:00")?
         .user_id("")?
         .build()?
-        .register("TimeTaskName", TaskCreationFlags::CreateOrUpdate as i32)?;
+        .register("TimeTaskName", TaskCreationFlags::CreateOrUpdate)?;
     Ok(())
 }
@@ -16,11 +16,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .months_of_year(vec![Month::January])?
         .days_of_week(vec![DayOfWeek::Monday])?
         .weeks_of_month(vec![WeekOfMonth::First])?
-        .start_boundary(&Local::now().to_rfc3339())?;
+        .start_boundary(Local::now().to_rfc3339())?;
 
     builder.build()?.register(
         "MonthlyDOWTaskName",
-        TaskCreationFlags::CreateOrUpdate as i32,
+        TaskCreationFlags::CreateOrUpdate,
     )?;
 
     Ok(())
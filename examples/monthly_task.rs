@@ -15,11 +15,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .action(Action::new("test", "notepad.exe", "", ""))?
         .days_of_month(vec![DayOfMonth::Day(1), DayOfMonth::Day(15)])?
         .months_of_year(vec![Month::December])?
-        .start_boundary(&Local::now().to_rfc3339())?;
+        .start_boundary(Local::now().to_rfc3339())?;
 
     builder
         .build()?
-        .register("MonthlyTaskName", TaskCreationFlags::CreateOrUpdate as i32)?;
+        .register("MonthlyTaskName", TaskCreationFlags::CreateOrUpdate)?;
 
     Ok(())
 }
@@ -15,9 +15,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .trigger("test_trigger_1", true)?
             .days_interval(1)?
             .action(Action::new("test", "notepad.exe", "", ""))?
-            .start_boundary(&Local::now().to_rfc3339())?
+            .start_boundary(Local::now().to_rfc3339())?
             .build()?
-            .register("TaskName1", TaskCreationFlags::CreateOrUpdate as i32)?;
+            .register("TaskName1", TaskCreationFlags::CreateOrUpdate)?;
     }
 
     if true {
@@ -28,9 +28,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .trigger("test_trigger_2", true)?
             .days_interval(1)?
             .action(Action::new("test", "notepad.exe", "", ""))?
-            .start_boundary(&Local::now().to_rfc3339())?
+            .start_boundary(Local::now().to_rfc3339())?
             .build()?
-            .register("TaskName2", TaskCreationFlags::CreateOrUpdate as i32)?;
+            .register("TaskName2", TaskCreationFlags::CreateOrUpdate)?;
     }
     Ok(())
 }
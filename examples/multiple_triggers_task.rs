@@ -0,0 +1,23 @@
+use planif::enums::TaskCreationFlags;
+use planif::schedule::TaskScheduler;
+use planif::schedule_builder::{Action, ScheduleBuilder};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let ts = TaskScheduler::new()?;
+    let com = ts.get_com();
+    let sb = ScheduleBuilder::new(&com).unwrap();
+
+    sb.create_logon()
+        .author("Matt")?
+        .description("Runs at logon and every day at 02:00")?
+        .trigger("logon_trigger", true)?
+        .user_id("")?
+        .create_daily()
+        .trigger("daily_trigger", true)?
+        .days_interval(1)?
+        .start_boundary("2022-04-28T02:00:00")?
+        .action(Action::new("test_action", "notepad.exe", "", ""))?
+        .build()?
+        .register("MultipleTriggersTaskName", TaskCreationFlags::CreateOrUpdate)?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use planif::enums::{StateChange, TaskCreationFlags};
+use planif::schedule::TaskScheduler;
+use planif::schedule_builder::{Action, ScheduleBuilder};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let ts = TaskScheduler::new()?;
+    let com = ts.get_com();
+    let sb = ScheduleBuilder::new(&com).unwrap();
+
+    sb.create_session_state_change()
+        .author("Matt")?
+        .description("Test Session State Change Trigger")?
+        .trigger("test_session_trigger", true)?
+        .state_change(StateChange::SessionLock)?
+        .action(Action::new("test_session_action", "notepad.exe", "", ""))?
+        .build()?
+        .register("SessionStateChangeTaskName", TaskCreationFlags::CreateOrUpdate)?;
+    Ok(())
+}
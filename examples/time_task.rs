@@ -13,7 +13,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .description("Test Time Trigger")?
         .trigger("test_time_trigger", true)?
         .action(Action::new("test_time_action", "notepad.exe", "", ""))?
-        .start_boundary("2022-04-28T02:14:08.660633427+00:00")?
+        // Starts 5 minutes after the schedule is built
+        .start_boundary_in(Duration {
+            minutes: Some(5),
+            ..Default::default()
+        })?
         // RandomDelay of 2 seconds
         .random_delay(Duration {
             seconds: Some(5),
@@ -21,6 +25,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             ..Default::default()
         })?
         .build()?
-        .register("TimeTaskName", TaskCreationFlags::CreateOrUpdate as i32)?;
+        .register("TimeTaskName", TaskCreationFlags::CreateOrUpdate)?;
     Ok(())
 }
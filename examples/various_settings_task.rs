@@ -1,15 +1,18 @@
 use chrono::prelude::*;
 use planif::enums::TaskCreationFlags;
 use planif::schedule_builder::{Action, ScheduleBuilder};
-use planif::settings::Settings;
+use planif::settings::{Duration, Priority, SettingsBuilder};
 use planif::schedule::TaskScheduler;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let ts = TaskScheduler::new()?;
     let com = ts.get_com();
     let sb = ScheduleBuilder::new(&com).unwrap();
-    let mut settings = Settings::new();
-    settings.run_only_if_idle = Some(true);
+    let settings = SettingsBuilder::new()
+        .run_only_if_idle(true)
+        .priority(Priority::BelowNormalLow)
+        .restart_on_failure(3, Duration { minutes: Some(5), ..Default::default() })
+        .build()?;
 
     sb.create_daily()
         .author("Matt")?
@@ -18,9 +21,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .trigger("test_trigger", true)?
         .days_interval(1)?
         .action(Action::new("test", "notepad.exe", "", ""))?
-        .start_boundary(&Local::now().to_rfc3339())?
+        .start_boundary(Local::now().to_rfc3339())?
         .build()?
-        .register("TaskName", TaskCreationFlags::CreateOrUpdate as i32)?;
+        .register("TaskName", TaskCreationFlags::CreateOrUpdate)?;
 
     Ok(())
 }
@@ -13,10 +13,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .description("Test Weekly Trigger")?
         .trigger("test_weekly_trigger", true)?
         .action(Action::new("test", "notepad.exe", "", ""))?
-        .start_boundary(&Local::now().to_rfc3339())?
+        .start_boundary(Local::now().to_rfc3339())?
         .days_of_week(vec![DayOfWeek::Sunday, DayOfWeek::Thursday])?
         .weeks_interval(3)?
         .build()?
-        .register("WeeklyTaskName", TaskCreationFlags::CreateOrUpdate as i32)?;
+        .register("WeeklyTaskName", TaskCreationFlags::CreateOrUpdate)?;
     Ok(())
 }
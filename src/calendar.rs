@@ -0,0 +1,413 @@
+use crate::enums::{DayOfWeek, Month, WeekOfMonth};
+use crate::error::ParseError;
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+// Defines a set of the variants of a flag enum, stored in the bitwise mask used by the Task
+// Scheduler. Each variant has an abbreviation, used by `Display`, and a name, both accepted by
+// `FromStr`.
+macro_rules! flag_set {
+    (
+        $(#[$meta:meta])*
+        $set:ident($bits:ty) of $item:ident ($description:literal, $item_description:literal) {
+            $($variant:ident => $abbreviation:literal, $name:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $set {
+            bits: $bits,
+        }
+
+        impl $set {
+            const ITEMS: &'static [($item, &'static str, &'static str)] =
+                &[$(($item::$variant, $abbreviation, $name)),+];
+
+            /// Creates an empty set.
+            pub const fn empty() -> Self {
+                Self { bits: 0 }
+            }
+
+            #[doc = concat!("Creates the set of every [", stringify!($item), "].")]
+            pub const fn all() -> Self {
+                Self {
+                    bits: 0 $(| $item::$variant as $bits)+,
+                }
+            }
+
+            /// The bitwise mask of the set, as used by the Task Scheduler.
+            pub const fn bits(&self) -> $bits {
+                self.bits
+            }
+
+            /// Decodes a bitwise mask of the Task Scheduler, `None` when it has unknown bits.
+            pub const fn from_bits(bits: $bits) -> Option<Self> {
+                if bits & !Self::all().bits == 0 {
+                    Some(Self { bits })
+                } else {
+                    None
+                }
+            }
+
+            /// Decodes a bitwise mask of the Task Scheduler, ignoring the unknown bits.
+            pub const fn from_bits_truncate(bits: $bits) -> Self {
+                Self {
+                    bits: bits & Self::all().bits,
+                }
+            }
+
+            /// Whether the set contains the item.
+            pub const fn contains(&self, item: $item) -> bool {
+                self.bits & item as $bits != 0
+            }
+
+            /// Adds the item to the set.
+            pub fn insert(&mut self, item: $item) {
+                self.bits |= item as $bits;
+            }
+
+            /// Removes the item from the set.
+            pub fn remove(&mut self, item: $item) {
+                self.bits &= !(item as $bits);
+            }
+
+            /// The items which are in either set.
+            pub const fn union(self, other: Self) -> Self {
+                Self {
+                    bits: self.bits | other.bits,
+                }
+            }
+
+            /// The items which are in both sets.
+            pub const fn intersection(self, other: Self) -> Self {
+                Self {
+                    bits: self.bits & other.bits,
+                }
+            }
+
+            /// The items which are in this set but not in the other.
+            pub const fn difference(self, other: Self) -> Self {
+                Self {
+                    bits: self.bits & !other.bits,
+                }
+            }
+
+            /// Whether the set has no items.
+            pub const fn is_empty(&self) -> bool {
+                self.bits == 0
+            }
+
+            /// The number of items in the set.
+            pub const fn len(&self) -> usize {
+                self.bits.count_ones() as usize
+            }
+
+            /// Iterates over the items of the set, in the order of the calendar.
+            pub fn iter(&self) -> impl Iterator<Item = $item> + '_ {
+                Self::ITEMS
+                    .iter()
+                    .map(|(item, _, _)| *item)
+                    .filter(|item| self.contains(*item))
+            }
+        }
+
+        impl From<$item> for $set {
+            fn from(item: $item) -> Self {
+                Self {
+                    bits: item as $bits,
+                }
+            }
+        }
+
+        impl FromIterator<$item> for $set {
+            fn from_iter<I: IntoIterator<Item = $item>>(items: I) -> Self {
+                let mut set = Self::empty();
+                for item in items {
+                    set.insert(item);
+                }
+                set
+            }
+        }
+
+        impl Extend<$item> for $set {
+            fn extend<I: IntoIterator<Item = $item>>(&mut self, items: I) {
+                for item in items {
+                    self.insert(item);
+                }
+            }
+        }
+
+        impl From<Vec<$item>> for $set {
+            fn from(items: Vec<$item>) -> Self {
+                items.into_iter().collect()
+            }
+        }
+
+        impl<const N: usize> From<[$item; N]> for $set {
+            fn from(items: [$item; N]) -> Self {
+                items.into_iter().collect()
+            }
+        }
+
+        impl IntoIterator for $set {
+            type Item = $item;
+            type IntoIter = std::vec::IntoIter<$item>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter().collect::<Vec<_>>().into_iter()
+            }
+        }
+
+        impl BitOr for $set {
+            type Output = Self;
+
+            fn bitor(self, other: Self) -> Self {
+                self.union(other)
+            }
+        }
+
+        impl BitOr<$item> for $set {
+            type Output = Self;
+
+            fn bitor(self, item: $item) -> Self {
+                self.union(item.into())
+            }
+        }
+
+        impl BitOr for $item {
+            type Output = $set;
+
+            fn bitor(self, other: Self) -> $set {
+                $set::from(self).union(other.into())
+            }
+        }
+
+        impl BitOrAssign for $set {
+            fn bitor_assign(&mut self, other: Self) {
+                *self = self.union(other);
+            }
+        }
+
+        impl BitAnd for $set {
+            type Output = Self;
+
+            fn bitand(self, other: Self) -> Self {
+                self.intersection(other)
+            }
+        }
+
+        impl BitAndAssign for $set {
+            fn bitand_assign(&mut self, other: Self) {
+                *self = self.intersection(other);
+            }
+        }
+
+        impl Sub for $set {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                self.difference(other)
+            }
+        }
+
+        impl SubAssign for $set {
+            fn sub_assign(&mut self, other: Self) {
+                *self = self.difference(other);
+            }
+        }
+
+        impl fmt::Display for $set {
+            /// Formats the abbreviations of the items separated by commas.
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let abbreviations: Vec<&str> = Self::ITEMS
+                    .iter()
+                    .filter(|(item, _, _)| self.contains(*item))
+                    .map(|(_, abbreviation, _)| *abbreviation)
+                    .collect();
+                write!(f, "{}", abbreviations.join(","))
+            }
+        }
+
+        impl FromStr for $set {
+            type Err = ParseError;
+
+            /// Parses items separated by commas, by abbreviation or by name, ignoring the case.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut set = Self::empty();
+                if s.trim().is_empty() {
+                    return Ok(set);
+                }
+
+                let mut position = 0;
+                for part in s.split(',') {
+                    let value = part.trim();
+                    let found = Self::ITEMS.iter().find(|(_, abbreviation, name)| {
+                        value.eq_ignore_ascii_case(abbreviation) || value.eq_ignore_ascii_case(name)
+                    });
+                    match found {
+                        Some((item, _, _)) => set.insert(*item),
+                        None => {
+                            return Err(ParseError {
+                                message: format!(
+                                    "Invalid {} '{}': '{}' is not a {}",
+                                    $description, s, value, $item_description
+                                ),
+                                position: Some(position + part.len() - part.trim_start().len()),
+                            })
+                        }
+                    }
+                    position += part.len() + 1;
+                }
+                Ok(set)
+            }
+        }
+    };
+}
+
+flag_set! {
+    /// A set of days of the week, ie: the days on which a weekly trigger starts the task.
+    ///
+    /// # Example
+    /// ```
+    /// use planif::calendar::DaysOfWeek;
+    /// use planif::enums::DayOfWeek;
+    ///
+    /// let days = DayOfWeek::Monday | DayOfWeek::Wednesday | DayOfWeek::Friday;
+    /// assert_eq!(days.to_string(), "Mon,Wed,Fri");
+    /// assert_eq!("mon, wednesday,Fri".parse::<DaysOfWeek>().unwrap(), days);
+    /// assert_eq!(days.bits(), 0x2a);
+    /// assert_eq!(days & DaysOfWeek::weekends(), DaysOfWeek::empty());
+    /// ```
+    DaysOfWeek(i16) of DayOfWeek ("days of the week", "day of the week") {
+        Sunday => "Sun", "Sunday",
+        Monday => "Mon", "Monday",
+        Tuesday => "Tue", "Tuesday",
+        Wednesday => "Wed", "Wednesday",
+        Thursday => "Thu", "Thursday",
+        Friday => "Fri", "Friday",
+        Saturday => "Sat", "Saturday",
+    }
+}
+
+impl DaysOfWeek {
+    /// Creates the set of the days from Monday to Friday.
+    pub const fn weekdays() -> Self {
+        Self::all().difference(Self::weekends())
+    }
+
+    /// Creates the set of Saturday and Sunday.
+    pub const fn weekends() -> Self {
+        Self {
+            bits: DayOfWeek::Saturday as i16 | DayOfWeek::Sunday as i16,
+        }
+    }
+}
+
+flag_set! {
+    /// A set of months of the year, ie: the months during which a monthly trigger starts the task.
+    ///
+    /// # Example
+    /// ```
+    /// use planif::calendar::Months;
+    /// use planif::enums::Month;
+    ///
+    /// let months: Months = "Jan,Apr,Jul,Oct".parse().unwrap();
+    /// assert!(months.contains(Month::April));
+    /// assert_eq!(months.len(), 4);
+    /// assert_eq!(Months::from_bits(0x249), Some(months));
+    /// assert_eq!(Months::from_bits(0x1000), None);
+    /// ```
+    Months(i16) of Month ("months", "month") {
+        January => "Jan", "January",
+        February => "Feb", "February",
+        March => "Mar", "March",
+        April => "Apr", "April",
+        May => "May", "May",
+        June => "Jun", "June",
+        July => "Jul", "July",
+        August => "Aug", "August",
+        September => "Sep", "September",
+        October => "Oct", "October",
+        November => "Nov", "November",
+        December => "Dec", "December",
+    }
+}
+
+flag_set! {
+    /// A set of weeks of the month, ie: the weeks during which a monthly day-of-week trigger starts
+    /// the task. The last week of the month is set with
+    /// [run_on_last_week](crate::schedule_builder::ScheduleBuilder::run_on_last_week).
+    ///
+    /// # Example
+    /// ```
+    /// use planif::calendar::WeeksOfMonth;
+    /// use planif::enums::WeekOfMonth;
+    ///
+    /// let weeks = WeekOfMonth::First | WeekOfMonth::Third;
+    /// assert_eq!(weeks.to_string(), "First,Third");
+    /// assert_eq!(weeks.iter().collect::<Vec<_>>(), vec![WeekOfMonth::First, WeekOfMonth::Third]);
+    /// ```
+    WeeksOfMonth(i16) of WeekOfMonth ("weeks of the month", "week of the month") {
+        First => "First", "1",
+        Second => "Second", "2",
+        Third => "Third", "3",
+        Fourth => "Fourth", "4",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets() {
+        let days = DaysOfWeek::from(vec![DayOfWeek::Monday, DayOfWeek::Monday]);
+        assert_eq!(days.bits(), DayOfWeek::Monday as i16);
+        assert_eq!(days.len(), 1);
+
+        assert_eq!(DaysOfWeek::weekdays().to_string(), "Mon,Tue,Wed,Thu,Fri");
+        assert_eq!(DaysOfWeek::weekends().to_string(), "Sun,Sat");
+        assert_eq!(
+            DaysOfWeek::weekdays() | DaysOfWeek::weekends(),
+            DaysOfWeek::all()
+        );
+        assert_eq!(
+            DaysOfWeek::all() - DaysOfWeek::weekdays(),
+            DaysOfWeek::weekends()
+        );
+        assert_eq!(DaysOfWeek::from_bits(0x80), None);
+        assert_eq!(DaysOfWeek::from_bits_truncate(0xff), DaysOfWeek::all());
+
+        let mut months = Months::from([Month::March, Month::January]);
+        months.remove(Month::March);
+        months |= Month::December.into();
+        assert_eq!(
+            months.into_iter().collect::<Vec<_>>(),
+            vec![Month::January, Month::December]
+        );
+        assert_eq!(Months::all().bits(), 0xfff);
+
+        assert_eq!(WeeksOfMonth::all().bits(), 0xf);
+        assert_eq!(
+            "2, 4".parse::<WeeksOfMonth>().unwrap().to_string(),
+            "Second,Fourth"
+        );
+    }
+
+    #[test]
+    fn parse_sets() {
+        assert_eq!("".parse::<Months>().unwrap(), Months::empty());
+        assert_eq!(
+            "SUN,sat".parse::<DaysOfWeek>().unwrap(),
+            DaysOfWeek::weekends()
+        );
+
+        let error = "Mon,Wed,Fry".parse::<DaysOfWeek>().unwrap_err();
+        assert_eq!(error.position, Some(8));
+        assert_eq!(
+            error.message,
+            "Invalid days of the week 'Mon,Wed,Fry': 'Fry' is not a day of the week"
+        );
+    }
+}
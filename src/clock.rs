@@ -0,0 +1,87 @@
+use crate::datetime::{DateTime, ZonedDateTime};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The source of the current date and time, used to evaluate the relative boundaries of the
+/// triggers when a schedule is built, see
+/// [start_boundary_in](crate::schedule_builder::ScheduleBuilder::start_boundary_in).
+pub trait Clock {
+    /// Returns the current date and time.
+    fn now(&self) -> ZonedDateTime;
+}
+
+/// The clock of the system, in UTC. It is the default clock of the
+/// [schedule builders](crate::schedule_builder::ScheduleBuilder). A system clock outside of the
+/// years 1 to 9999 is clamped to them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> ZonedDateTime {
+        let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX),
+            Err(e) => i64::try_from(e.duration().as_secs()).map_or(i64::MIN, |s| -s),
+        };
+        ZonedDateTime::new(DateTime::saturating_from_timestamp(seconds), 0)
+    }
+}
+
+/// A clock which always returns the same date and time, to build reproducible schedules.
+///
+/// # Example
+/// ```
+/// use planif::clock::{Clock, FixedClock};
+/// use planif::datetime::{DateTime, ZonedDateTime};
+///
+/// let now = ZonedDateTime::new(DateTime::new(2023, 1, 1, 8, 0, 0).unwrap(), 3600);
+/// let clock = FixedClock::new(now);
+/// assert_eq!(clock.now(), now);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock {
+    now: ZonedDateTime,
+}
+
+impl FixedClock {
+    /// Creates a clock which always returns `now`.
+    pub fn new(now: ZonedDateTime) -> Self {
+        Self { now }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> ZonedDateTime {
+        self.now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_clock() {
+        let now = ZonedDateTime::new("2023-01-01T08:00:00".parse().unwrap(), -5 * 3600);
+        let clock = FixedClock::new(now);
+        assert_eq!(clock.now(), now);
+        assert_eq!(clock.now().to_string(), "2023-01-01T08:00:00-05:00");
+    }
+
+    #[test]
+    fn system_clock() {
+        let now = SystemClock.now();
+        assert_eq!(now.offset(), 0);
+        assert!(now.local() > "2023-01-01T00:00:00".parse().unwrap());
+    }
+
+    #[test]
+    fn saturating_timestamps() {
+        assert_eq!(
+            DateTime::saturating_from_timestamp(i64::MAX).to_string(),
+            "9999-12-31T23:59:59"
+        );
+        assert_eq!(
+            DateTime::saturating_from_timestamp(i64::MIN).to_string(),
+            "0001-01-01T00:00:00"
+        );
+    }
+}
@@ -1,26 +1,38 @@
+use crate::error::Error;
+#[cfg(windows)]
+use crate::error::TaskSchedulerError;
 use std::rc::Rc;
+#[cfg(windows)]
 use windows::Win32::System::Com::{
     CoInitializeEx, CoUninitialize, COINIT_MULTITHREADED
 };
 
 /// Represents a COM runtime required for building schedules tasks
-#[derive(Clone)]
-pub struct ComRuntime(Rc<Com>);
+///
+/// On platforms other than Windows the runtime is a no-op. Schedules can still be built and
+/// inspected, but [registering](crate::schedule::Schedule::register) them will fail.
+#[derive(Clone, Debug)]
+// The `Rc<Com>` is never read, it keeps the COM initialized until the last clone is dropped.
+pub struct ComRuntime(#[allow(dead_code)] Rc<Com>);
 
 impl ComRuntime {
     /// Creates a COM runtime for use with one or more
     /// [`ScheduleBuilder`](super::schedule_builder::ScheduleBuilder)
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new() -> Result<Self, Error> {
         Ok(ComRuntime(Rc::new(Com::initialize()?)))
     }
 }
 
+#[derive(Debug)]
 struct Com;
 
 impl Com {
-    fn initialize() -> Result<Self, Box<dyn std::error::Error>> {
+    fn initialize() -> Result<Self, Error> {
+        #[cfg(windows)]
         unsafe {
-            CoInitializeEx(None, COINIT_MULTITHREADED)?;
+            CoInitializeEx(None, COINIT_MULTITHREADED).map_err(|e| {
+                TaskSchedulerError::new("CoInitializeEx", e.code().0, &e.message().to_string())
+            })?;
         }
         Ok(Com)
     }
@@ -28,9 +40,9 @@ impl Com {
 
 impl Drop for Com {
     fn drop(&mut self) {
+        #[cfg(windows)]
         unsafe {
             CoUninitialize();
         }
     }
 }
-
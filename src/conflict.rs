@@ -0,0 +1,479 @@
+use crate::datetime::DateTime;
+use crate::simulation::{Execution, Timeline};
+use std::fmt::Write;
+use std::ops::Range;
+
+/// The rules checked by [Timeline::conflicts], in addition to the overlaps between tasks.
+///
+/// # Example
+/// ```
+/// use planif::conflict::{Blackout, ConflictRules};
+///
+/// let rules = ConflictRules {
+///     concurrency_budget: Some(2),
+///     blackouts: vec![Blackout {
+///         name: "Business hours".to_string(),
+///         window: "2023-01-02T09:00:00".parse().unwrap().."2023-01-02T17:00:00".parse().unwrap(),
+///     }],
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConflictRules {
+    /// The maximum number of executions running at the same time, `None` for no maximum.
+    pub concurrency_budget: Option<usize>,
+    /// The windows during which no task may run.
+    pub blackouts: Vec<Blackout>,
+}
+
+/// A window during which no task may run, such as business hours.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blackout {
+    /// The name of the blackout, reported with its conflicts.
+    pub name: String,
+    /// The start (inclusive) and end (exclusive) of the blackout.
+    pub window: Range<DateTime>,
+}
+
+/// The conflicts found in a [Timeline], created by [Timeline::conflicts].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictReport {
+    /// The start of the simulated range, inclusive.
+    pub from: DateTime,
+    /// The end of the simulated range, exclusive.
+    pub to: DateTime,
+    /// The conflicts, ordered by the start of their window.
+    pub conflicts: Vec<Conflict>,
+}
+
+/// A window of time during which executions conflict.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The rule broken by the executions.
+    pub kind: ConflictKind,
+    /// The start (inclusive) and end (exclusive) of the conflict.
+    pub window: Range<DateTime>,
+    /// The executions which may run during the window.
+    pub executions: Vec<ExecutionRef>,
+}
+
+/// The rule broken by a [Conflict].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Executions of two or more tasks may run at the same time.
+    Overlap,
+    /// More executions than the concurrency budget may run at the same time.
+    ConcurrencyBudget {
+        /// The concurrency budget of the [rules](ConflictRules).
+        budget: usize,
+        /// The largest number of executions which may run at the same time during the window.
+        running: usize,
+    },
+    /// An execution may run during a blackout.
+    Blackout {
+        /// The name of the blackout.
+        name: String,
+    },
+}
+
+/// Identifies an [Execution] of a [Timeline] in a [Conflict].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionRef {
+    /// The name of the task.
+    pub task: String,
+    /// The identifier of the trigger starting the execution.
+    pub trigger: String,
+    /// The occurrence of the trigger.
+    pub scheduled: DateTime,
+}
+
+impl From<&Execution> for ExecutionRef {
+    fn from(execution: &Execution) -> Self {
+        Self {
+            task: execution.task.clone(),
+            trigger: execution.trigger.clone(),
+            scheduled: execution.scheduled,
+        }
+    }
+}
+
+impl Timeline {
+    /// Finds the windows where executions of different tasks may run at the same time, where more
+    /// executions than the concurrency budget may run, and where executions may run during a
+    /// blackout.
+    ///
+    /// An execution may run from the earliest time it starts until its latest end, or until the
+    /// end of the timeline when it has no time limit, the executions are limited to the range of
+    /// the timeline. Skipped executions never run.
+    ///
+    /// # Example
+    /// ```
+    /// use planif::conflict::{ConflictKind, ConflictRules};
+    /// use planif::settings::Duration;
+    /// use planif::simulation::Simulator;
+    /// use planif::task_definition::{TaskDefinition, Trigger, TriggerKind};
+    ///
+    /// let task = |start: &str| {
+    ///     let mut definition = TaskDefinition::new();
+    ///     definition.triggers.push(Trigger {
+    ///         start_boundary: Some(start.to_string()),
+    ///         execution_time_limit: Some(Duration { hours: Some(2), ..Default::default() }),
+    ///         ..Trigger::new("Daily", TriggerKind::Daily(Default::default()))
+    ///     });
+    ///     definition
+    /// };
+    /// let backup = task("2023-01-01T01:00:00");
+    /// let defrag = task("2023-01-01T02:00:00");
+    ///
+    /// let report = Simulator::new()
+    ///     .task("Backup", &backup)
+    ///     .task("Defrag", &defrag)
+    ///     .run("2023-01-01T00:00:00".parse().unwrap(), "2023-01-02T00:00:00".parse().unwrap())
+    ///     .unwrap()
+    ///     .conflicts(&ConflictRules::default());
+    ///
+    /// assert_eq!(report.conflicts.len(), 1);
+    /// assert_eq!(report.conflicts[0].kind, ConflictKind::Overlap);
+    /// assert_eq!(report.conflicts[0].window.start.to_string(), "2023-01-01T02:00:00");
+    /// assert_eq!(report.conflicts[0].window.end.to_string(), "2023-01-01T03:00:00");
+    /// ```
+    pub fn conflicts(&self, rules: &ConflictRules) -> ConflictReport {
+        let runs: Vec<(Range<DateTime>, &Execution)> = self
+            .executions
+            .iter()
+            .filter_map(|execution| {
+                let start = (*execution.start.as_ref()?.start()).max(self.from);
+                let end = execution.end.unwrap_or(self.to).min(self.to);
+                (start < end).then_some((start..end, execution))
+            })
+            .collect();
+
+        let mut conflicts = Vec::new();
+        for (window, executions) in windows(&runs, |running| {
+            let first = running.first().map(|e| &e.task);
+            running.iter().any(|e| Some(&e.task) != first)
+        }) {
+            conflicts.push(Conflict {
+                kind: ConflictKind::Overlap,
+                window,
+                executions,
+            });
+        }
+
+        if let Some(budget) = rules.concurrency_budget {
+            for (window, executions) in windows(&runs, |running| running.len() > budget) {
+                conflicts.push(Conflict {
+                    kind: ConflictKind::ConcurrencyBudget {
+                        budget,
+                        running: max_running(&runs, &window),
+                    },
+                    window,
+                    executions,
+                });
+            }
+        }
+
+        for blackout in &rules.blackouts {
+            for (run, execution) in &runs {
+                let start = run.start.max(blackout.window.start);
+                let end = run.end.min(blackout.window.end);
+                if start < end {
+                    conflicts.push(Conflict {
+                        kind: ConflictKind::Blackout {
+                            name: blackout.name.clone(),
+                        },
+                        window: start..end,
+                        executions: vec![ExecutionRef::from(*execution)],
+                    });
+                }
+            }
+        }
+
+        conflicts.sort_by_key(|c| (c.window.start, c.window.end));
+        ConflictReport {
+            from: self.from,
+            to: self.to,
+            conflicts,
+        }
+    }
+}
+
+// Returns the largest windows during which the running executions match `predicate`, with the
+// executions which run during each window.
+fn windows(
+    runs: &[(Range<DateTime>, &Execution)],
+    predicate: impl Fn(&[&Execution]) -> bool,
+) -> Vec<(Range<DateTime>, Vec<ExecutionRef>)> {
+    let mut times: Vec<DateTime> = runs.iter().flat_map(|(r, _)| [r.start, r.end]).collect();
+    times.sort();
+    times.dedup();
+
+    let mut windows: Vec<(Range<DateTime>, Vec<usize>)> = Vec::new();
+    for segment in times.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+        let running: Vec<usize> = (0..runs.len())
+            .filter(|i| runs[*i].0.start <= start && start < runs[*i].0.end)
+            .collect();
+        let executions: Vec<&Execution> = running.iter().map(|i| runs[*i].1).collect();
+        if !predicate(&executions) {
+            continue;
+        }
+
+        match windows.last_mut() {
+            Some((window, indexes)) if window.end == start => {
+                window.end = end;
+                indexes.extend(running);
+            }
+            _ => windows.push((start..end, running)),
+        }
+    }
+
+    windows
+        .into_iter()
+        .map(|(window, mut indexes)| {
+            indexes.sort();
+            indexes.dedup();
+            let executions = indexes.iter().map(|i| ExecutionRef::from(runs[*i].1));
+            (window, executions.collect())
+        })
+        .collect()
+}
+
+// The largest number of executions running at the same time during the window.
+fn max_running(runs: &[(Range<DateTime>, &Execution)], window: &Range<DateTime>) -> usize {
+    runs.iter()
+        .map(|(run, _)| run.start)
+        .chain([window.start])
+        .filter(|time| window.contains(time))
+        .map(|time| runs.iter().filter(|(run, _)| run.contains(&time)).count())
+        .max()
+        .unwrap_or(0)
+}
+
+impl ConflictReport {
+    /// Serializes the report to JSON.
+    ///
+    /// # Example
+    /// ```
+    /// use planif::conflict::ConflictReport;
+    ///
+    /// let report = ConflictReport {
+    ///     from: "2023-01-01T00:00:00".parse().unwrap(),
+    ///     to: "2023-01-02T00:00:00".parse().unwrap(),
+    ///     conflicts: vec![],
+    /// };
+    /// assert_eq!(
+    ///     report.to_json(),
+    ///     r#"{"from":"2023-01-01T00:00:00","to":"2023-01-02T00:00:00","conflicts":[]}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        write!(
+            json,
+            r#"{{"from":"{}","to":"{}","conflicts":["#,
+            self.from, self.to
+        )
+        .unwrap();
+
+        for (i, conflict) in self.conflicts.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            match &conflict.kind {
+                ConflictKind::Overlap => json.push_str(r#"{"kind":"overlap""#),
+                ConflictKind::ConcurrencyBudget { budget, running } => write!(
+                    json,
+                    r#"{{"kind":"concurrency_budget","budget":{},"running":{}"#,
+                    budget, running
+                )
+                .unwrap(),
+                ConflictKind::Blackout { name } => {
+                    json.push_str(r#"{"kind":"blackout","blackout":"#);
+                    write_json_string(&mut json, name);
+                }
+            }
+            write!(
+                json,
+                r#","start":"{}","end":"{}","executions":["#,
+                conflict.window.start, conflict.window.end
+            )
+            .unwrap();
+
+            for (j, execution) in conflict.executions.iter().enumerate() {
+                if j > 0 {
+                    json.push(',');
+                }
+                json.push_str(r#"{"task":"#);
+                write_json_string(&mut json, &execution.task);
+                json.push_str(r#","trigger":"#);
+                write_json_string(&mut json, &execution.trigger);
+                write!(json, r#","scheduled":"{}"}}"#, execution.scheduled).unwrap();
+            }
+            json.push_str("]}");
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Duration;
+    use crate::simulation::Simulator;
+    use crate::task_definition::{TaskDefinition, Trigger, TriggerKind};
+
+    // A daily task running for up to `hours` hours.
+    fn task(start: &str, hours: usize) -> TaskDefinition {
+        let mut definition = TaskDefinition::new();
+        definition.triggers.push(Trigger {
+            start_boundary: Some(start.to_string()),
+            execution_time_limit: Some(Duration {
+                hours: Some(hours),
+                ..Default::default()
+            }),
+            ..Trigger::new("Daily", TriggerKind::Daily(Default::default()))
+        });
+        definition
+    }
+
+    fn datetime(s: &str) -> DateTime {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn executions_running_at_the_start_of_the_timeline() {
+        let backup = task("2022-12-31T23:00:00", 3);
+        let defrag = task("2023-01-01T00:30:00", 1);
+        let report = Simulator::new()
+            .task("Backup", &backup)
+            .task("Defrag", &defrag)
+            .run(
+                datetime("2023-01-01T00:00:00"),
+                datetime("2023-01-01T12:00:00"),
+            )
+            .unwrap()
+            .conflicts(&ConflictRules {
+                concurrency_budget: None,
+                blackouts: vec![Blackout {
+                    name: "Night".to_string(),
+                    window: datetime("2022-12-31T22:00:00")..datetime("2023-01-01T00:15:00"),
+                }],
+            });
+
+        let windows: Vec<(String, String)> = report
+            .conflicts
+            .iter()
+            .map(|c| (c.window.start.to_string(), c.window.end.to_string()))
+            .collect();
+        assert_eq!(
+            windows,
+            vec![
+                ("2023-01-01T00:00:00".to_string(), "2023-01-01T00:15:00".to_string()),
+                ("2023-01-01T00:30:00".to_string(), "2023-01-01T01:30:00".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn conflict_report() {
+        let backup = task("2023-01-01T01:00:00", 3);
+        let defrag = task("2023-01-01T02:00:00", 2);
+        let indexing = task("2023-01-01T03:00:00", 1);
+        let timeline = Simulator::new()
+            .task("Backup", &backup)
+            .task("Defrag", &defrag)
+            .task("Indexing \"fast\"", &indexing)
+            .run(
+                datetime("2023-01-01T00:00:00"),
+                datetime("2023-01-02T00:00:00"),
+            )
+            .unwrap();
+
+        let report = timeline.conflicts(&ConflictRules {
+            concurrency_budget: Some(2),
+            blackouts: vec![Blackout {
+                name: "Maintenance".to_string(),
+                window: datetime("2023-01-01T03:30:00")..datetime("2023-01-01T05:00:00"),
+            }],
+        });
+
+        let summary: Vec<(ConflictKind, String, String, usize)> = report
+            .conflicts
+            .iter()
+            .map(|c| {
+                (
+                    c.kind.clone(),
+                    c.window.start.to_string(),
+                    c.window.end.to_string(),
+                    c.executions.len(),
+                )
+            })
+            .collect();
+        let blackout = ConflictKind::Blackout {
+            name: "Maintenance".to_string(),
+        };
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    ConflictKind::Overlap,
+                    "2023-01-01T02:00:00".to_string(),
+                    "2023-01-01T04:00:00".to_string(),
+                    3
+                ),
+                (
+                    ConflictKind::ConcurrencyBudget {
+                        budget: 2,
+                        running: 3
+                    },
+                    "2023-01-01T03:00:00".to_string(),
+                    "2023-01-01T04:00:00".to_string(),
+                    3
+                ),
+                (
+                    blackout.clone(),
+                    "2023-01-01T03:30:00".to_string(),
+                    "2023-01-01T04:00:00".to_string(),
+                    1
+                ),
+                (
+                    blackout.clone(),
+                    "2023-01-01T03:30:00".to_string(),
+                    "2023-01-01T04:00:00".to_string(),
+                    1
+                ),
+                (
+                    blackout,
+                    "2023-01-01T03:30:00".to_string(),
+                    "2023-01-01T04:00:00".to_string(),
+                    1
+                ),
+            ]
+        );
+
+        let json = report.to_json();
+        assert!(json.starts_with(
+            r#"{"from":"2023-01-01T00:00:00","to":"2023-01-02T00:00:00","conflicts":[{"kind":"overlap","start":"2023-01-01T02:00:00","end":"2023-01-01T04:00:00","executions":[{"task":"Backup","trigger":"Daily","scheduled":"2023-01-01T01:00:00"}"#
+        ));
+        assert!(json.contains(r#"{"task":"Indexing \"fast\"","#));
+        assert!(json.contains(r#"{"kind":"concurrency_budget","budget":2,"running":3,"#));
+        assert!(json.ends_with("]}]}"));
+    }
+}
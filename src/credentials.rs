@@ -0,0 +1,327 @@
+use crate::error::{Error, InvalidOperationError, SecretError};
+use crate::settings::LogonType;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::atomic::{compiler_fence, Ordering};
+
+/// A password which is overwritten with zeros when it is dropped and is redacted by `Debug`.
+///
+/// # Example
+/// ```
+/// use planif::credentials::Password;
+///
+/// // The owned string is moved into the password, which overwrites it when dropped
+/// let value = String::from("hunter2");
+/// let password = Password::new(value);
+/// assert_eq!(format!("{:?}", password), "Password(<redacted>)");
+/// assert_eq!(password.expose(), "hunter2");
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct Password {
+    value: String,
+}
+
+impl Password {
+    /// Creates a password. An owned `String` is moved into the password and is overwritten when
+    /// the password is dropped, a `&str` is copied and the original is left untouched.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+        }
+    }
+
+    /// Returns the password in clear text.
+    pub fn expose(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Drop for Password {
+    fn drop(&mut self) {
+        // Safety: the whole allocation of the string is overwritten with zeros, which are valid
+        // UTF-8, before the string is freed.
+        unsafe {
+            let bytes = self.value.as_mut_vec();
+            zeroize(bytes.as_mut_ptr(), bytes.capacity());
+        }
+    }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Password(<redacted>)")
+    }
+}
+
+impl From<String> for Password {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for Password {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+// Overwrites `len` values from `data` with their default, without the writes being optimized out.
+pub(crate) unsafe fn zeroize<T: Copy + Default>(data: *mut T, len: usize) {
+    for i in 0..len {
+        std::ptr::write_volatile(data.add(i), T::default());
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// The account a task is [registered](crate::schedule::Schedule::register_with_credentials) with.
+///
+/// The [Password](LogonType::Password) and
+/// [InteractiveTokenOrPassword](LogonType::InteractiveTokenOrPassword) logon types require a
+/// password, the other logon types do not accept one.
+///
+/// # Example
+/// ```
+/// use planif::credentials::{Credentials, EnvironmentVariable};
+/// use planif::settings::LogonType;
+///
+/// std::env::set_var("BACKUP_PASSWORD", "hunter2");
+/// let credentials = Credentials::with_provider(
+///     "OURDOMAIN\\svc-backup",
+///     LogonType::Password,
+///     &EnvironmentVariable::new("BACKUP_PASSWORD"),
+/// ).unwrap();
+///
+/// assert_eq!(
+///     format!("{:?}", credentials),
+///     "Credentials { user_id: \"OURDOMAIN\\\\svc-backup\", password: Some(Password(<redacted>)), logon_type: Password }"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    user_id: String,
+    password: Option<Password>,
+    logon_type: LogonType,
+}
+
+impl Credentials {
+    /// Creates the credentials of `user_id`, checking that a password is given when the logon type
+    /// requires one.
+    pub fn new(
+        user_id: &str,
+        password: Option<Password>,
+        logon_type: LogonType,
+    ) -> Result<Self, Error> {
+        let message = if user_id.is_empty() {
+            "The user id of the credentials cannot be empty"
+        } else {
+            match (logon_type, &password) {
+                (LogonType::Password | LogonType::InteractiveTokenOrPassword, None) => {
+                    "A password is required by the Password and InteractiveTokenOrPassword logon types"
+                }
+                (
+                    LogonType::None
+                    | LogonType::S4U
+                    | LogonType::InteractiveToken
+                    | LogonType::Group
+                    | LogonType::ServiceAccount,
+                    Some(_),
+                ) => {
+                    "A password is only used by the Password and InteractiveTokenOrPassword logon types"
+                }
+                _ => {
+                    return Ok(Self {
+                        user_id: user_id.to_string(),
+                        password,
+                        logon_type,
+                    })
+                }
+            }
+        };
+
+        Err(Error::InvalidOperation(InvalidOperationError {
+            message: message.to_string(),
+        }))
+    }
+
+    /// Creates the credentials of `user_id` with the password returned by the provider.
+    pub fn with_provider(
+        user_id: &str,
+        logon_type: LogonType,
+        provider: &dyn SecretProvider,
+    ) -> Result<Self, Error> {
+        Self::new(user_id, Some(provider.password()?), logon_type)
+    }
+
+    /// The identifier of the user, ie: `MyDomain\MyName` or `NT AUTHORITY\SYSTEM`.
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// The password of the user.
+    pub fn password(&self) -> Option<&Password> {
+        self.password.as_ref()
+    }
+
+    /// The security logon method of the task.
+    pub fn logon_type(&self) -> LogonType {
+        self.logon_type
+    }
+}
+
+/// A source of passwords, such as an [environment variable](EnvironmentVariable), a
+/// [file](SecretFile) or a closure returning a [Password].
+///
+/// # Example
+/// ```
+/// use planif::credentials::{Password, SecretProvider};
+///
+/// let vault = || Ok(Password::new("from the vault"));
+/// assert_eq!(vault.password().unwrap().expose(), "from the vault");
+/// ```
+pub trait SecretProvider {
+    /// Returns the password.
+    fn password(&self) -> Result<Password, Error>;
+}
+
+impl<F: Fn() -> Result<Password, Error>> SecretProvider for F {
+    fn password(&self) -> Result<Password, Error> {
+        self()
+    }
+}
+
+/// Reads the password from an environment variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentVariable {
+    name: String,
+}
+
+impl EnvironmentVariable {
+    /// Creates a provider reading the environment variable `name`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+}
+
+impl SecretProvider for EnvironmentVariable {
+    fn password(&self) -> Result<Password, Error> {
+        std::env::var(&self.name).map(Password::new).map_err(|e| {
+            Error::Secret(SecretError {
+                message: format!("Environment variable '{}': {}", self.name, e),
+            })
+        })
+    }
+}
+
+/// Reads the password from a file, such as a mounted secret, without its trailing line break.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretFile {
+    path: PathBuf,
+}
+
+impl SecretFile {
+    /// Creates a provider reading the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl SecretProvider for SecretFile {
+    fn password(&self) -> Result<Password, Error> {
+        let error = |message: String| {
+            Error::Secret(SecretError {
+                message: format!("Secret file '{}': {}", self.path.display(), message),
+            })
+        };
+        let wipe = |mut bytes: Vec<u8>| unsafe { zeroize(bytes.as_mut_ptr(), bytes.capacity()) };
+
+        let mut file = File::open(&self.path).map_err(|e| error(e.to_string()))?;
+        let len = file.metadata().map_err(|e| error(e.to_string()))?.len();
+        // The buffer is large enough to be read without being reallocated, which would leave a
+        // copy of the password which is never overwritten.
+        let mut bytes = Vec::with_capacity(usize::try_from(len).unwrap_or(0).saturating_add(1));
+        if let Err(e) = file.read_to_end(&mut bytes) {
+            wipe(bytes);
+            return Err(error(e.to_string()));
+        }
+        let mut password = match String::from_utf8(bytes) {
+            Ok(value) => Password::new(value),
+            Err(e) => {
+                wipe(e.into_bytes());
+                return Err(error("the password is not valid UTF-8".to_string()));
+            }
+        };
+
+        let len = password.value.trim_end_matches(['\r', '\n']).len();
+        password.value.truncate(len);
+        Ok(password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_providers() {
+        let path = std::env::temp_dir().join(format!("planif-secret-{}", std::process::id()));
+        std::fs::write(&path, "s3cret\r\n").unwrap();
+        let credentials =
+            Credentials::with_provider("User", LogonType::Password, &SecretFile::new(&path))
+                .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(credentials.password().unwrap().expose(), "s3cret");
+        assert!(!format!("{:?}", credentials).contains("s3cret"));
+
+        match EnvironmentVariable::new("PLANIF_MISSING_SECRET").password() {
+            Err(Error::Secret(e)) => assert_eq!(
+                e.message,
+                "Environment variable 'PLANIF_MISSING_SECRET': environment variable not found"
+            ),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert!(SecretFile::new(&path).password().is_err());
+
+        std::fs::write(&path, [0x73, 0xff, 0x0a]).unwrap();
+        let result = SecretFile::new(&path).password();
+        std::fs::remove_file(&path).unwrap();
+        match result {
+            Err(Error::Secret(e)) => {
+                assert!(e.message.ends_with("the password is not valid UTF-8"))
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn credentials_validation() {
+        let message = |result: Result<Credentials, Error>| match result {
+            Err(Error::InvalidOperation(e)) => e.message,
+            result => panic!("unexpected result {:?}", result),
+        };
+
+        assert_eq!(
+            message(Credentials::new("User", None, LogonType::Password)),
+            "A password is required by the Password and InteractiveTokenOrPassword logon types"
+        );
+        assert_eq!(
+            message(Credentials::new(
+                "User",
+                Some("secret".into()),
+                LogonType::S4U
+            )),
+            "A password is only used by the Password and InteractiveTokenOrPassword logon types"
+        );
+        assert!(Credentials::new("NT AUTHORITY\\SYSTEM", None, LogonType::ServiceAccount).is_ok());
+    }
+
+    #[test]
+    fn zeroize_values() {
+        let mut wide = [0x68u16, 0x69];
+        unsafe { zeroize(wide.as_mut_ptr(), wide.len()) };
+        assert_eq!(wide, [0, 0]);
+    }
+}
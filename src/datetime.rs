@@ -0,0 +1,710 @@
+use crate::enums::DayOfWeek;
+use crate::error::{Error, InvalidOperationError, ParseError};
+use crate::time_zone::TimeZone;
+use std::fmt;
+use std::str::FromStr;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+// 0001-01-01T00:00:00 and 9999-12-31T23:59:59, in seconds since 1970-01-01T00:00:00.
+const MIN_SECONDS: i64 = -62_135_596_800;
+const MAX_SECONDS: i64 = 253_402_300_799;
+
+/// A date and time on the wall clock, without a time zone, between the years 1 and 9999.
+///
+/// It is the format of the start and end boundaries of the triggers (ie: `2007-01-01T08:00:00`),
+/// and of the [occurrences](crate::occurrence) computed from them.
+///
+/// # Example
+/// ```
+/// use planif::datetime::DateTime;
+/// use planif::enums::DayOfWeek;
+///
+/// let datetime: DateTime = "2007-01-01T08:00:00".parse().unwrap();
+/// assert_eq!(datetime, DateTime::new(2007, 1, 1, 8, 0, 0).unwrap());
+/// assert_eq!(datetime.day_of_week(), DayOfWeek::Monday);
+/// assert_eq!(datetime.to_string(), "2007-01-01T08:00:00");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    // Seconds since 1970-01-01T00:00:00
+    seconds: i64,
+}
+
+impl DateTime {
+    /// Creates a date and time. Returns an error when the date or the time does not exist.
+    pub fn new(
+        year: i32,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: u32,
+    ) -> Result<Self, InvalidOperationError> {
+        let invalid = |message: String| Err(InvalidOperationError { message });
+
+        if !(1..=9999).contains(&year) {
+            return invalid(format!(
+                "The year must be between 1 and 9999, found {}",
+                year
+            ));
+        }
+        if !(1..=12).contains(&month) {
+            return invalid(format!(
+                "The month must be between 1 and 12, found {}",
+                month
+            ));
+        }
+        let last_day = days_in_month(year, month);
+        if day < 1 || day > last_day {
+            return invalid(format!(
+                "The day must be between 1 and {} for {:04}-{:02}, found {}",
+                last_day, year, month, day
+            ));
+        }
+        if hour > 23 || minute > 59 || second > 59 {
+            return invalid(format!(
+                "The time {:02}:{:02}:{:02} does not exist",
+                hour, minute, second
+            ));
+        }
+
+        let seconds_of_day = (hour * 3600 + minute * 60 + second) as i64;
+        Ok(Self {
+            seconds: days_from_civil(year, month, day) * SECONDS_PER_DAY + seconds_of_day,
+        })
+    }
+
+    /// The year, between 1 and 9999.
+    pub fn year(&self) -> i32 {
+        civil_from_days(self.days()).0
+    }
+
+    /// The month, between 1 and 12.
+    pub fn month(&self) -> u32 {
+        civil_from_days(self.days()).1
+    }
+
+    /// The day of the month, between 1 and 31.
+    pub fn day(&self) -> u32 {
+        civil_from_days(self.days()).2
+    }
+
+    /// The hour, between 0 and 23.
+    pub fn hour(&self) -> u32 {
+        (self.seconds_of_day() / 3600) as u32
+    }
+
+    /// The minute, between 0 and 59.
+    pub fn minute(&self) -> u32 {
+        (self.seconds_of_day() / 60 % 60) as u32
+    }
+
+    /// The second, between 0 and 59.
+    pub fn second(&self) -> u32 {
+        (self.seconds_of_day() % 60) as u32
+    }
+
+    /// The day of the week.
+    pub fn day_of_week(&self) -> DayOfWeek {
+        DAYS_OF_WEEK[day_of_week(self.days())]
+    }
+
+    // Days since 1970-01-01.
+    pub(crate) fn days(&self) -> i64 {
+        self.seconds.div_euclid(SECONDS_PER_DAY)
+    }
+
+    pub(crate) fn seconds_of_day(&self) -> i64 {
+        self.seconds.rem_euclid(SECONDS_PER_DAY)
+    }
+
+    // Returns `None` when the result is outside of the years 1 to 9999.
+    pub(crate) fn from_days(days: i64, seconds_of_day: i64) -> Option<Self> {
+        Self::from_timestamp(
+            days.checked_mul(SECONDS_PER_DAY)?
+                .checked_add(seconds_of_day)?,
+        )
+    }
+
+    pub(crate) fn add_seconds(&self, seconds: i64) -> Option<Self> {
+        Self::from_timestamp(self.seconds.checked_add(seconds)?)
+    }
+
+    pub(crate) fn seconds_since(&self, other: &DateTime) -> i64 {
+        self.seconds - other.seconds
+    }
+
+    // Clamps the result to the years 1 to 9999.
+    pub(crate) fn saturating_add_seconds(&self, seconds: i64) -> Self {
+        Self {
+            seconds: self
+                .seconds
+                .saturating_add(seconds)
+                .clamp(MIN_SECONDS, MAX_SECONDS),
+        }
+    }
+
+    // Seconds since 1970-01-01T00:00:00
+    #[cfg(feature = "chrono")]
+    pub(crate) fn timestamp(&self) -> i64 {
+        self.seconds
+    }
+
+    // Returns `None` when the seconds since 1970-01-01T00:00:00 are outside of the years 1 to 9999.
+    pub(crate) fn from_timestamp(seconds: i64) -> Option<Self> {
+        (MIN_SECONDS..=MAX_SECONDS)
+            .contains(&seconds)
+            .then_some(Self { seconds })
+    }
+
+    // Clamps the seconds since 1970-01-01T00:00:00 to the years 1 to 9999.
+    pub(crate) fn saturating_from_timestamp(seconds: i64) -> Self {
+        Self {
+            seconds: seconds.clamp(MIN_SECONDS, MAX_SECONDS),
+        }
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (year, month, day) = civil_from_days(self.days());
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            self.hour(),
+            self.minute(),
+            self.second()
+        )
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = ParseError;
+
+    /// Parses a date and time in the `YYYY-MM-DDTHH:MM:SS` format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (datetime, length) = parse_datetime(s)?;
+        if length != s.len() {
+            return Err(datetime_error(
+                s,
+                length,
+                "unexpected characters after the seconds",
+            ));
+        }
+        Ok(datetime)
+    }
+}
+
+/// A date and time on the wall clock of a time zone, with its offset from UTC.
+///
+/// It is displayed in the format of a boundary synchronized across time zones, ie:
+/// `2023-03-26T03:00:00+02:00`.
+///
+/// # Example
+/// ```
+/// use planif::datetime::{DateTime, ZonedDateTime};
+///
+/// let local = DateTime::new(2023, 3, 26, 3, 0, 0).unwrap();
+/// let zoned = ZonedDateTime::new(local, 2 * 3600);
+/// assert_eq!(zoned.utc(), DateTime::new(2023, 3, 26, 1, 0, 0).unwrap());
+/// assert_eq!(zoned.to_string(), "2023-03-26T03:00:00+02:00");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ZonedDateTime {
+    local: DateTime,
+    offset: i32,
+}
+
+impl ZonedDateTime {
+    /// Creates a date and time on a wall clock which is `offset` seconds ahead of UTC.
+    pub fn new(local: DateTime, offset: i32) -> Self {
+        Self { local, offset }
+    }
+
+    /// The date and time on the wall clock.
+    pub fn local(&self) -> DateTime {
+        self.local
+    }
+
+    /// The offset from UTC in seconds, positive east of Greenwich.
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+
+    /// The date and time in UTC.
+    pub fn utc(&self) -> DateTime {
+        self.local.saturating_add_seconds(-self.offset as i64)
+    }
+}
+
+impl fmt::Display for ZonedDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.offset < 0 { '-' } else { '+' };
+        let offset = self.offset.unsigned_abs();
+        write!(
+            f,
+            "{}{}{:02}:{:02}",
+            self.local,
+            sign,
+            offset / 3600,
+            offset / 60 % 60
+        )
+    }
+}
+
+/// The start or end boundary of a trigger.
+///
+/// The Task Scheduler reads a boundary without a UTC offset on the wall clock of the computer
+/// running the task, a daily trigger then runs at the same local time on every computer and across
+/// daylight saving time transitions. A boundary with a UTC offset is synchronized across time
+/// zones: the trigger runs at the same instant on every computer.
+///
+/// # Example
+/// ```
+/// use planif::datetime::{Boundary, DateTime};
+/// use planif::time_zone::FixedOffset;
+///
+/// let local = DateTime::new(2023, 1, 1, 8, 0, 0).unwrap();
+/// assert_eq!(Boundary::Local(local).to_string(), "2023-01-01T08:00:00");
+///
+/// let eastern = FixedOffset::east(-5 * 3600);
+/// let synchronized = Boundary::synchronized(local, &eastern);
+/// assert_eq!(synchronized.to_string(), "2023-01-01T08:00:00-05:00");
+///
+/// let boundary: Boundary = "2023-01-01T08:00:00.000-05:00".parse().unwrap();
+/// assert_eq!(boundary, synchronized);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Boundary {
+    /// A date and time on the wall clock of the computer running the task.
+    Local(DateTime),
+    /// A date and time synchronized across time zones.
+    Synchronized(ZonedDateTime),
+}
+
+impl Boundary {
+    /// Creates a boundary synchronized across time zones from a date and time on the wall clock of
+    /// `zone`. See [TimeZone::at_local] for the dates and times skipped or repeated by a daylight
+    /// saving time transition.
+    pub fn synchronized<Z: TimeZone + ?Sized>(local: DateTime, zone: &Z) -> Self {
+        Boundary::Synchronized(zone.at_local(&local))
+    }
+
+    /// Returns the date and time of the boundary in `zone`, which is the time zone of the computer
+    /// running the task for a local boundary.
+    pub fn resolve<Z: TimeZone + ?Sized>(&self, zone: &Z) -> ZonedDateTime {
+        match self {
+            Boundary::Local(local) => zone.at_local(local),
+            Boundary::Synchronized(zoned) => zone.at_utc(&zoned.utc()),
+        }
+    }
+
+    /// Whether the boundary is after `other`, on every computer. A local boundary may be up to 14
+    /// hours before or after a synchronized boundary written with the same date and time.
+    pub fn is_after(&self, other: &Boundary) -> bool {
+        const MAX_OFFSET: i64 = 14 * 3600;
+        let bounds = |boundary: &Boundary| match boundary {
+            Boundary::Local(local) => (
+                local.saturating_add_seconds(-MAX_OFFSET),
+                local.saturating_add_seconds(MAX_OFFSET),
+            ),
+            Boundary::Synchronized(zoned) => (zoned.utc(), zoned.utc()),
+        };
+
+        match (self, other) {
+            (Boundary::Local(local), Boundary::Local(other)) => local > other,
+            _ => bounds(self).0 > bounds(other).1,
+        }
+    }
+}
+
+impl fmt::Display for Boundary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Boundary::Local(local) => local.fmt(f),
+            Boundary::Synchronized(zoned) => zoned.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Boundary {
+    type Err = ParseError;
+
+    /// Parses a boundary such as `2023-01-01T08:00:00`, `2023-01-01T08:00:00Z` or
+    /// `2023-01-01T08:00:00+02:00`. Fractional seconds are accepted and truncated.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match parse_boundary(s)? {
+            (local, None) => Boundary::Local(local),
+            (local, Some(offset)) => Boundary::Synchronized(ZonedDateTime::new(local, offset)),
+        })
+    }
+}
+
+/// A value which can be used as the start or end boundary of a trigger, see
+/// [start_boundary](crate::schedule_builder::ScheduleBuilder::start_boundary).
+///
+/// It is implemented for strings in the format accepted by the Task Scheduler, for [Boundary],
+/// [DateTime] (a local boundary) and [ZonedDateTime], for `chrono::NaiveDateTime` and
+/// `chrono::DateTime` with the `chrono` feature, and for `time::PrimitiveDateTime` and
+/// `time::OffsetDateTime` with the `time` feature.
+pub trait IntoBoundary {
+    /// Converts the value to a boundary, or returns an error when it is not a valid boundary.
+    fn into_boundary(self) -> Result<Boundary, Error>;
+}
+
+impl IntoBoundary for Boundary {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        Ok(self)
+    }
+}
+
+impl IntoBoundary for DateTime {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        Ok(Boundary::Local(self))
+    }
+}
+
+impl IntoBoundary for ZonedDateTime {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        Ok(Boundary::Synchronized(self))
+    }
+}
+
+impl IntoBoundary for &str {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        Ok(self.parse()?)
+    }
+}
+
+impl IntoBoundary for &String {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        self.as_str().into_boundary()
+    }
+}
+
+impl IntoBoundary for String {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        self.as_str().into_boundary()
+    }
+}
+
+// Fractional seconds are truncated, dates outside of the years 1 to 9999 are rejected.
+#[cfg(feature = "chrono")]
+fn from_chrono(datetime: &chrono::NaiveDateTime) -> Result<DateTime, Error> {
+    use chrono::{Datelike, Timelike};
+    let (year, month, day) = (datetime.year(), datetime.month(), datetime.day());
+    let (hour, minute, second) = (datetime.hour(), datetime.minute(), datetime.second());
+    DateTime::new(year, month, day, hour, minute, second).map_err(conversion_error)
+}
+
+// Fractional seconds are truncated, dates outside of the years 1 to 9999 are rejected.
+#[cfg(feature = "time")]
+fn from_time(datetime: &time::PrimitiveDateTime) -> Result<DateTime, Error> {
+    let (year, month, day) = (
+        datetime.year(),
+        datetime.month() as u32,
+        datetime.day() as u32,
+    );
+    let (hour, minute, second) = datetime.as_hms();
+    DateTime::new(year, month, day, hour as u32, minute as u32, second as u32)
+        .map_err(conversion_error)
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn conversion_error(error: InvalidOperationError) -> Error {
+    Error::Conversion(crate::error::ConversionError {
+        message: error.message,
+    })
+}
+
+#[cfg(feature = "chrono")]
+impl IntoBoundary for chrono::NaiveDateTime {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        Ok(Boundary::Local(from_chrono(&self)?))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> IntoBoundary for chrono::DateTime<Tz> {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        use chrono::Offset;
+        let local = from_chrono(&self.naive_local())?;
+        let offset = self.offset().fix().local_minus_utc();
+        Ok(Boundary::Synchronized(ZonedDateTime::new(local, offset)))
+    }
+}
+
+#[cfg(feature = "time")]
+impl IntoBoundary for time::PrimitiveDateTime {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        Ok(Boundary::Local(from_time(&self)?))
+    }
+}
+
+#[cfg(feature = "time")]
+impl IntoBoundary for time::OffsetDateTime {
+    fn into_boundary(self) -> Result<Boundary, Error> {
+        let local = from_time(&time::PrimitiveDateTime::new(self.date(), self.time()))?;
+        Ok(Boundary::Synchronized(ZonedDateTime::new(
+            local,
+            self.offset().whole_seconds(),
+        )))
+    }
+}
+
+/// Parses a trigger boundary: a date and time, optionally followed by fractional seconds and by
+/// `Z` or a UTC offset such as `+02:00`. Returns the date and time on the wall clock, the fraction
+/// is truncated, and the UTC offset in seconds.
+pub(crate) fn parse_boundary(s: &str) -> Result<(DateTime, Option<i32>), ParseError> {
+    let (datetime, mut position) = parse_datetime(s)?;
+    let bytes = s.as_bytes();
+
+    if bytes.get(position) == Some(&b'.') {
+        let digits = bytes[position + 1..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return Err(datetime_error(
+                s,
+                position + 1,
+                "expected fractional seconds",
+            ));
+        }
+        position += 1 + digits;
+    }
+
+    let offset = match bytes.get(position) {
+        None => None,
+        Some(b'Z') => {
+            position += 1;
+            Some(0)
+        }
+        Some(sign @ (b'+' | b'-')) => {
+            let hours = number(s, position + 1, 2, 0..=14)?;
+            expect(s, position + 3, b':')?;
+            let minutes = number(s, position + 4, 2, 0..=59)?;
+            let offset = (hours * 3600 + minutes * 60) as i32;
+            position += 6;
+            Some(if *sign == b'-' { -offset } else { offset })
+        }
+        Some(_) => return Err(datetime_error(s, position, "expected Z or a UTC offset")),
+    };
+
+    if position != s.len() {
+        return Err(datetime_error(
+            s,
+            position,
+            "unexpected characters after the UTC offset",
+        ));
+    }
+    Ok((datetime, offset))
+}
+
+// Parses `YYYY-MM-DDTHH:MM:SS` at the start of `s` and returns the length which was read.
+fn parse_datetime(s: &str) -> Result<(DateTime, usize), ParseError> {
+    let year = number(s, 0, 4, 1..=9999)?;
+    expect(s, 4, b'-')?;
+    let month = number(s, 5, 2, 1..=12)?;
+    expect(s, 7, b'-')?;
+    let day = number(s, 8, 2, 1..=31)?;
+    expect(s, 10, b'T')?;
+    let hour = number(s, 11, 2, 0..=23)?;
+    expect(s, 13, b':')?;
+    let minute = number(s, 14, 2, 0..=59)?;
+    expect(s, 16, b':')?;
+    let second = number(s, 17, 2, 0..=59)?;
+
+    let datetime = DateTime::new(year as i32, month, day, hour, minute, second)
+        .map_err(|e| datetime_error(s, 8, &e.message))?;
+    Ok((datetime, 19))
+}
+
+fn number(
+    s: &str,
+    position: usize,
+    digits: usize,
+    range: std::ops::RangeInclusive<u32>,
+) -> Result<u32, ParseError> {
+    let value = s
+        .get(position..position + digits)
+        .filter(|d| d.bytes().all(|c| c.is_ascii_digit()))
+        .ok_or_else(|| datetime_error(s, position, &format!("expected {} digits", digits)))?;
+
+    let value = value.parse().unwrap_or(0);
+    if !range.contains(&value) {
+        return Err(datetime_error(
+            s,
+            position,
+            &format!(
+                "expected a value between {} and {}, found {}",
+                range.start(),
+                range.end(),
+                value
+            ),
+        ));
+    }
+    Ok(value)
+}
+
+fn expect(s: &str, position: usize, c: u8) -> Result<(), ParseError> {
+    if s.as_bytes().get(position) == Some(&c) {
+        Ok(())
+    } else {
+        Err(datetime_error(
+            s,
+            position,
+            &format!("expected '{}'", c as char),
+        ))
+    }
+}
+
+fn datetime_error(s: &str, position: usize, message: &str) -> ParseError {
+    ParseError {
+        message: format!("Invalid date and time '{}': {}", s, message),
+        position: Some(position),
+    }
+}
+
+const DAYS_OF_WEEK: [DayOfWeek; 7] = [
+    DayOfWeek::Sunday,
+    DayOfWeek::Monday,
+    DayOfWeek::Tuesday,
+    DayOfWeek::Wednesday,
+    DayOfWeek::Thursday,
+    DayOfWeek::Friday,
+    DayOfWeek::Saturday,
+];
+
+// The day of the week of the days since 1970-01-01, from 0 (Sunday) to 6 (Saturday).
+pub(crate) fn day_of_week(days: i64) -> usize {
+    // 1970-01-01 is a Thursday
+    (days + 4).rem_euclid(7) as usize
+}
+
+pub(crate) fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 of a date of the proleptic Gregorian calendar.
+// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+pub(crate) fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// The year, month and day of the days since 1970-01-01.
+// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub(crate) fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn civil_days_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+
+        for days in (MIN_SECONDS / SECONDS_PER_DAY..=MAX_SECONDS / SECONDS_PER_DAY).step_by(97) {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+    }
+
+    #[test]
+    fn parse_datetime_values() {
+        let datetime: DateTime = "2024-02-29T23:59:59".parse().unwrap();
+        assert_eq!(
+            (datetime.year(), datetime.month(), datetime.day()),
+            (2024, 2, 29)
+        );
+        assert_eq!(
+            (datetime.hour(), datetime.minute(), datetime.second()),
+            (23, 59, 59)
+        );
+        assert_eq!(datetime.day_of_week(), DayOfWeek::Thursday);
+
+        assert_eq!(
+            "2023-02-29T08:00:00"
+                .parse::<DateTime>()
+                .unwrap_err()
+                .position,
+            Some(8)
+        );
+        assert_eq!(
+            "2023-01-01 08:00:00"
+                .parse::<DateTime>()
+                .unwrap_err()
+                .position,
+            Some(10)
+        );
+        assert!("2023-01-01T08:00:00Z".parse::<DateTime>().is_err());
+    }
+
+    #[test]
+    fn parse_boundaries() {
+        let datetime = DateTime::new(2023, 1, 1, 8, 0, 0).unwrap();
+        assert_eq!(
+            parse_boundary("2023-01-01T08:00:00").unwrap(),
+            (datetime, None)
+        );
+        assert_eq!(
+            parse_boundary("2023-01-01T08:00:00Z").unwrap(),
+            (datetime, Some(0))
+        );
+        assert_eq!(
+            parse_boundary("2023-01-01T08:00:00.123456-05:30").unwrap(),
+            (datetime, Some(-19_800))
+        );
+        assert!(parse_boundary("2023-01-01T08:00:00.Z").is_err());
+        assert!(parse_boundary("2023-01-01T08:00:00+0200").is_err());
+    }
+
+    #[test]
+    fn boundary_order() {
+        let boundary = |s: &str| s.parse::<Boundary>().unwrap();
+
+        assert!(boundary("2023-01-01T08:00:01").is_after(&boundary("2023-01-01T08:00:00")));
+        assert!(!boundary("2023-01-01T08:00:00").is_after(&boundary("2023-01-01T08:00:00")));
+        assert!(boundary("2023-01-01T08:00:00Z").is_after(&boundary("2023-01-01T09:00:00+02:00")));
+        // A local boundary may be anywhere between UTC-14:00 and UTC+14:00
+        assert!(!boundary("2023-01-01T20:00:00Z").is_after(&boundary("2023-01-01T08:00:00")));
+        assert!(boundary("2023-01-02T00:00:00Z").is_after(&boundary("2023-01-01T08:00:00")));
+    }
+}
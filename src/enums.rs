@@ -1,3 +1,6 @@
+use crate::error::{Error, InvalidOperationError};
+use std::ops::{BitOr, BitOrAssign};
+
 #[derive(Debug, Clone, Copy)]
 /// Represents the day of the month.
 pub enum DayOfMonth {
@@ -11,16 +14,15 @@ impl From<DayOfMonth> for i32 {
     /// Shifts the DayOfMonth::Day(x) to the decimal value
     ///
     /// See Remarks table: <https://docs.microsoft.com/en-us/windows/win32/taskschd/monthlytrigger-daysofmonth>
-    #[allow(overflowing_literals)]
     fn from(day: DayOfMonth) -> Self {
         match day {
             DayOfMonth::Day(i) => 1 << (i - 1),
-            DayOfMonth::Last => 0x80000000,
+            DayOfMonth::Last => i32::MIN,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// Day of the week.
 pub enum DayOfWeek {
     /// Sunday (0x01)
@@ -39,7 +41,7 @@ pub enum DayOfWeek {
     Saturday = 1 << 6,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// Month of the year.
 pub enum Month {
     /// January (0x01)
@@ -74,9 +76,29 @@ impl From<Month> for i16 {
     }
 }
 
-/// Task Creation constants  
+/// The kind of session state change which starts a
+/// [session state change trigger](crate::schedule_builder::ScheduleBuilder#impl-ScheduleBuilder<SessionStateChange>).
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/api/taskschd/ne-taskschd-task_session_state_change_type>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateChange {
+    /// A user session is connected to the local computer.
+    ConsoleConnect = 1,
+    /// A user session is disconnected from the local computer.
+    ConsoleDisconnect = 2,
+    /// A user session is connected to a remote computer.
+    RemoteConnect = 3,
+    /// A user session is disconnected from a remote computer.
+    RemoteDisconnect = 4,
+    /// The user's workstation is locked.
+    SessionLock = 7,
+    /// The user's workstation is unlocked.
+    SessionUnlock = 8,
+}
+
+/// Task Creation constants, combined in [CreationFlags] with `|`.
 /// see <https://docs.microsoft.com/en-us/windows/win32/api/taskschd/ne-taskschd-task_creation>
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TaskCreationFlags {
     /// The Task Scheduler service registers the task as a new task.
     Create = 2,
@@ -89,11 +111,11 @@ pub enum TaskCreationFlags {
     /// for the context principal. When the [register method](crate::schedule::Schedule::register) function is called with this flag to
     /// update a task, the Task Scheduler service does not add the ACE for the new context principal
     /// and does not remove the ACE from the old context principal.
-    DontAddPrincipalAce = 10,
+    DontAddPrincipalAce = 0x10,
     /// The Task Scheduler service creates the task, but ignores the registration triggers in the task.
     /// By ignoring the registration triggers, the task will not execute when it is registered
     /// unless a time-based trigger causes it to execute on registration.
-    IgnoreRegistrationTriggers = 20,
+    IgnoreRegistrationTriggers = 0x20,
     /// The Task Scheduler service registers the task as an updated version of an existing task.
     /// When a task with a registration trigger is updated, the task will execute after the update occurs.
     Update = 4,
@@ -103,7 +125,119 @@ pub enum TaskCreationFlags {
     ValidateOnly = 1,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A combination of [TaskCreationFlags] used to [register](crate::schedule::Schedule::register) a
+/// schedule.
+///
+/// # Example
+/// ```
+/// use planif::enums::{CreationFlags, TaskCreationFlags};
+///
+/// let flags = TaskCreationFlags::CreateOrUpdate | TaskCreationFlags::Disable;
+/// assert!(flags.contains(TaskCreationFlags::Update));
+/// assert_eq!(flags.bits(), 0xe);
+/// assert!(flags.validate().is_ok());
+///
+/// let flags = TaskCreationFlags::ValidateOnly | TaskCreationFlags::Create;
+/// assert!(flags.validate().is_err());
+/// assert!(CreationFlags::from_bits(0x3).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CreationFlags {
+    bits: i32,
+}
+
+impl CreationFlags {
+    const ALL: i32 = 0x3f;
+
+    /// Creates a combination without flags.
+    pub const fn empty() -> Self {
+        Self { bits: 0 }
+    }
+
+    /// The `TASK_CREATION` value passed to the Task Scheduler.
+    pub const fn bits(&self) -> i32 {
+        self.bits
+    }
+
+    /// Decodes a `TASK_CREATION` value, rejecting unknown flags and illegal combinations.
+    pub fn from_bits(bits: i32) -> Result<Self, Error> {
+        if bits & !Self::ALL != 0 {
+            return Err(Error::InvalidOperation(InvalidOperationError {
+                message: format!("{:#x} is not a combination of task creation flags", bits),
+            }));
+        }
+        let flags = Self { bits };
+        flags.validate()?;
+        Ok(flags)
+    }
+
+    /// Whether every bit of the flag is set, ie: [CreateOrUpdate](TaskCreationFlags::CreateOrUpdate)
+    /// is only contained when both [Create](TaskCreationFlags::Create) and
+    /// [Update](TaskCreationFlags::Update) are set.
+    pub const fn contains(&self, flag: TaskCreationFlags) -> bool {
+        self.bits & flag as i32 == flag as i32
+    }
+
+    /// Adds the flag to the combination.
+    pub fn insert(&mut self, flag: TaskCreationFlags) {
+        self.bits |= flag as i32;
+    }
+
+    /// Checks that the combination is accepted by the Task Scheduler:
+    /// [ValidateOnly](TaskCreationFlags::ValidateOnly) cannot be combined with
+    /// [Create](TaskCreationFlags::Create) or [Update](TaskCreationFlags::Update).
+    pub fn validate(&self) -> Result<(), Error> {
+        let create_or_update = TaskCreationFlags::CreateOrUpdate as i32;
+        if self.contains(TaskCreationFlags::ValidateOnly) && self.bits & create_or_update != 0 {
+            return Err(Error::InvalidOperation(InvalidOperationError {
+                message: "ValidateOnly cannot be combined with Create, Update or CreateOrUpdate"
+                    .to_string(),
+            }));
+        }
+        Ok(())
+    }
+}
+
+impl From<TaskCreationFlags> for CreationFlags {
+    fn from(flag: TaskCreationFlags) -> Self {
+        Self { bits: flag as i32 }
+    }
+}
+
+impl BitOr for TaskCreationFlags {
+    type Output = CreationFlags;
+
+    fn bitor(self, other: Self) -> CreationFlags {
+        CreationFlags::from(self) | other
+    }
+}
+
+impl BitOr<TaskCreationFlags> for CreationFlags {
+    type Output = Self;
+
+    fn bitor(mut self, flag: TaskCreationFlags) -> Self {
+        self.insert(flag);
+        self
+    }
+}
+
+impl BitOr for CreationFlags {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            bits: self.bits | other.bits,
+        }
+    }
+}
+
+impl BitOrAssign<TaskCreationFlags> for CreationFlags {
+    fn bitor_assign(&mut self, flag: TaskCreationFlags) {
+        self.insert(flag);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// The week of the month
 pub enum WeekOfMonth {
     /// First (0x01)
@@ -1,4 +1,4 @@
-use std::{error::Error, fmt};
+use std::fmt;
 
 #[derive(Debug)]
 /// The `InvalidOperationError` identifies errors where operations (function calls) are made with
@@ -8,7 +8,7 @@ pub struct InvalidOperationError {
     pub message: String,
 }
 
-impl Error for InvalidOperationError {}
+impl std::error::Error for InvalidOperationError {}
 
 impl fmt::Display for InvalidOperationError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -24,10 +24,451 @@ pub struct RequiredPropertyError {
     pub message: String,
 }
 
-impl Error for RequiredPropertyError {}
+impl std::error::Error for RequiredPropertyError {}
 
 impl fmt::Display for RequiredPropertyError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Required Property Error: {}", self.message)
     }
 }
+
+#[derive(Debug)]
+/// The `ParseError` identifies errors when reading a value or a document which is not in the
+/// expected format.
+pub struct ParseError {
+    /// A message with additional information on the error.
+    pub message: String,
+    /// The byte offset in the parsed value where the error was found, when it is known.
+    pub position: Option<usize>,
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "Parse Error: {} (at position {})", self.message, position),
+            None => write!(f, "Parse Error: {}", self.message),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// The `ConversionError` identifies errors when converting a [Duration](crate::settings::Duration)
+/// from or to the durations of the standard library, `chrono` or `time`.
+pub struct ConversionError {
+    /// A message with additional information on the error.
+    pub message: String,
+}
+
+impl std::error::Error for ConversionError {}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Conversion Error: {}", self.message)
+    }
+}
+
+#[derive(Debug)]
+/// The `ValidationError` identifies values which are rejected by the Windows Task Scheduler. They
+/// are found when a [Schedule](crate::schedule::Schedule) is built, before it is registered.
+pub struct ValidationError {
+    /// The path of the invalid field in the [TaskDefinition](crate::task_definition::TaskDefinition),
+    /// for example `triggers[0].repetition.interval`.
+    pub field: String,
+    /// A message with additional information on the error.
+    pub message: String,
+}
+
+impl std::error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Validation Error: {}: {}", self.field, self.message)
+    }
+}
+
+#[derive(Debug)]
+/// The `SecretError` identifies errors when a [SecretProvider](crate::credentials::SecretProvider)
+/// cannot read a password. The message never contains the password.
+pub struct SecretError {
+    /// A message with additional information on the error.
+    pub message: String,
+}
+
+impl std::error::Error for SecretError {}
+
+impl fmt::Display for SecretError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret Error: {}", self.message)
+    }
+}
+
+/// The errors returned by planif.
+///
+/// # Example
+/// ```
+/// use planif::com::ComRuntime;
+/// use planif::schedule_builder::ScheduleBuilder;
+/// use planif::Error;
+///
+/// let com = ComRuntime::new().unwrap();
+/// let result = ScheduleBuilder::new(&com).unwrap()
+///     .create_daily()
+///     .build();
+///
+/// assert!(matches!(result, Err(Error::InvalidOperation(_))));
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    /// An operation was made with invalid or incomplete data.
+    InvalidOperation(InvalidOperationError),
+    /// A required builder function has not been called.
+    RequiredProperty(RequiredPropertyError),
+    /// A value or a document is not in the expected format.
+    Parse(ParseError),
+    /// A duration cannot be converted.
+    Conversion(ConversionError),
+    /// A value is rejected by the Task Scheduler.
+    Validation(ValidationError),
+    /// A call to the Task Scheduler failed.
+    TaskScheduler(TaskSchedulerError),
+    /// A password cannot be read.
+    Secret(SecretError),
+}
+
+impl Error {
+    /// Returns the decoded HRESULT when the error was returned by the Task Scheduler.
+    pub fn code(&self) -> Option<ErrorCode> {
+        match self {
+            Error::TaskScheduler(e) => Some(e.code),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidOperation(e) => Some(e),
+            Error::RequiredProperty(e) => Some(e),
+            Error::Parse(e) => Some(e),
+            Error::Conversion(e) => Some(e),
+            Error::Validation(e) => Some(e),
+            Error::TaskScheduler(e) => Some(e),
+            Error::Secret(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidOperation(e) => e.fmt(f),
+            Error::RequiredProperty(e) => e.fmt(f),
+            Error::Parse(e) => e.fmt(f),
+            Error::Conversion(e) => e.fmt(f),
+            Error::Validation(e) => e.fmt(f),
+            Error::TaskScheduler(e) => e.fmt(f),
+            Error::Secret(e) => e.fmt(f),
+        }
+    }
+}
+
+impl From<InvalidOperationError> for Error {
+    fn from(e: InvalidOperationError) -> Self {
+        Error::InvalidOperation(e)
+    }
+}
+
+impl From<RequiredPropertyError> for Error {
+    fn from(e: RequiredPropertyError) -> Self {
+        Error::RequiredProperty(e)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<ConversionError> for Error {
+    fn from(e: ConversionError) -> Self {
+        Error::Conversion(e)
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(e: ValidationError) -> Self {
+        Error::Validation(e)
+    }
+}
+
+impl From<TaskSchedulerError> for Error {
+    fn from(e: TaskSchedulerError) -> Self {
+        Error::TaskScheduler(e)
+    }
+}
+
+impl From<SecretError> for Error {
+    fn from(e: SecretError) -> Self {
+        Error::Secret(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The `TaskSchedulerError` identifies errors returned by the Windows Task Scheduler or the COM.
+pub struct TaskSchedulerError {
+    /// The operation which failed, for example `ITaskFolder::RegisterTaskDefinition`.
+    pub operation: String,
+    /// The decoded HRESULT.
+    pub code: ErrorCode,
+    /// The message returned by Windows, empty when there is none.
+    pub message: String,
+}
+
+impl TaskSchedulerError {
+    /// Creates an error for a failed `operation` from its HRESULT.
+    pub fn new(operation: &str, hresult: i32, message: &str) -> Self {
+        Self {
+            operation: operation.to_string(),
+            code: ErrorCode::from_hresult(hresult),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl std::error::Error for TaskSchedulerError {}
+
+impl fmt::Display for TaskSchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = self.message.trim_end();
+        if message.is_empty() {
+            return write!(f, "Task Scheduler Error: {} failed with {}", self.operation, self.code);
+        }
+
+        // Prefer the message returned by Windows, it is localized
+        write!(
+            f,
+            "Task Scheduler Error: {} failed with {} (0x{:08X}): {}",
+            self.operation,
+            self.code.name().unwrap_or("HRESULT"),
+            self.code.hresult(),
+            message
+        )
+    }
+}
+
+/// HRESULTs returned by the Task Scheduler.
+///
+/// The decoding does not depend on Windows, codes can be decoded on any platform.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/task-scheduler-error-and-success-constants>
+///
+/// # Example
+/// ```
+/// use planif::error::ErrorCode;
+///
+/// let code = ErrorCode::from_hresult(0x8004131Au32 as i32);
+/// assert_eq!(code, ErrorCode::MalformedXml);
+/// assert_eq!(code.hresult(), 0x8004131Au32 as i32);
+/// assert_eq!(code.to_string(), "SCHED_E_MALFORMEDXML (0x8004131A): The task XML is malformed.");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// SCHED_E_TRIGGER_NOT_FOUND
+    TriggerNotFound,
+    /// SCHED_E_TASK_NOT_READY
+    TaskNotReady,
+    /// SCHED_E_TASK_NOT_RUNNING
+    TaskNotRunning,
+    /// SCHED_E_SERVICE_NOT_INSTALLED
+    ServiceNotInstalled,
+    /// SCHED_E_CANNOT_OPEN_TASK
+    CannotOpenTask,
+    /// SCHED_E_INVALID_TASK
+    InvalidTask,
+    /// SCHED_E_ACCOUNT_INFORMATION_NOT_SET
+    AccountInformationNotSet,
+    /// SCHED_E_ACCOUNT_NAME_NOT_FOUND
+    AccountNameNotFound,
+    /// SCHED_E_ACCOUNT_DBASE_CORRUPT
+    AccountDbaseCorrupt,
+    /// SCHED_E_NO_SECURITY_SERVICES
+    NoSecurityServices,
+    /// SCHED_E_UNKNOWN_OBJECT_VERSION
+    UnknownObjectVersion,
+    /// SCHED_E_UNSUPPORTED_ACCOUNT_OPTION
+    UnsupportedAccountOption,
+    /// SCHED_E_SERVICE_NOT_RUNNING
+    ServiceNotRunning,
+    /// SCHED_E_UNEXPECTEDNODE
+    UnexpectedNode,
+    /// SCHED_E_NAMESPACE
+    Namespace,
+    /// SCHED_E_INVALIDVALUE
+    InvalidValue,
+    /// SCHED_E_MISSINGNODE
+    MissingNode,
+    /// SCHED_E_MALFORMEDXML
+    MalformedXml,
+    /// SCHED_E_TOO_MANY_NODES
+    TooManyNodes,
+    /// SCHED_E_PAST_END_BOUNDARY
+    PastEndBoundary,
+    /// SCHED_E_ALREADY_RUNNING
+    AlreadyRunning,
+    /// SCHED_E_USER_NOT_LOGGED_ON
+    UserNotLoggedOn,
+    /// SCHED_E_INVALID_TASK_HASH
+    InvalidTaskHash,
+    /// SCHED_E_SERVICE_NOT_AVAILABLE
+    ServiceNotAvailable,
+    /// SCHED_E_SERVICE_TOO_BUSY
+    ServiceTooBusy,
+    /// SCHED_E_TASK_ATTEMPTED
+    TaskAttempted,
+    /// SCHED_E_TASK_DISABLED
+    TaskDisabled,
+    /// SCHED_E_TASK_NOT_V1_COMPAT
+    TaskNotV1Compat,
+    /// SCHED_E_START_ON_DEMAND
+    StartOnDemand,
+    /// SCHED_E_TASK_NOT_UBPM_COMPAT
+    TaskNotUbpmCompat,
+    /// SCHED_E_DEPRECATED_FEATURE_USED
+    DeprecatedFeatureUsed,
+    /// E_ACCESSDENIED
+    AccessDenied,
+    /// E_INVALIDARG
+    InvalidArgument,
+    /// HRESULT_FROM_WIN32(ERROR_FILE_NOT_FOUND), returned when a task or a folder does not exist.
+    NotFound,
+    /// HRESULT_FROM_WIN32(ERROR_PATH_NOT_FOUND)
+    PathNotFound,
+    /// HRESULT_FROM_WIN32(ERROR_ALREADY_EXISTS), returned when a task or a folder already exists.
+    AlreadyExists,
+    /// HRESULT_FROM_WIN32(ERROR_LOGON_FAILURE)
+    LogonFailure,
+    /// An HRESULT without a named variant.
+    Other(i32),
+}
+
+const ERROR_CODES: [(ErrorCode, u32, &str, &str); 37] = [
+    (ErrorCode::TriggerNotFound, 0x80041309, "SCHED_E_TRIGGER_NOT_FOUND", "A task's trigger is not found."),
+    (ErrorCode::TaskNotReady, 0x8004130A, "SCHED_E_TASK_NOT_READY", "One or more of the properties required to run this task have not been set."),
+    (ErrorCode::TaskNotRunning, 0x8004130B, "SCHED_E_TASK_NOT_RUNNING", "There is no running instance of the task."),
+    (ErrorCode::ServiceNotInstalled, 0x8004130C, "SCHED_E_SERVICE_NOT_INSTALLED", "The Task Scheduler service is not installed on this computer."),
+    (ErrorCode::CannotOpenTask, 0x8004130D, "SCHED_E_CANNOT_OPEN_TASK", "The task object could not be opened."),
+    (ErrorCode::InvalidTask, 0x8004130E, "SCHED_E_INVALID_TASK", "The object is either an invalid task object or is not a task object."),
+    (ErrorCode::AccountInformationNotSet, 0x8004130F, "SCHED_E_ACCOUNT_INFORMATION_NOT_SET", "No account information could be found in the Task Scheduler security database for the task indicated."),
+    (ErrorCode::AccountNameNotFound, 0x80041310, "SCHED_E_ACCOUNT_NAME_NOT_FOUND", "Unable to establish existence of the account specified."),
+    (ErrorCode::AccountDbaseCorrupt, 0x80041311, "SCHED_E_ACCOUNT_DBASE_CORRUPT", "Corruption was detected in the Task Scheduler security database; the database has been reset."),
+    (ErrorCode::NoSecurityServices, 0x80041312, "SCHED_E_NO_SECURITY_SERVICES", "Task Scheduler security services are available only on Windows NT."),
+    (ErrorCode::UnknownObjectVersion, 0x80041313, "SCHED_E_UNKNOWN_OBJECT_VERSION", "The task object version is either unsupported or invalid."),
+    (ErrorCode::UnsupportedAccountOption, 0x80041314, "SCHED_E_UNSUPPORTED_ACCOUNT_OPTION", "The task has been configured with an unsupported combination of account settings and run time options."),
+    (ErrorCode::ServiceNotRunning, 0x80041315, "SCHED_E_SERVICE_NOT_RUNNING", "The Task Scheduler service is not running."),
+    (ErrorCode::UnexpectedNode, 0x80041316, "SCHED_E_UNEXPECTEDNODE", "The task XML contains an unexpected node."),
+    (ErrorCode::Namespace, 0x80041317, "SCHED_E_NAMESPACE", "The task XML contains an element or attribute from an unexpected namespace."),
+    (ErrorCode::InvalidValue, 0x80041318, "SCHED_E_INVALIDVALUE", "The task XML contains a value which is incorrectly formatted or out of range."),
+    (ErrorCode::MissingNode, 0x80041319, "SCHED_E_MISSINGNODE", "The task XML is missing a required element or attribute."),
+    (ErrorCode::MalformedXml, 0x8004131A, "SCHED_E_MALFORMEDXML", "The task XML is malformed."),
+    (ErrorCode::TooManyNodes, 0x8004131D, "SCHED_E_TOO_MANY_NODES", "The task XML contains too many nodes of the same type."),
+    (ErrorCode::PastEndBoundary, 0x8004131E, "SCHED_E_PAST_END_BOUNDARY", "The task cannot be started after the trigger end boundary."),
+    (ErrorCode::AlreadyRunning, 0x8004131F, "SCHED_E_ALREADY_RUNNING", "An instance of this task is already running."),
+    (ErrorCode::UserNotLoggedOn, 0x80041320, "SCHED_E_USER_NOT_LOGGED_ON", "The task will not run because the user is not logged on."),
+    (ErrorCode::InvalidTaskHash, 0x80041321, "SCHED_E_INVALID_TASK_HASH", "The task image is corrupt or has been tampered with."),
+    (ErrorCode::ServiceNotAvailable, 0x80041322, "SCHED_E_SERVICE_NOT_AVAILABLE", "The Task Scheduler service is not available."),
+    (ErrorCode::ServiceTooBusy, 0x80041323, "SCHED_E_SERVICE_TOO_BUSY", "The Task Scheduler service is too busy to handle your request. Please try again later."),
+    (ErrorCode::TaskAttempted, 0x80041324, "SCHED_E_TASK_ATTEMPTED", "The Task Scheduler service attempted to run the task, but the task did not run due to one of the constraints in the task definition."),
+    (ErrorCode::TaskDisabled, 0x80041326, "SCHED_E_TASK_DISABLED", "The task is disabled."),
+    (ErrorCode::TaskNotV1Compat, 0x80041327, "SCHED_E_TASK_NOT_V1_COMPAT", "The task has properties that are not compatible with earlier versions of Windows."),
+    (ErrorCode::StartOnDemand, 0x80041328, "SCHED_E_START_ON_DEMAND", "The task settings do not allow the task to start on demand."),
+    (ErrorCode::TaskNotUbpmCompat, 0x80041329, "SCHED_E_TASK_NOT_UBPM_COMPAT", "The combination of properties that task is using is not compatible with the scheduling engine."),
+    (ErrorCode::DeprecatedFeatureUsed, 0x80041330, "SCHED_E_DEPRECATED_FEATURE_USED", "The task definition uses a deprecated feature."),
+    (ErrorCode::AccessDenied, 0x80070005, "E_ACCESSDENIED", "Access is denied."),
+    (ErrorCode::InvalidArgument, 0x80070057, "E_INVALIDARG", "One or more arguments are not valid."),
+    (ErrorCode::NotFound, 0x80070002, "ERROR_FILE_NOT_FOUND", "The task or folder cannot be found."),
+    (ErrorCode::PathNotFound, 0x80070003, "ERROR_PATH_NOT_FOUND", "The path cannot be found."),
+    (ErrorCode::AlreadyExists, 0x800700B7, "ERROR_ALREADY_EXISTS", "The task or folder already exists."),
+    (ErrorCode::LogonFailure, 0x8007052E, "ERROR_LOGON_FAILURE", "The user name or password is incorrect."),
+];
+
+impl ErrorCode {
+    /// Decodes an HRESULT.
+    pub fn from_hresult(hresult: i32) -> Self {
+        ERROR_CODES
+            .iter()
+            .find(|(_, value, _, _)| *value as i32 == hresult)
+            .map(|(code, _, _, _)| *code)
+            .unwrap_or(ErrorCode::Other(hresult))
+    }
+
+    /// Returns the HRESULT.
+    pub fn hresult(&self) -> i32 {
+        match self {
+            ErrorCode::Other(hresult) => *hresult,
+            _ => self.entry().map_or(0, |(_, value, _, _)| *value as i32),
+        }
+    }
+
+    /// Returns the name of the constant, for example `SCHED_E_MALFORMEDXML`, or `None` for
+    /// [Other](ErrorCode::Other) codes.
+    pub fn name(&self) -> Option<&'static str> {
+        self.entry().map(|(_, _, name, _)| *name)
+    }
+
+    /// Returns a description of the error, or `None` for [Other](ErrorCode::Other) codes.
+    pub fn description(&self) -> Option<&'static str> {
+        self.entry().map(|(_, _, _, description)| *description)
+    }
+
+    fn entry(&self) -> Option<&'static (ErrorCode, u32, &'static str, &'static str)> {
+        ERROR_CODES.iter().find(|(code, _, _, _)| code == self)
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.name(), self.description()) {
+            (Some(name), Some(description)) => {
+                write!(f, "{} (0x{:08X}): {}", name, self.hresult(), description)
+            }
+            _ => write!(f, "HRESULT 0x{:08X}", self.hresult()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_hresults() {
+        for (code, hresult, _, _) in ERROR_CODES {
+            assert_eq!(ErrorCode::from_hresult(hresult as i32), code);
+            assert_eq!(code.hresult(), hresult as i32);
+        }
+
+        let other = ErrorCode::from_hresult(0x80004005u32 as i32);
+        assert_eq!(other, ErrorCode::Other(0x80004005u32 as i32));
+        assert_eq!(other.description(), None);
+        assert_eq!(other.to_string(), "HRESULT 0x80004005");
+    }
+
+    #[test]
+    fn task_scheduler_error_display() {
+        let error = Error::from(TaskSchedulerError::new(
+            "ITaskFolder::RegisterTaskDefinition",
+            0x80070005u32 as i32,
+            "Access is denied.\r\n",
+        ));
+
+        assert_eq!(error.code(), Some(ErrorCode::AccessDenied));
+        assert_eq!(
+            error.to_string(),
+            "Task Scheduler Error: ITaskFolder::RegisterTaskDefinition failed with E_ACCESSDENIED (0x80070005): Access is denied."
+        );
+    }
+}
@@ -11,42 +11,89 @@
 //! - Monthly
 //! - MonthlyDOW
 //! - Registration
+//! - SessionStateChange
 //! - Time
 //! - Weekly
 //!
 //! ## Example
 //!
-//! ```rust
+//! ```rust,no_run
 //! use chrono::prelude::*;
-//! use planif::schedule::TaskCreationFlags;
+//! use planif::enums::TaskCreationFlags;
+//! use planif::schedule::TaskScheduler;
 //! use planif::schedule_builder::{Action, ScheduleBuilder};
 //!
 //! fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let sb = ScheduleBuilder::new().unwrap();
+//!     let ts = TaskScheduler::new()?;
+//!     let com = ts.get_com();
+//!     let sb = ScheduleBuilder::new(&com).unwrap();
 //!     sb.create_daily()
 //!         .author("planif")?
 //!         .description("Daily Trigger")?
 //!         .trigger("daily_trigger", true)?
 //!         .days_interval(1)?
 //!         .action(Action::new("test", "notepad.exe", "", ""))?
-//!         .start_boundary(&Local::now().to_rfc3339())?
+//!         .start_boundary(Local::now().to_rfc3339())?
 //!         .build()?
-//!         .register("TaskName", TaskCreationFlags::CreateOrUpdate as i32)?;
+//!         .register("TaskName", TaskCreationFlags::CreateOrUpdate)?;
 //!     Ok(())
 //! }
 //! ```
 //!
 //! For more examples, refer to the `planif/examples` folder. The folder contains code for creating each of the triggers.
+//!
+//! ## Errors
+//!
+//! Functions return a [planif::Error](Error). Errors returned by the Task Scheduler carry the
+//! failing operation and the decoded HRESULT, which can be matched with [ErrorCode](error::ErrorCode).
+//!
+//! ```rust,no_run
+//! use planif::enums::TaskCreationFlags;
+//! use planif::error::ErrorCode;
+//! # use planif::schedule::Schedule;
+//! # fn register(schedule: Schedule) {
+//! match schedule.register("TaskName", TaskCreationFlags::Create) {
+//!     Err(e) if e.code() == Some(ErrorCode::AccessDenied) => eprintln!("run as administrator"),
+//!     Err(e) => eprintln!("{}", e),
+//!     Ok(()) => {}
+//! }
+//! # }
+//! ```
 
+/// Sets of days, weeks and months of the calendar triggers.
+pub mod calendar;
+/// Clocks evaluating the relative boundaries of the triggers.
+pub mod clock;
+/// Detect the conflicts between the executions of tasks.
+pub mod conflict;
+/// Credentials and passwords used to register tasks.
+pub mod credentials;
+/// Dates and times of the trigger boundaries and occurrences.
+pub mod datetime;
 /// Enums used throughout the crate.
 pub mod enums;
 /// Errors used throughout the crate.
 pub mod error;
+/// Compute when the calendar triggers start a task.
+pub mod occurrence;
 /// Register scheduled tasks.
 pub mod schedule;
 /// Build different [Schedules](schedule::Schedule) for the Windows Task Scheduler.
 pub mod schedule_builder;
+/// Parse and validate the [security descriptors](sddl::SecurityDescriptor) of tasks and folders.
+pub mod sddl;
 /// Various settings available while building [Schedules](schedule::Schedule).
 pub mod settings;
+/// Simulate the executions of tasks over a date range.
+pub mod simulation;
+/// Platform independent model of the tasks built by the [schedule builders](schedule_builder).
+pub mod task_definition;
+mod task_service;
+/// Time zones used to compute the occurrences of triggers.
+pub mod time_zone;
+/// Task Scheduler XML documents.
+pub mod xml;
 /// Com
 pub mod com;
+
+pub use error::Error;
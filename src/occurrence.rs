@@ -0,0 +1,781 @@
+use crate::calendar::Months;
+use crate::datetime::{
+    civil_from_days, day_of_week, days_in_month, Boundary, DateTime, ZonedDateTime,
+};
+use crate::error::{Error, InvalidOperationError, RequiredPropertyError};
+use crate::task_definition::{validate_repetition, TaskDefinition, Trigger, TriggerKind};
+use crate::time_zone::TimeZone;
+
+// A 400 years cycle of the Gregorian calendar, after which the monthly schedules repeat.
+const CYCLE_DAYS: i64 = 146_097;
+
+/// The days on which a calendar trigger starts the task.
+#[derive(Debug, Clone)]
+enum Calendar {
+    Once,
+    Daily {
+        interval: i64,
+    },
+    Weekly {
+        interval: i64,
+        days_of_week: i16,
+    },
+    Monthly {
+        days_of_month: u32,
+        months: i16,
+        last_day: bool,
+    },
+    MonthlyDOW {
+        days_of_week: i16,
+        weeks: i16,
+        last_week: bool,
+        months: i16,
+    },
+}
+
+impl Calendar {
+    fn new(trigger: &Trigger) -> Result<Self, Error> {
+        let interval = |value: Option<i16>, name: &str| match value.unwrap_or(1) {
+            i if i >= 1 => Ok(i as i64),
+            i => Err(Error::InvalidOperation(InvalidOperationError {
+                message: format!(
+                    "The {} must be at least 1, found {} (trigger '{}')",
+                    name, i, trigger.id
+                ),
+            })),
+        };
+
+        Ok(match &trigger.kind {
+            TriggerKind::Time(_) => Calendar::Once,
+            TriggerKind::Daily(daily) => Calendar::Daily {
+                interval: interval(daily.days_interval, "days interval")?,
+            },
+            TriggerKind::Weekly(weekly) => Calendar::Weekly {
+                interval: interval(weekly.weeks_interval, "weeks interval")?,
+                days_of_week: weekly.days_of_week.unwrap_or_default().bits(),
+            },
+            TriggerKind::Monthly(monthly) => Calendar::Monthly {
+                days_of_month: monthly.days_of_month.unwrap_or(0) as u32,
+                months: months(monthly.months_of_year),
+                last_day: monthly.run_on_last_day.unwrap_or(false),
+            },
+            TriggerKind::MonthlyDOW(monthly_dow) => Calendar::MonthlyDOW {
+                days_of_week: monthly_dow.days_of_week.unwrap_or_default().bits(),
+                weeks: monthly_dow.weeks_of_month.unwrap_or_default().bits(),
+                last_week: monthly_dow.run_on_last_week.unwrap_or(false),
+                months: months(monthly_dow.months_of_year),
+            },
+            _ => {
+                return Err(Error::InvalidOperation(InvalidOperationError {
+                    message: format!(
+                        "Occurrences can only be computed for daily, weekly, monthly, monthly day-of-week and time triggers (trigger '{}')",
+                        trigger.id
+                    ),
+                }))
+            }
+        })
+    }
+
+    // Whether the calendar never matches a day, which would make searching endless.
+    fn is_empty(&self) -> bool {
+        match self {
+            Calendar::Once | Calendar::Daily { .. } => false,
+            Calendar::Weekly { days_of_week, .. } => *days_of_week == 0,
+            Calendar::Monthly {
+                days_of_month,
+                months,
+                last_day,
+            } => *months == 0 || (*days_of_month == 0 && !last_day),
+            Calendar::MonthlyDOW {
+                days_of_week,
+                weeks,
+                last_week,
+                months,
+            } => *months == 0 || *days_of_week == 0 || (*weeks == 0 && !last_week),
+        }
+    }
+
+    // The number of days after which a day matching the calendar is found, if there is one.
+    fn search_days(&self) -> i64 {
+        match self {
+            Calendar::Once => 0,
+            Calendar::Daily { interval } => *interval,
+            Calendar::Weekly { interval, .. } => 7 * interval,
+            Calendar::Monthly { .. } | Calendar::MonthlyDOW { .. } => CYCLE_DAYS,
+        }
+    }
+
+    // Whether the task starts on the day, both are in days since 1970-01-01.
+    fn matches(&self, start_day: i64, day: i64) -> bool {
+        let has_month = |months: i16, month: u32| months & (1 << (month - 1)) != 0;
+        let has_day_of_week = |days: i16| days & (1 << day_of_week(day)) != 0;
+
+        match self {
+            Calendar::Once => day == start_day,
+            Calendar::Daily { interval } => (day - start_day) % interval == 0,
+            Calendar::Weekly {
+                interval,
+                days_of_week,
+            } => {
+                // Weeks start on Sunday
+                let first_week = start_day - day_of_week(start_day) as i64;
+                has_day_of_week(*days_of_week) && ((day - first_week) / 7) % interval == 0
+            }
+            Calendar::Monthly {
+                days_of_month,
+                months,
+                last_day,
+            } => {
+                let (year, month, day) = civil_from_days(day);
+                let is_last_day = day == days_in_month(year, month);
+                has_month(*months, month)
+                    && (days_of_month & (1 << (day - 1)) != 0
+                        || (is_last_day && (*last_day || days_of_month & 0x8000_0000 != 0)))
+            }
+            Calendar::MonthlyDOW {
+                days_of_week,
+                weeks,
+                last_week,
+                months,
+            } => {
+                let (year, month, day_of_month) = civil_from_days(day);
+                let week = (day_of_month - 1) / 7;
+                let is_last_week = day_of_month + 7 > days_in_month(year, month);
+                has_month(*months, month)
+                    && has_day_of_week(*days_of_week)
+                    && ((week < 4 && weeks & (1 << week) != 0) || (*last_week && is_last_week))
+            }
+        }
+    }
+}
+
+// No months is read as every month, as the Task Scheduler does.
+fn months(months_of_year: Option<Months>) -> i16 {
+    match months_of_year.unwrap_or_default() {
+        months if months.is_empty() => Months::all().bits(),
+        months => months.bits(),
+    }
+}
+
+/// An iterator over the dates and times at which a calendar trigger starts the task, created by
+/// [Trigger::occurrences].
+///
+/// The occurrences are in the wall-clock time of the start boundary. The UTC offsets of the
+/// boundaries and the random delays of the triggers are not applied, see
+/// [Trigger::occurrences_in] to compute the occurrences in a time zone.
+#[derive(Debug, Clone)]
+pub struct Occurrences {
+    calendar: Calendar,
+    start: DateTime,
+    end: Option<DateTime>,
+    after: DateTime,
+    // The interval and the duration of the repetition in seconds, a duration of 0 repeats the
+    // task until the next occurrence.
+    repetition: Option<(i64, i64)>,
+    base: Option<DateTime>,
+    next_base: Option<DateTime>,
+    step: i64,
+}
+
+impl Occurrences {
+    fn new(trigger: &Trigger, after: DateTime) -> Result<Self, Error> {
+        let (start, end) = boundaries(trigger)?;
+        let end = end.map(|end| wall_clock(&end));
+        Self::from_boundaries(trigger, wall_clock(&start), end, after)
+    }
+
+    fn from_boundaries(
+        trigger: &Trigger,
+        start: DateTime,
+        end: Option<DateTime>,
+        after: DateTime,
+    ) -> Result<Self, Error> {
+        let calendar = Calendar::new(trigger)?;
+        let repetition = match &trigger.repetition {
+            Some(repetition) => {
+                validate_repetition("repetition", repetition)?;
+                Some((
+                    repetition.interval.total_seconds()? as i64,
+                    repetition.duration.total_seconds()? as i64,
+                ))
+            }
+            None => None,
+        };
+
+        let mut occurrences = Self {
+            calendar,
+            start,
+            end,
+            after,
+            repetition,
+            base: None,
+            next_base: None,
+            step: 0,
+        };
+        if trigger.enabled && !occurrences.calendar.is_empty() {
+            occurrences.seek();
+        }
+        Ok(occurrences)
+    }
+
+    // Positions the iterator on the last occurrence which may repeat after `after`.
+    fn seek(&mut self) {
+        let start_day = self.start.days();
+        let first = self.find_day(start_day, 1);
+
+        let base = if self.after < self.start {
+            first
+        } else if self.repetition.is_none() {
+            self.find_day(self.after.days(), 1)
+        } else {
+            let mut day = self.after.days();
+            if self.at(day).is_some_and(|time| time > self.after) {
+                day -= 1;
+            }
+            self.find_day(day, -1).or(first)
+        };
+
+        self.base = base;
+        self.next_base = base.and_then(|base| self.find_day(base.days() + 1, 1));
+        if let (Some(base), Some((interval, _))) = (base, self.repetition) {
+            if interval > 0 && self.after >= base {
+                self.step = self.after.seconds_since(&base) / interval;
+            }
+        }
+    }
+
+    // Finds the first day from `day` in the direction of `step` on which the task starts.
+    fn find_day(&self, day: i64, step: i64) -> Option<DateTime> {
+        let start_day = self.start.days();
+        let mut day = day.max(start_day);
+        let limit = if step < 0 {
+            start_day
+        } else {
+            day + self.calendar.search_days()
+        };
+        while (step > 0 && day <= limit) || (step < 0 && day >= limit) {
+            if self.calendar.matches(start_day, day) {
+                return self.at(day);
+            }
+            day += step;
+        }
+        None
+    }
+
+    fn at(&self, day: i64) -> Option<DateTime> {
+        DateTime::from_days(day, self.start.seconds_of_day())
+    }
+
+    fn advance(&mut self) {
+        self.base = self.next_base;
+        self.next_base = self.base.and_then(|base| self.find_day(base.days() + 1, 1));
+        self.step = 0;
+    }
+}
+
+fn boundaries(trigger: &Trigger) -> Result<(Boundary, Option<Boundary>), Error> {
+    let start = trigger.start_boundary.as_deref().ok_or_else(|| {
+        Error::RequiredProperty(RequiredPropertyError {
+            message: format!(
+                "The start boundary must be set to compute occurrences (trigger '{}')",
+                trigger.id
+            ),
+        })
+    })?;
+    let end = match &trigger.end_boundary {
+        Some(end) => Some(end.parse()?),
+        None => None,
+    };
+    Ok((start.parse()?, end))
+}
+
+// The date and time written in the boundary, ignoring its UTC offset.
+fn wall_clock(boundary: &Boundary) -> DateTime {
+    match boundary {
+        Boundary::Local(local) => *local,
+        Boundary::Synchronized(zoned) => zoned.local(),
+    }
+}
+
+impl Iterator for Occurrences {
+    type Item = DateTime;
+
+    fn next(&mut self) -> Option<DateTime> {
+        loop {
+            let base = self.base?;
+            let time = match self.repetition {
+                None if self.step == 0 => Some(base),
+                None => None,
+                Some((interval, duration)) => {
+                    let offset = self.step.checked_mul(interval)?;
+                    if self.step > 0 && (interval == 0 || (duration > 0 && offset > duration)) {
+                        None
+                    } else {
+                        base.add_seconds(offset)
+                            .filter(|time| self.next_base.map_or(true, |next| *time < next))
+                    }
+                }
+            };
+
+            let Some(time) = time else {
+                self.advance();
+                continue;
+            };
+            self.step += 1;
+
+            if self.end.is_some_and(|end| time >= end) {
+                self.base = None;
+                return None;
+            }
+            if time > self.after {
+                return Some(time);
+            }
+        }
+    }
+}
+
+/// An iterator over the dates and times at which a calendar trigger starts the task in a time
+/// zone, created by [Trigger::occurrences_in].
+///
+/// A trigger with a local start boundary follows the wall clock of the time zone: a daily trigger
+/// at 08:00 runs at 08:00 in both winter and summer time. The occurrences skipped by a daylight
+/// saving time transition run at the end of the transition, and the occurrences which happen twice
+/// run once, at the earliest. A trigger with a start boundary synchronized across time zones
+/// follows the UTC offset of its start boundary: it runs at the same instant on every computer,
+/// whatever the time zone.
+#[derive(Debug, Clone)]
+pub struct ZonedOccurrences<Z> {
+    occurrences: Occurrences,
+    zone: Z,
+    // The UTC offset of a start boundary synchronized across time zones.
+    offset: Option<i32>,
+    end: Option<DateTime>,
+    after: DateTime,
+    last: Option<DateTime>,
+}
+
+impl<Z: TimeZone> ZonedOccurrences<Z> {
+    fn new(trigger: &Trigger, zone: Z, after: &ZonedDateTime) -> Result<Self, Error> {
+        let (start, end) = boundaries(trigger)?;
+        let after = after.utc();
+
+        let (offset, wall_clock_after) = match start {
+            Boundary::Local(_) => {
+                // The wall clock may be set back, start one day earlier and skip the occurrences
+                // before `after`.
+                (
+                    None,
+                    zone.at_utc(&after).local().saturating_add_seconds(-86_400),
+                )
+            }
+            Boundary::Synchronized(start) => (
+                Some(start.offset()),
+                after.saturating_add_seconds(start.offset() as i64),
+            ),
+        };
+        let end = end.map(|end| end.resolve(&zone).utc());
+
+        Ok(Self {
+            occurrences: Occurrences::from_boundaries(
+                trigger,
+                wall_clock(&start),
+                None,
+                wall_clock_after,
+            )?,
+            zone,
+            offset,
+            end,
+            after,
+            last: None,
+        })
+    }
+}
+
+impl<Z: TimeZone> Iterator for ZonedOccurrences<Z> {
+    type Item = ZonedDateTime;
+
+    fn next(&mut self) -> Option<ZonedDateTime> {
+        loop {
+            let time = self.occurrences.next()?;
+            let time = match self.offset {
+                Some(offset) => self.zone.at_utc(&ZonedDateTime::new(time, offset).utc()),
+                None => self.zone.at_local(&time),
+            };
+
+            let utc = time.utc();
+            if self.end.is_some_and(|end| utc >= end) {
+                self.occurrences.base = None;
+                return None;
+            }
+            if utc > self.after && self.last.map_or(true, |last| utc > last) {
+                self.last = Some(utc);
+                return Some(time);
+            }
+        }
+    }
+}
+
+impl Trigger {
+    /// Returns the dates and times after `after` at which the trigger starts the task.
+    ///
+    /// Occurrences are computed for daily, weekly, monthly, monthly day-of-week and time triggers,
+    /// from their start boundary up to their end boundary, including the repetitions of the task.
+    /// The repetitions of an occurrence stop at the end of the repetition duration or at the next
+    /// occurrence. A disabled trigger has no occurrences.
+    ///
+    /// # Example
+    /// ```
+    /// use planif::datetime::DateTime;
+    /// use planif::enums::DayOfWeek;
+    /// use planif::task_definition::{Trigger, TriggerKind, WeeklyTrigger};
+    ///
+    /// let trigger = Trigger {
+    ///     start_boundary: Some("2023-01-02T08:00:00".to_string()),
+    ///     ..Trigger::new("WeeklyTrigger", TriggerKind::Weekly(WeeklyTrigger {
+    ///         days_of_week: Some(DayOfWeek::Monday | DayOfWeek::Friday),
+    ///         weeks_interval: Some(2),
+    ///         ..Default::default()
+    ///     }))
+    /// };
+    ///
+    /// let after: DateTime = "2023-01-03T00:00:00".parse().unwrap();
+    /// let occurrences: Vec<String> = trigger.occurrences(after).unwrap()
+    ///     .take(3)
+    ///     .map(|o| o.to_string())
+    ///     .collect();
+    /// assert_eq!(occurrences, vec![
+    ///     "2023-01-06T08:00:00",
+    ///     "2023-01-16T08:00:00",
+    ///     "2023-01-20T08:00:00",
+    /// ]);
+    /// ```
+    pub fn occurrences(&self, after: DateTime) -> Result<Occurrences, Error> {
+        Occurrences::new(self, after)
+    }
+
+    /// Returns the next `count` dates and times after `after` at which the trigger starts the task.
+    ///
+    /// See [occurrences](Trigger::occurrences).
+    pub fn next_occurrences(&self, after: DateTime, count: usize) -> Result<Vec<DateTime>, Error> {
+        Ok(self.occurrences(after)?.take(count).collect())
+    }
+
+    /// Returns the dates and times after `after` at which the trigger starts the task on a computer
+    /// in the time zone `zone`, applying its daylight saving time transitions and the UTC offsets of
+    /// the boundaries. See [ZonedOccurrences].
+    ///
+    /// # Example
+    /// ```
+    /// use planif::datetime::DateTime;
+    /// use planif::task_definition::{Trigger, TriggerKind};
+    /// use planif::time_zone::{FixedOffset, TimeZone};
+    ///
+    /// let trigger = Trigger {
+    ///     start_boundary: Some("2023-01-01T08:00:00+01:00".to_string()),
+    ///     ..Trigger::new("DailyTrigger", TriggerKind::Daily(Default::default()))
+    /// };
+    ///
+    /// let tokyo = FixedOffset::east(9 * 3600);
+    /// let after = tokyo.at_local(&"2023-01-01T00:00:00".parse().unwrap());
+    /// let next = trigger.occurrences_in(&tokyo, &after).unwrap().next().unwrap();
+    /// assert_eq!(next.to_string(), "2023-01-01T16:00:00+09:00");
+    /// ```
+    pub fn occurrences_in<Z: TimeZone>(
+        &self,
+        zone: Z,
+        after: &ZonedDateTime,
+    ) -> Result<ZonedOccurrences<Z>, Error> {
+        ZonedOccurrences::new(self, zone, after)
+    }
+}
+
+impl TaskDefinition {
+    /// Returns the next `count` dates and times after `after` at which the calendar triggers of the
+    /// task start it. Triggers started by an event, such as boot or logon triggers, are ignored.
+    ///
+    /// See [Trigger::occurrences].
+    ///
+    /// # Example
+    /// ```
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// let schedule = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_daily()
+    ///     .trigger("Morning", true).unwrap()
+    ///     .start_boundary("2023-01-01T08:00:00").unwrap()
+    ///     .create_time()
+    ///     .trigger("Once", true).unwrap()
+    ///     .start_boundary("2023-01-01T12:00:00").unwrap()
+    ///     .build().unwrap();
+    ///
+    /// let occurrences = schedule.definition()
+    ///     .next_occurrences("2023-01-01T00:00:00".parse().unwrap(), 3)
+    ///     .unwrap();
+    /// assert_eq!(occurrences[1].to_string(), "2023-01-01T12:00:00");
+    /// assert_eq!(occurrences[2].to_string(), "2023-01-02T08:00:00");
+    /// ```
+    pub fn next_occurrences(&self, after: DateTime, count: usize) -> Result<Vec<DateTime>, Error> {
+        let mut occurrences = Vec::new();
+        for trigger in self.triggers.iter().filter(|t| t.kind.is_calendar()) {
+            occurrences.extend(trigger.next_occurrences(after, count)?);
+        }
+        occurrences.sort();
+        occurrences.dedup();
+        occurrences.truncate(count);
+        Ok(occurrences)
+    }
+
+    /// Returns the next `count` dates and times after `after` at which the calendar triggers of the
+    /// task start it on a computer in the time zone `zone`.
+    ///
+    /// See [Trigger::occurrences_in].
+    pub fn next_occurrences_in<Z: TimeZone>(
+        &self,
+        zone: Z,
+        after: &ZonedDateTime,
+        count: usize,
+    ) -> Result<Vec<ZonedDateTime>, Error> {
+        let mut occurrences = Vec::new();
+        for trigger in self.triggers.iter().filter(|t| t.kind.is_calendar()) {
+            occurrences.extend(trigger.occurrences_in(&zone, after)?.take(count));
+        }
+        occurrences.sort_by_key(|o| o.utc());
+        occurrences.dedup_by_key(|o| o.utc());
+        occurrences.truncate(count);
+        Ok(occurrences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::{DayOfWeek, Month, WeekOfMonth};
+    use crate::settings::Duration;
+    use crate::task_definition::{
+        DailyTrigger, MonthlyDOWTrigger, MonthlyTrigger, RepetitionPattern, TimeTrigger,
+    };
+
+    fn trigger(kind: TriggerKind, start: &str) -> Trigger {
+        Trigger {
+            start_boundary: Some(start.to_string()),
+            ..Trigger::new("Trigger", kind)
+        }
+    }
+
+    fn next(trigger: &Trigger, after: &str, count: usize) -> Vec<String> {
+        trigger
+            .next_occurrences(after.parse().unwrap(), count)
+            .unwrap()
+            .iter()
+            .map(|o| o.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn daily_occurrences() {
+        let mut daily = trigger(
+            TriggerKind::Daily(DailyTrigger {
+                days_interval: Some(3),
+                ..Default::default()
+            }),
+            "2023-01-01T08:00:00",
+        );
+        daily.end_boundary = Some("2023-01-10T08:00:00".to_string());
+
+        assert_eq!(
+            next(&daily, "2022-12-01T00:00:00", 5),
+            vec![
+                "2023-01-01T08:00:00",
+                "2023-01-04T08:00:00",
+                "2023-01-07T08:00:00"
+            ]
+        );
+        assert_eq!(
+            next(&daily, "2023-01-04T08:00:00", 5),
+            vec!["2023-01-07T08:00:00"]
+        );
+
+        daily.enabled = false;
+        assert!(next(&daily, "2022-12-01T00:00:00", 5).is_empty());
+    }
+
+    #[test]
+    fn monthly_occurrences() {
+        // The 31st and the last day of January, February and April
+        let monthly = trigger(
+            TriggerKind::Monthly(MonthlyTrigger {
+                days_of_month: Some(1 << 30),
+                months_of_year: Some("Jan,Feb,Apr".parse().unwrap()),
+                run_on_last_day: Some(true),
+                ..Default::default()
+            }),
+            "2024-01-01T23:30:00",
+        );
+
+        assert_eq!(
+            next(&monthly, "2024-01-01T00:00:00", 4),
+            vec![
+                "2024-01-31T23:30:00",
+                "2024-02-29T23:30:00",
+                "2024-04-30T23:30:00",
+                "2025-01-31T23:30:00",
+            ]
+        );
+
+        // The 30th of February never happens
+        let never = trigger(
+            TriggerKind::Monthly(MonthlyTrigger {
+                days_of_month: Some(1 << 29),
+                months_of_year: Some(Month::February.into()),
+                ..Default::default()
+            }),
+            "2024-01-01T00:00:00",
+        );
+        assert!(next(&never, "2024-01-01T00:00:00", 1).is_empty());
+    }
+
+    #[test]
+    fn monthly_dow_occurrences() {
+        // The second and the last Sunday of every month
+        let monthly_dow = trigger(
+            TriggerKind::MonthlyDOW(MonthlyDOWTrigger {
+                days_of_week: Some(DayOfWeek::Sunday.into()),
+                weeks_of_month: Some(WeekOfMonth::Second.into()),
+                run_on_last_week: Some(true),
+                ..Default::default()
+            }),
+            "2023-04-01T10:00:00",
+        );
+
+        assert_eq!(
+            next(&monthly_dow, "2023-04-01T00:00:00", 4),
+            vec![
+                "2023-04-09T10:00:00",
+                "2023-04-30T10:00:00",
+                "2023-05-14T10:00:00",
+                "2023-05-28T10:00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn repeated_occurrences() {
+        let mut time = trigger(
+            TriggerKind::Time(TimeTrigger::default()),
+            "2023-01-01T08:00:00",
+        );
+        time.repetition = Some(RepetitionPattern {
+            duration: Duration {
+                minutes: Some(4),
+                ..Default::default()
+            },
+            interval: Duration {
+                minutes: Some(1),
+                ..Default::default()
+            },
+            stop_at_duration_end: false,
+        });
+        assert_eq!(next(&time, "2023-01-01T00:00:00", 10).len(), 5);
+        assert_eq!(
+            next(&time, "2023-01-01T08:02:30", 10),
+            vec!["2023-01-01T08:03:00", "2023-01-01T08:04:00"]
+        );
+
+        // Without a duration, the repetitions of a daily trigger stop at the next day
+        let mut daily = trigger(
+            TriggerKind::Daily(DailyTrigger::default()),
+            "2023-01-01T08:00:00",
+        );
+        daily.repetition = Some(RepetitionPattern {
+            duration: Duration::default(),
+            interval: Duration {
+                hours: Some(5),
+                ..Default::default()
+            },
+            stop_at_duration_end: false,
+        });
+        assert_eq!(
+            next(&daily, "2023-03-01T20:00:00", 4),
+            vec![
+                "2023-03-01T23:00:00",
+                "2023-03-02T04:00:00",
+                "2023-03-02T08:00:00",
+                "2023-03-02T13:00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn daylight_saving_time_occurrences() {
+        use crate::time_zone::NewYork;
+
+        let next_in = |trigger: &Trigger, after: &str, count| -> Vec<String> {
+            let after = NewYork.at_local(&after.parse().unwrap());
+            trigger
+                .occurrences_in(NewYork, &after)
+                .unwrap()
+                .take(count)
+                .map(|o| o.to_string())
+                .collect()
+        };
+
+        // 02:30 is skipped on March 12
+        let daily = trigger(
+            TriggerKind::Daily(DailyTrigger::default()),
+            "2023-03-10T02:30:00",
+        );
+        assert_eq!(
+            next_in(&daily, "2023-03-11T00:00:00", 3),
+            vec![
+                "2023-03-11T02:30:00-05:00",
+                "2023-03-12T03:00:00-04:00",
+                "2023-03-13T02:30:00-04:00",
+            ]
+        );
+
+        // 01:30 happens twice on November 5
+        let daily = trigger(
+            TriggerKind::Daily(DailyTrigger::default()),
+            "2023-11-04T01:30:00",
+        );
+        assert_eq!(
+            next_in(&daily, "2023-11-05T00:00:00", 2),
+            vec!["2023-11-05T01:30:00-04:00", "2023-11-06T01:30:00-05:00"]
+        );
+
+        // Synchronized across time zones, the trigger keeps the UTC time of its start boundary
+        let mut synchronized = trigger(
+            TriggerKind::Daily(DailyTrigger::default()),
+            "2023-03-11T08:00:00-05:00",
+        );
+        synchronized.end_boundary = Some("2023-03-13T12:00:00Z".to_string());
+        assert_eq!(
+            next_in(&synchronized, "2023-03-01T00:00:00", 5),
+            vec!["2023-03-11T08:00:00-05:00", "2023-03-12T09:00:00-04:00"]
+        );
+    }
+
+    #[test]
+    fn occurrences_errors() {
+        let after = DateTime::new(2023, 1, 1, 0, 0, 0).unwrap();
+        let boot = trigger(TriggerKind::Boot(Default::default()), "2023-01-01T08:00:00");
+        assert!(matches!(
+            boot.occurrences(after),
+            Err(Error::InvalidOperation(_))
+        ));
+
+        let no_start = Trigger::new("Daily", TriggerKind::Daily(Default::default()));
+        assert!(matches!(
+            no_start.occurrences(after),
+            Err(Error::RequiredProperty(_))
+        ));
+
+        let invalid_start = trigger(TriggerKind::Daily(Default::default()), "tomorrow");
+        assert!(matches!(
+            invalid_start.occurrences(after),
+            Err(Error::Parse(_))
+        ));
+    }
+}
@@ -1,45 +1,151 @@
-use windows::core::BSTR;
-use windows::Win32::System::TaskScheduler::{
-    IActionCollection, IRegistrationInfo, ITaskDefinition, ITaskFolder, ITaskService,
-    ITaskSettings, ITrigger, ITriggerCollection, TASK_LOGON_INTERACTIVE_TOKEN,
-};
-use windows::Win32::System::Com::VARIANT;
-
 use crate::com::ComRuntime;
+use crate::credentials::Credentials;
+use crate::enums::CreationFlags;
+use crate::error::{Error, InvalidOperationError};
+use crate::sddl::SecurityDescriptor;
+use crate::task_definition::TaskDefinition;
+use crate::xml::{self, SchemaVersion};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 /// A schedule is created by a [schedule builder](crate::schedule_builder). Once created, the
 /// Schedule can be registered with the Windows Task Scheduler.
 pub struct Schedule {
-    pub(crate) task_folder: ITaskFolder,
-    pub(crate) actions: IActionCollection,
-    pub(crate) force_start_boundary: bool,
-    pub(crate) registration_info: IRegistrationInfo,
-    pub(crate) settings: ITaskSettings,
-    pub(crate) task_definition: ITaskDefinition,
-    pub(crate) task_service: ITaskService,
-    pub(crate) trigger: Option<ITrigger>,
-    pub(crate) triggers: ITriggerCollection,
-    //repetition: IRepetitionPattern,
+    // Keeps the COM initialized until the schedule is registered or dropped.
+    #[allow(dead_code)]
+    pub(crate) com: ComRuntime,
+    pub(crate) definition: TaskDefinition,
+    pub(crate) folder: String,
+    pub(crate) folder_security_descriptor: Option<SecurityDescriptor>,
 }
 
 impl Schedule {
-    /// Registers the schedule. Flags can be set by using the [TaskCreationFlags](crate::enums::TaskCreationFlags) enum.
-    pub fn register(self, task_name: &str, flags: i32) -> Result<(), Box<dyn std::error::Error>> {
+    /// Returns the definition of the task which will be registered.
+    pub fn definition(&self) -> &TaskDefinition {
+        &self.definition
+    }
+
+    /// Returns the folder in which the task will be registered.
+    pub fn folder(&self) -> &str {
+        &self.folder
+    }
+
+    /// Returns the security descriptor of the folder, used if the folder is created when the task
+    /// is registered.
+    pub fn folder_security_descriptor(&self) -> Option<&SecurityDescriptor> {
+        self.folder_security_descriptor.as_ref()
+    }
+
+    /// Serializes the schedule to a Task Scheduler XML document, for example to be used with
+    /// `schtasks /create /xml`, see [xml::serialize].
+    ///
+    /// # Example
+    /// ```
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Action, ScheduleBuilder};
+    /// use planif::xml::SchemaVersion;
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// let document = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_boot()
+    ///     .trigger("BootTrigger", true).unwrap()
+    ///     .action(Action::new("", "notepad.exe", "", "")).unwrap()
+    ///     .build().unwrap()
+    ///     .to_xml(SchemaVersion::V1_2);
+    ///
+    /// assert!(document.contains("<BootTrigger id=\"BootTrigger\">"));
+    /// ```
+    pub fn to_xml(&self, version: SchemaVersion) -> String {
+        xml::serialize(&self.definition, version)
+    }
+
+    /// Registers the schedule with the logon type and the user or group of its
+    /// [principal](crate::task_definition::TaskDefinition::principal), or with the interactive
+    /// token of the current user when it has no principal. The flags are a
+    /// [TaskCreationFlags](crate::enums::TaskCreationFlags) or a combination of them, see
+    /// [CreationFlags].
+    ///
+    /// Registering is only supported on Windows, other platforms return an
+    /// [InvalidOperationError](crate::error::InvalidOperationError).
+    ///
+    /// # Example
+    /// ```
+    /// use planif::enums::TaskCreationFlags;
+    /// use planif::schedule::Schedule;
+    ///
+    /// # fn register(schedule: Schedule) -> Result<(), planif::error::Error> {
+    /// schedule.register(
+    ///     "TaskName",
+    ///     TaskCreationFlags::CreateOrUpdate | TaskCreationFlags::Disable,
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register(self, task_name: &str, flags: impl Into<CreationFlags>) -> Result<(), Error> {
+        let flags = flags.into();
+        flags.validate()?;
+        self.register_task(task_name, flags, None)
+    }
+
+    /// Registers the schedule to run with the credentials. The password is only copied to the
+    /// memory passed to the Task Scheduler, which is overwritten with zeros once the task is
+    /// registered.
+    ///
+    /// Registering is only supported on Windows, other platforms return an
+    /// [InvalidOperationError](crate::error::InvalidOperationError).
+    ///
+    /// # Example
+    /// ```
+    /// use planif::credentials::{Credentials, EnvironmentVariable};
+    /// use planif::enums::TaskCreationFlags;
+    /// use planif::schedule::Schedule;
+    /// use planif::settings::LogonType;
+    ///
+    /// # fn register(schedule: Schedule) -> Result<(), planif::error::Error> {
+    /// let credentials = Credentials::with_provider(
+    ///     "OURDOMAIN\\svc-backup",
+    ///     LogonType::Password,
+    ///     &EnvironmentVariable::new("BACKUP_PASSWORD"),
+    /// )?;
+    /// schedule.register_with_credentials("Backup", TaskCreationFlags::CreateOrUpdate, &credentials)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn register_with_credentials(
+        self,
+        task_name: &str,
+        flags: impl Into<CreationFlags>,
+        credentials: &Credentials,
+    ) -> Result<(), Error> {
+        let flags = flags.into();
+        flags.validate()?;
+        self.register_task(task_name, flags, Some(credentials))
+    }
+
+    fn register_task(
+        self,
+        task_name: &str,
+        flags: CreationFlags,
+        credentials: Option<&Credentials>,
+    ) -> Result<(), Error> {
+        #[cfg(windows)]
         unsafe {
-            self.task_folder.RegisterTaskDefinition(
-                &BSTR::from(task_name),
-                &self.task_definition,
-                flags,
-                // TODO allow user to specify creds
-                VARIANT::default(),
-                VARIANT::default(),
-                TASK_LOGON_INTERACTIVE_TOKEN,
-                VARIANT::default(),
-            )?;
+            crate::task_service::register(
+                &self.definition,
+                &self.folder,
+                self.folder_security_descriptor.as_ref(),
+                task_name,
+                flags.bits(),
+                credentials,
+            )
         }
 
-        Ok(())
+        #[cfg(not(windows))]
+        {
+            let _ = (task_name, flags, credentials);
+            Err(Error::InvalidOperation(InvalidOperationError {
+                message: "Schedules can only be registered on Windows".to_string(),
+            }))
+        }
     }
 }
 
@@ -58,7 +164,7 @@ impl TaskScheduler {
     /// let ts = TaskScheduler::new().unwrap();
     /// ```
     ///
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new() -> Result<Self, Error> {
         Ok(Self {
             com: ComRuntime::new()?
         })
@@ -68,7 +174,7 @@ impl TaskScheduler {
     /// # Example
     /// ```
     /// use planif::schedule::TaskScheduler;
-    /// 
+    ///
     /// let ts = TaskScheduler::new().unwrap();
     /// let com = ts.get_com();
     /// ```
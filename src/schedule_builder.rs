@@ -1,25 +1,19 @@
 use crate::{
-    enums::{DayOfMonth, DayOfWeek, Month, WeekOfMonth},
-    error::{InvalidOperationError, RequiredPropertyError},
+    calendar::{DaysOfWeek, Months, WeeksOfMonth},
+    clock::{Clock, SystemClock},
+    com::ComRuntime,
+    datetime::{Boundary, IntoBoundary, ZonedDateTime},
+    enums::{DayOfMonth, StateChange},
+    error::{ConversionError, Error, InvalidOperationError, ParseError, RequiredPropertyError},
     schedule::Schedule,
     settings::{Duration, PrincipalSettings, Settings},
-    com::ComRuntime,
-};
-use windows::Win32::System::Com::{
-    CoCreateInstance, CLSCTX_ALL, VARIANT,
-};
-use windows::core::{BSTR, ComInterface};
-use windows::Win32::Foundation::VARIANT_BOOL;
-use windows::Win32::System::TaskScheduler::{
-    IAction, IActionCollection, IBootTrigger, IDailyTrigger, IEventTrigger, IExecAction,
-    IIdleTrigger, ILogonTrigger, IMonthlyDOWTrigger, IMonthlyTrigger, INetworkSettings, IPrincipal,
-    IRegistrationInfo, IRegistrationTrigger, IRepetitionPattern, ITaskDefinition, ITaskFolder,
-    ITaskService, ITaskSettings, ITimeTrigger, ITrigger, ITriggerCollection, IWeeklyTrigger,
-    TaskScheduler, TASK_ACTION_EXEC, TASK_LOGON_TYPE, TASK_RUNLEVEL_TYPE, TASK_TRIGGER_BOOT,
-    TASK_TRIGGER_DAILY, TASK_TRIGGER_EVENT, TASK_TRIGGER_IDLE, TASK_TRIGGER_LOGON,
-    TASK_TRIGGER_MONTHLY, TASK_TRIGGER_MONTHLYDOW, TASK_TRIGGER_REGISTRATION, TASK_TRIGGER_TIME,
-    TASK_TRIGGER_WEEKLY,
+    task_definition::{
+        BootTrigger, DailyTrigger, EventTrigger, LogonTrigger, MonthlyDOWTrigger, MonthlyTrigger,
+        RegistrationTrigger, RepetitionPattern, SessionStateChangeTrigger, TaskDefinition,
+        TimeTrigger, Trigger, TriggerKind, WeeklyTrigger,
+    },
 };
+use std::rc::Rc;
 
 /* triggers */
 /// Marker type for base [`ScheduleBuilder<Base>`]
@@ -41,6 +35,8 @@ pub struct Monthly {}
 pub struct MonthlyDOW {}
 /// Marker type for registration [`ScheduleBuilder<Registration>`](ScheduleBuilder#impl-ScheduleBuilder<Registration>)
 pub struct Registration {}
+/// Marker type for a session state change [`ScheduleBuilder<SessionStateChange>`](ScheduleBuilder#impl-ScheduleBuilder<SessionStateChange>)
+pub struct SessionStateChange {}
 /// Marker type for a time [`ScheduleBuilder<Time>`](ScheduleBuilder#impl-ScheduleBuilder<Time>)
 pub struct Time {}
 /// Marker type for a weekly
@@ -48,10 +44,45 @@ pub struct Time {}
 pub struct Weekly {}
 
 /// A generic schedule builder used to create a specific builder.
+///
+/// A task may have several triggers of different kinds. Once a trigger is configured, calling one
+/// of the `create_*` methods again starts a builder for the next trigger, the setters of the
+/// previous triggers are then no longer available.
+///
+/// # Example
+/// ```
+/// use planif::com::ComRuntime;
+/// use planif::schedule_builder::{Action, ScheduleBuilder};
+///
+/// let com = ComRuntime::new().unwrap();
+/// let schedule = ScheduleBuilder::new(&com).unwrap()
+///     .create_logon()
+///     .trigger("LogonTrigger", true).unwrap()
+///     .user_id("DOMAIN\\UserName").unwrap()
+///     .create_daily()
+///     .trigger("DailyTrigger", true).unwrap()
+///     .start_boundary("2007-01-01T02:00:00").unwrap()
+///     .days_interval(1).unwrap()
+///     .action(Action::new("id", "notepad.exe", "", "")).unwrap()
+///     .build().unwrap();
+///
+/// assert_eq!(schedule.definition().triggers.len(), 2);
+/// ```
 pub struct ScheduleBuilder<Frequency = Base> {
     pub(crate) frequency: std::marker::PhantomData<Frequency>,
     pub(crate) schedule: Schedule,
-    com: ComRuntime,
+    // Index of the trigger configured by the setters, `None` until `trigger()` is called.
+    pub(crate) current_trigger: Option<usize>,
+    // The clock evaluating the relative boundaries when the schedule is built.
+    pub(crate) clock: Rc<dyn Clock>,
+    pub(crate) relative_boundaries: Vec<RelativeBoundary>,
+}
+
+// A start or end boundary set to an amount of seconds after the schedule is built.
+pub(crate) struct RelativeBoundary {
+    trigger: usize,
+    end: bool,
+    seconds: i64,
 }
 
 impl ScheduleBuilder<Base> {
@@ -61,63 +92,40 @@ impl ScheduleBuilder<Base> {
     /// use planif::schedule_builder::{ Base, ScheduleBuilder };
     /// use planif::com::ComRuntime;
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Base> = ScheduleBuilder::new(&com).unwrap();
     /// ```
-    pub fn new(com: &ComRuntime) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let sb_com = com.clone();
-
-            let task_service: ITaskService = CoCreateInstance(&TaskScheduler, None, CLSCTX_ALL)?;
-            task_service.Connect(
-                VARIANT::default(),
-                VARIANT::default(),
-                VARIANT::default(),
-                VARIANT::default(),
-            )?;
-
-            let task_folder: ITaskFolder = task_service.GetFolder(&BSTR::from("\\"))?;
-            let task_definition: ITaskDefinition = task_service.NewTask(0)?;
-            let triggers: ITriggerCollection = task_definition.Triggers()?;
-            let registration_info: IRegistrationInfo = task_definition.RegistrationInfo()?;
-            let actions: IActionCollection = task_definition.Actions()?;
-            let settings: ITaskSettings = task_definition.Settings()?;
-
-            Ok(Self {
-                com: sb_com,
-                frequency: std::marker::PhantomData::<Base>,
-                schedule: Schedule {
-                    task_folder,
-                    actions,
-                    force_start_boundary: false,
-                    registration_info,
-                    settings,
-                    task_service,
-                    task_definition,
-                    trigger: None,
-                    triggers,
-                },
-            })
-        }
+    pub fn new(com: &ComRuntime) -> Result<Self, Error> {
+        Ok(Self {
+            frequency: std::marker::PhantomData::<Base>,
+            schedule: Schedule {
+                com: com.clone(),
+                definition: TaskDefinition::new(),
+                folder: "\\".to_string(),
+                folder_security_descriptor: None,
+            },
+            current_trigger: None,
+            clock: Rc::new(SystemClock),
+            relative_boundaries: Vec::new(),
+        })
     }
+}
 
+impl<Frequency> ScheduleBuilder<Frequency> {
     /// Creates a builder for a boot trigger.
     ///
     /// # Example
     ///
     /// ```
-    /// use planif::schedule_builder::{ Boot, ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Boot, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Boot> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_boot();
     /// ```
     pub fn create_boot(self) -> ScheduleBuilder<Boot> {
-        ScheduleBuilder::<Boot> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<Boot>,
-            schedule: self.schedule,
-        }
+        self.with_frequency()
     }
 
     /// Creates a builder for a daily trigger.
@@ -125,19 +133,15 @@ impl ScheduleBuilder<Base> {
     /// # Example
     ///
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, Daily, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Daily, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Daily> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily();
     /// ```
-    pub fn create_daily(mut self) -> ScheduleBuilder<Daily> {
-        self.schedule.force_start_boundary = true;
-        ScheduleBuilder::<Daily> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<Daily>,
-            schedule: self.schedule,
-        }
+    pub fn create_daily(self) -> ScheduleBuilder<Daily> {
+        self.with_frequency()
     }
 
     /// Creates a builder for an event trigger.
@@ -145,19 +149,15 @@ impl ScheduleBuilder<Base> {
     /// # Example
     ///
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, Event, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Event, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Event> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_event();
     /// ```
-    pub fn create_event(mut self) -> ScheduleBuilder<Event> {
-        self.schedule.force_start_boundary = true;
-        ScheduleBuilder::<Event> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<Event>,
-            schedule: self.schedule,
-        }
+    pub fn create_event(self) -> ScheduleBuilder<Event> {
+        self.with_frequency()
     }
 
     /// Creates a builder for an idle trigger.
@@ -165,18 +165,15 @@ impl ScheduleBuilder<Base> {
     /// # Example
     ///
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, Idle, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Idle, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Idle> = ScheduleBuilder::new(&com).unwrap()
     ///         .create_idle();
     /// ```
     pub fn create_idle(self) -> ScheduleBuilder<Idle> {
-        ScheduleBuilder::<Idle> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<Idle>,
-            schedule: self.schedule,
-        }
+        self.with_frequency()
     }
 
     /// Creates a builder for a logon trigger.
@@ -184,73 +181,76 @@ impl ScheduleBuilder<Base> {
     /// # Example
     ///
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, Logon, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Logon, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Logon> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_logon();
     /// ```
     pub fn create_logon(self) -> ScheduleBuilder<Logon> {
-        ScheduleBuilder::<Logon> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<Logon>,
-            schedule: self.schedule,
-        }
+        self.with_frequency()
     }
 
     /// Creates a builder for a monthly trigger.
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, Monthly, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Monthly, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Monthly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly();
     /// ```
     pub fn create_monthly(self) -> ScheduleBuilder<Monthly> {
-        ScheduleBuilder::<Monthly> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<Monthly>,
-            schedule: self.schedule,
-        }
+        self.with_frequency()
     }
 
     /// Creates a builder for a monthly day of week trigger.
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, MonthlyDOW, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{MonthlyDOW, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<MonthlyDOW> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly_dow();
     /// ```
-    pub fn create_monthly_dow(mut self) -> ScheduleBuilder<MonthlyDOW> {
-        self.schedule.force_start_boundary = true;
-        ScheduleBuilder::<MonthlyDOW> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<MonthlyDOW>,
-            schedule: self.schedule,
-        }
+    pub fn create_monthly_dow(self) -> ScheduleBuilder<MonthlyDOW> {
+        self.with_frequency()
     }
 
     /// Creates a builder for a trigger that starts a task when the task is registered or updated.
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, Registration, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Registration, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Registration> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_registration();
     /// ```
     pub fn create_registration(self) -> ScheduleBuilder<Registration> {
-        ScheduleBuilder::<Registration> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<Registration>,
-            schedule: self.schedule,
-        }
+        self.with_frequency()
+    }
+
+    /// Creates a builder for a trigger that starts a task when a user session is connected,
+    /// disconnected, locked or unlocked.
+    ///
+    /// # Example
+    /// ```
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, SessionStateChange};
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// let builder: ScheduleBuilder<SessionStateChange> = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_session_state_change();
+    /// ```
+    pub fn create_session_state_change(self) -> ScheduleBuilder<SessionStateChange> {
+        self.with_frequency()
     }
 
     /// Creates a builder for a time trigger.
@@ -258,19 +258,15 @@ impl ScheduleBuilder<Base> {
     /// # Example
     ///
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Time };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Time};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Time> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_time();
     /// ```
-    pub fn create_time(mut self) -> ScheduleBuilder<Time> {
-        self.schedule.force_start_boundary = true;
-        ScheduleBuilder::<Time> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<Time>,
-            schedule: self.schedule,
-        }
+    pub fn create_time(self) -> ScheduleBuilder<Time> {
+        self.with_frequency()
     }
 
     /// Creates a builder for a weekly trigger.
@@ -278,22 +274,17 @@ impl ScheduleBuilder<Base> {
     /// # Example
     ///
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Weekly };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Weekly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Weekly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_weekly();
     /// ```
     pub fn create_weekly(self) -> ScheduleBuilder<Weekly> {
-        ScheduleBuilder::<Weekly> {
-            com: self.com,
-            frequency: std::marker::PhantomData::<Weekly>,
-            schedule: self.schedule,
-        }
+        self.with_frequency()
     }
-}
 
-impl<Frequency> ScheduleBuilder<Frequency> {
     /// Sets the task folder for this trigger.
     /// For example, the root folder is "\\".
     /// Do not use a backslash following the last folder name in the path.
@@ -301,47 +292,83 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     ///
     /// # Example
     /// ```
+    /// use planif::com::ComRuntime;
     /// use planif::schedule::Schedule;
     /// use planif::schedule_builder::ScheduleBuilder;
     ///
-    /// let schedule: Schedule = ScheduleBuilder::new().unwrap()
+    /// let com = ComRuntime::new().unwrap();
+    /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .in_folder("\\My Tasks").unwrap()
+    ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .build().unwrap();
     /// ```
-    pub fn in_folder(mut self, folder: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            // check if folder exists, if not make it
-            self.schedule.task_folder =
-                match self.schedule.task_service.GetFolder(&BSTR::from(folder)) {
-                    Ok(x) => x,
-                    Err(_) => self
-                        .schedule
-                        .task_service
-                        .GetFolder(&BSTR::from("\\"))
-                        .unwrap()
-                        .CreateFolder(
-                            &BSTR::from(folder.trim_start_matches('\\')),
-                            VARIANT::default(),
-                        )?,
-                };
-        }
+    pub fn in_folder(mut self, folder: &str) -> Result<Self, Error> {
+        self.schedule.folder = folder.to_string();
+        Ok(self)
+    }
+
+    /// Sets the security descriptor of the task folder, in SDDL, which is applied if the folder
+    /// does not exist yet and is created when the task is registered.
+    /// _optional_
+    ///
+    /// # Example
+    /// ```
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule::Schedule;
+    /// use planif::schedule_builder::ScheduleBuilder;
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_daily()
+    ///     .in_folder("\\Operations").unwrap()
+    ///     .folder_security_descriptor("D:P(A;OICI;FA;;;BA)(A;OICI;FA;;;SY)").unwrap()
+    ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
+    ///     .build().unwrap();
+    /// ```
+    pub fn folder_security_descriptor(mut self, sddl: &str) -> Result<Self, Error> {
+        self.schedule.folder_security_descriptor = Some(sddl.parse()?);
+        Ok(self)
+    }
+
+    /// Sets the clock which evaluates the relative boundaries, see
+    /// [start_boundary_in](ScheduleBuilder::start_boundary_in). The [system clock](SystemClock)
+    /// is used by default, a [FixedClock](crate::clock::FixedClock) builds reproducible schedules.
+    ///
+    /// # Example
+    /// ```
+    /// use planif::clock::FixedClock;
+    /// use planif::com::ComRuntime;
+    /// use planif::datetime::{DateTime, ZonedDateTime};
+    /// use planif::schedule_builder::ScheduleBuilder;
+    /// use planif::settings::Duration;
+    ///
+    /// let now = ZonedDateTime::new(DateTime::new(2023, 1, 1, 8, 0, 0).unwrap(), 0);
+    /// let com = ComRuntime::new().unwrap();
+    /// let schedule = ScheduleBuilder::new(&com).unwrap()
+    ///     .clock(FixedClock::new(now)).unwrap()
+    ///     .create_time()
+    ///     .trigger("TimeTrigger", true).unwrap()
+    ///     .start_boundary_in(Duration { minutes: Some(5), ..Default::default() }).unwrap()
+    ///     .build().unwrap();
+    ///
+    /// assert_eq!(
+    ///     schedule.definition().triggers[0].start_boundary.as_deref(),
+    ///     Some("2023-01-01T08:05:00+00:00")
+    /// );
+    /// ```
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Result<Self, Error> {
+        self.clock = Rc::new(clock);
         Ok(self)
     }
 
     /// Creates the action to execute when the task is run.
     ///
     /// See examples <https://github.com/mattrobineau/planif/tree/main/examples>
-    pub fn action(self, action: Action) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let i_action: IAction = self.schedule.actions.Create(TASK_ACTION_EXEC)?;
-            let i_exec_action: IExecAction = i_action.cast()?;
-
-            i_exec_action.SetPath(&action.path)?;
-            i_exec_action.SetId(&action.id)?;
-            i_exec_action.SetWorkingDirectory(&action.working_dir)?;
-            i_exec_action.SetArguments(&action.args)?;
-        }
+    pub fn action(mut self, action: Action) -> Result<Self, Error> {
+        self.schedule.definition.actions.push(action);
         Ok(self)
     }
 
@@ -351,50 +378,88 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     /// # Example
     /// ```
     /// use planif::schedule::Schedule;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .author("Alice").unwrap()
     ///     .build().unwrap();
     /// ```
-    pub fn author(self, author: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            self.schedule
-                .registration_info
-                .SetAuthor(&BSTR::from(author))?;
-        }
+    pub fn author(mut self, author: &str) -> Result<Self, Error> {
+        self.schedule.definition.registration_info.author = Some(author.to_string());
         Ok(self)
     }
 
     /// Returns the schedule
     ///
+    /// The definition is [validated](crate::task_definition::TaskDefinition::validate) before the
+    /// schedule is returned, durations outside of the limits of the Task Scheduler return a
+    /// [ValidationError](crate::error::ValidationError).
+    ///
     /// # Example
     /// ```
     /// use planif::schedule::Schedule;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .author("Alice").unwrap()
     ///     .build().unwrap();
     /// ```
-    pub fn build(self) -> Result<Schedule, Box<dyn std::error::Error>> {
-        if self.schedule.trigger.is_none() {
-            return Err(Box::new(InvalidOperationError {
+    pub fn build(mut self) -> Result<Schedule, Error> {
+        let now = self.clock.now();
+        for relative in &self.relative_boundaries {
+            let time = now.local().add_seconds(relative.seconds).ok_or_else(|| ConversionError {
+                message: "The relative boundary is after the year 9999".to_string(),
+            })?;
+            let boundary = Boundary::Synchronized(ZonedDateTime::new(time, now.offset()));
+            let trigger = &mut self.schedule.definition.triggers[relative.trigger];
+            if relative.end {
+                trigger.end_boundary = Some(boundary.to_string());
+            } else {
+                trigger.start_boundary = Some(boundary.to_string());
+            }
+        }
+
+        let triggers = &self.schedule.definition.triggers;
+        if triggers.is_empty() {
+            return Err(Error::InvalidOperation(InvalidOperationError {
                 message: "Folder or trigger not set, cannot create scheduled task".to_string(),
             }));
         }
 
-        if self.schedule.force_start_boundary {
-            return Err(Box::new(RequiredPropertyError {
-                message: "The start boundary must be set for this trigger type".to_string(),
+        if let Some(trigger) = triggers.iter().find(|t| {
+            matches!(&t.kind, TriggerKind::SessionStateChange(s) if s.state_change.is_none())
+        }) {
+            return Err(Error::RequiredProperty(RequiredPropertyError {
+                message: format!(
+                    "The state change must be set for session state change triggers (trigger '{}')",
+                    trigger.id
+                ),
             }));
         }
+
+        if let Some(trigger) = triggers
+            .iter()
+            .find(|t| t.kind.requires_start_boundary() && t.start_boundary.is_none())
+        {
+            return Err(Error::RequiredProperty(RequiredPropertyError {
+                message: format!(
+                    "The start boundary must be set for this trigger type (trigger '{}')",
+                    trigger.id
+                ),
+            }));
+        }
+
+        self.schedule.definition.validate()?;
         Ok(self.schedule)
     }
 
@@ -404,21 +469,19 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     /// # Example
     /// ```
     /// use planif::schedule::Schedule;
-    /// use planif::schedule_builder::{ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .description("This is my trigger").unwrap()
     ///     .build().unwrap();
     /// ```
-    pub fn description(self, description: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            self.schedule
-                .registration_info
-                .SetDescription(&BSTR::from(description))?;
-        }
+    pub fn description(mut self, description: &str) -> Result<Self, Error> {
+        self.schedule.definition.registration_info.description = Some(description.to_string());
         Ok(self)
     }
 
@@ -426,13 +489,16 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     ///
     /// # Example
     /// ```
+    /// use planif::settings::Duration;
     /// use planif::schedule::Schedule;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("MyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .description("This is my trigger").unwrap()
     ///     .execution_time_limit(Duration {
     ///         minutes: Some(5),
@@ -442,22 +508,20 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     ///     .build().unwrap();
     /// ```
     pub fn execution_time_limit(
-        self,
+        mut self,
         time_limit: Duration,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                trigger.SetExecutionTimeLimit(&BSTR::from(time_limit.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
-        }
+    ) -> Result<Self, Error> {
+        self.current_trigger()?.execution_time_limit = Some(time_limit);
+        Ok(self)
     }
 
     /// Specifies the date and time when the trigger is activated. This call is required on
     /// Calendar triggers and Time Triggers.
-    /// `start_boundary`'s `start` parameter takes a rfc3339 formatted string (ie: 2007-01-01T08:00:00).
+    /// `start_boundary`'s `start` parameter takes a rfc3339 formatted string (ie: 2007-01-01T08:00:00),
+    /// or any other [IntoBoundary](crate::datetime::IntoBoundary) such as a
+    /// [Boundary](crate::datetime::Boundary) or, with the `chrono` feature, a `chrono::DateTime`.
+    /// Strings which are not in the format accepted by the Task Scheduler return a
+    /// [ParseError](crate::error::ParseError).
     ///
     /// ## References
     /// <https://docs.microsoft.com/en-us/windows/win32/taskschd/taskschedulerschema-startboundary-triggerbasetype-element>
@@ -466,9 +530,10 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     /// # Example
     /// ```
     /// use planif::schedule::Schedule;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("DailyTrigger", true).unwrap()
@@ -476,20 +541,43 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .build().unwrap();
     /// ```
-    pub fn start_boundary(mut self, start: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                trigger.SetStartBoundary(&BSTR::from(start))?;
-            }
-            self.schedule.force_start_boundary = false;
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
-        }
+    pub fn start_boundary(mut self, start: impl IntoBoundary) -> Result<Self, Error> {
+        let start = start.into_boundary()?;
+        self.current_trigger()?.start_boundary = Some(start.to_string());
+        self.clear_relative_boundary(false);
+        Ok(self)
+    }
+
+    /// Sets the start boundary to `delay` after the time the schedule is
+    /// [built](ScheduleBuilder::build), as given by the [clock](ScheduleBuilder::clock). The
+    /// boundary is synchronized across time zones, ie: `2023-01-01T08:05:00+00:00` with the system
+    /// clock.
+    ///
+    /// The delay cannot contain months or years, which have no fixed length.
+    ///
+    /// # Example
+    /// ```
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
+    /// use planif::settings::Duration;
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// // Starts in 5 minutes
+    /// let schedule = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_time()
+    ///     .trigger("TimeTrigger", true).unwrap()
+    ///     .start_boundary_in(Duration { minutes: Some(5), ..Default::default() }).unwrap()
+    ///     .build().unwrap();
+    /// ```
+    pub fn start_boundary_in(mut self, delay: Duration) -> Result<Self, Error> {
+        self.set_relative_boundary(false, delay)?;
+        Ok(self)
     }
 
     /// Specifies the date and time when the trigger is deactivated. The trigger cannot start the task after it is deactivated.
     /// `end_boundary`'s `end` parameter takes an rfc3339 formatted string (ie: 2007-01-01T08:00:00)
+    /// or any other [IntoBoundary](crate::datetime::IntoBoundary). The end boundary must be after
+    /// the start boundary, which is checked when the schedule is built.
     ///
     /// ## References
     /// <https://docs.microsoft.com/en-us/windows/win32/taskschd/taskschedulerschema-endboundary-triggerbasetype-element>
@@ -498,25 +586,47 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     /// # Example
     /// ```
     /// use planif::schedule::Schedule;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .description("This is my trigger").unwrap()
-    ///     .end_boundary("2007-01-01T08:00:00").unwrap()
+    ///     .end_boundary("2007-12-31T08:00:00").unwrap()
     ///     .build().unwrap();
     /// ```
-    pub fn end_boundary(self, end: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                trigger.SetEndBoundary(&BSTR::from(end))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
-        }
+    pub fn end_boundary(mut self, end: impl IntoBoundary) -> Result<Self, Error> {
+        let end = end.into_boundary()?;
+        self.current_trigger()?.end_boundary = Some(end.to_string());
+        self.clear_relative_boundary(true);
+        Ok(self)
+    }
+
+    /// Sets the end boundary to `delay` after the time the schedule is
+    /// [built](ScheduleBuilder::build), as given by the [clock](ScheduleBuilder::clock). See
+    /// [start_boundary_in](ScheduleBuilder::start_boundary_in).
+    ///
+    /// # Example
+    /// ```
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
+    /// use planif::settings::Duration;
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// // Expires 30 days after the registration
+    /// let schedule = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_daily()
+    ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary_in(Duration::default()).unwrap()
+    ///     .end_boundary_in(Duration { days: Some(30), ..Default::default() }).unwrap()
+    ///     .build().unwrap();
+    /// ```
+    pub fn end_boundary_in(mut self, delay: Duration) -> Result<Self, Error> {
+        self.set_relative_boundary(true, delay)?;
+        Ok(self)
     }
 
     /// Sets the repetition duration for a task.
@@ -547,45 +657,44 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     ///
     /// # Example
     /// ```
+    /// use planif::settings::Duration;
     /// use planif::schedule::Schedule;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .description("This is my trigger").unwrap()
     ///     .repetition(Duration {
-    ///             minutes: Some(5),
+    ///             hours: Some(1),
     ///             ..Default::default()
     ///         },
     ///         Duration {
-    ///             hours: 1,
+    ///             minutes: Some(5),
     ///             ..Default::default()
     ///         },
     ///         true).unwrap()
     ///     .build().unwrap();
     /// ```
     pub fn repetition(
-        self,
+        mut self,
         duration: Duration,
         interval: Duration,
         stop_at_duration_end: bool,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                let repetition: IRepetitionPattern = trigger.Repetition()?;
-                repetition.SetDuration(&BSTR::from(duration.to_string()))?;
-                repetition.SetInterval(&BSTR::from(interval.to_string()))?;
-                repetition.SetStopAtDurationEnd(VARIANT_BOOL::from(stop_at_duration_end))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
-        }
+    ) -> Result<Self, Error> {
+        self.current_trigger()?.repetition = Some(RepetitionPattern {
+            duration,
+            interval,
+            stop_at_duration_end,
+        });
+        Ok(self)
     }
 
-    /// Sets the task's principal
+    /// Sets the task's principal, which is validated by [PrincipalSettings::validate]. A principal
+    /// is easier to create with a [PrincipalBuilder](crate::settings::PrincipalBuilder).
     /// When specifying an account, remember to properly use the double backslash in code to specify the
     /// domain and user name. For example, use DOMAIN\\UserName to specify a value for the UserId property.
     ///
@@ -594,186 +703,129 @@ impl<Frequency> ScheduleBuilder<Frequency> {
     ///
     /// # Example
     /// ```
-    /// use planif::settings::{ PrincipalSettings, RunLevel, LogonType };
+    /// use planif::settings::{ PrincipalBuilder, RunLevel };
     /// use planif::schedule::Schedule;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder };
-    ///
-    /// let settings = PrincipalSettings {
-    ///     display_name: "Planif".to_string(),
-    ///     group_id: None,
-    ///     id: "MyPrincipalId".to_string(),
-    ///     logon_type: LogonType::ServiceAccount,
-    ///     run_level: RunLevel::LUA,
-    ///     user_id: Some("MyServiceAccount".to_string()),
-    /// };
-    ///
-    /// let com = ComRuntime::new()?;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
+    ///
+    /// let settings = PrincipalBuilder::network_service()
+    ///     .display_name("Planif")
+    ///     .id("MyPrincipalId")
+    ///     .run_level(RunLevel::LUA)
+    ///     .build().unwrap();
+    ///
+    /// let com = ComRuntime::new().unwrap();
     /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .description("This is my trigger").unwrap()
     ///     .principal(settings).unwrap()
     ///     .build().unwrap();
     /// ```
     pub fn principal(
-        self,
+        mut self,
         settings: PrincipalSettings,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let principal: IPrincipal = self.schedule.task_definition.Principal()?;
-            principal.SetDisplayName(&BSTR::from(settings.display_name))?;
-
-            if settings.group_id.is_some() && settings.user_id.is_some() {
-                return Err(Box::new(InvalidOperationError {
-                    message: "Invalid operation: group_id and user_id are mutually exclusive and cannot both be set."
-                        .to_string(),
-                }));
-            } else if let Some(gid) = settings.group_id {
-                principal.SetGroupId(&BSTR::from(gid))?;
-            } else if let Some(uid) = settings.user_id {
-                principal.SetUserId(&BSTR::from(uid))?;
-            }
+    ) -> Result<Self, Error> {
+        settings.validate()?;
+        self.schedule.definition.principal = Some(settings);
+        Ok(self)
+    }
 
-            principal.SetId(&BSTR::from(settings.id))?;
-            principal.SetLogonType(TASK_LOGON_TYPE(settings.logon_type as i32))?;
-            principal.SetRunLevel(TASK_RUNLEVEL_TYPE(settings.run_level as i32))?;
-            self.schedule.task_definition.SetPrincipal(&principal)?;
-            Ok(self)
-        }
+    /// Sets the security descriptor of the task, in SDDL, to restrict who may read, run or change
+    /// it. It is stored in the registration info and applied when the task is registered.
+    /// _optional_
+    ///
+    /// # Example
+    /// ```
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule::Schedule;
+    /// use planif::schedule_builder::ScheduleBuilder;
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_daily()
+    ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
+    ///     .security_descriptor("D:P(A;;FA;;;BA)(A;;FA;;;SY)(A;;FRFX;;;S-1-5-21-1004-1177-6820-1105)").unwrap()
+    ///     .build().unwrap();
+    ///
+    /// assert!(ScheduleBuilder::new(&com).unwrap()
+    ///     .security_descriptor("D:(A;;FA;;;Operators)")
+    ///     .is_err());
+    /// ```
+    pub fn security_descriptor(mut self, sddl: &str) -> Result<Self, Error> {
+        self.schedule.definition.registration_info.security_descriptor = Some(sddl.parse()?);
+        Ok(self)
     }
 
-    /// Sets the Task's settings.
+    /// Sets the Task's settings, which are checked by
+    /// [Settings::validate](crate::settings::Settings::validate).
     /// # Example
     /// ```
-    /// use planif::settings::{ Settings };
+    /// use planif::settings::SettingsBuilder;
     /// use planif::schedule::Schedule;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::ScheduleBuilder;
     ///
-    /// let settings = Settings::new();
-    /// settings.allow_demand_start = Some(true);
+    /// let settings = SettingsBuilder::new()
+    ///     .allow_demand_start(true)
+    ///     .build()
+    ///     .unwrap();
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let schedule: Schedule = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("DailyTrigger", true).unwrap()
+    ///     .start_boundary("2007-01-01T08:00:00").unwrap()
     ///     .description("This is my trigger").unwrap()
-    ///     .principal(settings).unwrap()
-    ///     .build()
-    ///     .settings(settings)
-    ///     .unwrap();
+    ///     .settings(settings).unwrap()
+    ///     .build().unwrap();
     /// ```
-    pub fn settings(self, settings: Settings) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let task_settings: ITaskSettings = self.schedule.task_definition.Settings()?;
-            // Handle idle settings
-            if let Some(s) = settings.idle_settings {
-                let idle_settings = task_settings.IdleSettings()?;
-
-                #[allow(deprecated)]
-                if let Some(setting) = s.idle_duration {
-                    idle_settings.SetIdleDuration(&BSTR::from(setting.to_string()))?;
-                }
-
-                if let Some(setting) = s.restart_on_idle {
-                    idle_settings.SetRestartOnIdle(VARIANT_BOOL::from(setting))?;
-                }
-
-                if let Some(setting) = s.stop_on_idle_end {
-                    idle_settings.SetStopOnIdleEnd(VARIANT_BOOL::from(setting))?;
-                }
-
-                #[allow(deprecated)]
-                if let Some(setting) = s.wait_timeout {
-                    idle_settings.SetWaitTimeout(&BSTR::from(setting.to_string()))?;
-                }
-
-                task_settings.SetIdleSettings(&idle_settings)?;
-            }
-
-            // Handle Network Settings
-            if let Some(s) = settings.network_settings {
-                let network_settings: INetworkSettings = task_settings.NetworkSettings()?;
-                network_settings.SetId(&BSTR::from(s.id))?;
-                network_settings.SetName(&BSTR::from(s.name))?;
-                task_settings.SetNetworkSettings(&network_settings)?;
-            }
-
-            // Handle settings
-            if let Some(s) = settings.allow_demand_start {
-                task_settings.SetAllowDemandStart(VARIANT_BOOL::from(s))?;
-            }
-
-            if let Some(s) = settings.allow_hard_terminate {
-                task_settings.SetAllowHardTerminate(VARIANT_BOOL::from(s))?;
-            }
-
-            if let Some(s) = settings.compatibility {
-                task_settings.SetCompatibility(s.into())?;
-            }
-
-            if let Some(s) = settings.delete_expired_task_after {
-                task_settings.SetDeleteExpiredTaskAfter(&BSTR::from(s.to_string()))?;
-            }
-
-            if let Some(s) = settings.disallow_start_if_on_batteries {
-                task_settings.SetDisallowStartIfOnBatteries(VARIANT_BOOL::from(s))?;
-            }
-
-            if let Some(s) = settings.enabled {
-                task_settings.SetEnabled(VARIANT_BOOL::from(s))?;
-            }
-
-            if let Some(s) = settings.execution_time_limit {
-                task_settings.SetExecutionTimeLimit(&BSTR::from(s))?;
-            }
-
-            if let Some(s) = settings.hidden {
-                task_settings.SetHidden(VARIANT_BOOL::from(s))?;
-            }
-
-            if let Some(s) = settings.multiple_instances_policy {
-                task_settings.SetMultipleInstances(s.into())?;
-            }
-
-            if let Some(s) = settings.priority {
-                task_settings.SetPriority(s)?;
-            }
-
-            if let Some(s) = settings.restart_count {
-                task_settings.SetRestartCount(s)?;
-            }
-
-            if let Some(s) = settings.restart_interval {
-                task_settings.SetRestartInterval(&BSTR::from(s))?;
-            }
-
-            if let Some(s) = settings.run_only_if_idle {
-                task_settings.SetRunOnlyIfIdle(VARIANT_BOOL::from(s))?;
-            }
-
-            if let Some(s) = settings.run_only_if_network_available {
-                task_settings.SetRunOnlyIfNetworkAvailable(VARIANT_BOOL::from(s))?;
-            }
+    pub fn settings(mut self, settings: Settings) -> Result<Self, Error> {
+        settings.validate()?;
+        self.schedule.definition.settings = settings;
+        Ok(self)
+    }
 
-            if let Some(s) = settings.start_when_available {
-                task_settings.SetStartWhenAvailable(VARIANT_BOOL::from(s))?;
-            }
+    fn with_frequency<Next>(self) -> ScheduleBuilder<Next> {
+        ScheduleBuilder::<Next> {
+            frequency: std::marker::PhantomData::<Next>,
+            schedule: self.schedule,
+            current_trigger: None,
+            clock: self.clock,
+            relative_boundaries: self.relative_boundaries,
+        }
+    }
 
-            if let Some(s) = settings.stop_if_going_on_batteries {
-                task_settings.SetStopIfGoingOnBatteries(VARIANT_BOOL::from(s))?;
-            }
+    fn set_relative_boundary(&mut self, end: bool, delay: Duration) -> Result<(), Error> {
+        let seconds = i64::try_from(delay.total_seconds()?).map_err(|_| ConversionError {
+            message: format!("{} is too long", delay),
+        })?;
+        let trigger = self.current_trigger.ok_or_else(trigger_uninitialised_error)?;
+        self.clear_relative_boundary(end);
+        self.relative_boundaries.push(RelativeBoundary {
+            trigger,
+            end,
+            seconds,
+        });
+        Ok(())
+    }
 
-            if let Some(s) = settings.wake_to_run {
-                task_settings.SetWakeToRun(VARIANT_BOOL::from(s))?;
-            }
+    fn clear_relative_boundary(&mut self, end: bool) {
+        let current = self.current_trigger;
+        self.relative_boundaries
+            .retain(|b| Some(b.trigger) != current || b.end != end);
+    }
 
-            if let Some(s) = settings.xml_text {
-                task_settings.SetXmlText(&BSTR::from(s))?;
-            }
+    fn push_trigger(&mut self, trigger: Trigger) {
+        self.schedule.definition.triggers.push(trigger);
+        self.current_trigger = Some(self.schedule.definition.triggers.len() - 1);
+    }
 
-            self.schedule.task_definition.SetSettings(&task_settings)?;
-        }
-        Ok(self)
+    fn current_trigger(&mut self) -> Result<&mut Trigger, Error> {
+        let index = self.current_trigger.ok_or_else(trigger_uninitialised_error)?;
+        Ok(&mut self.schedule.definition.triggers[index])
     }
 }
 
@@ -786,24 +838,19 @@ impl ScheduleBuilder<Boot> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ Boot, ComRuntime, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Boot, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Boot> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_boot()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_BOOT)?;
-            let i_boot_trigger: IBootTrigger = trigger.cast::<IBootTrigger>()?;
-            i_boot_trigger.SetId(&BSTR::from(id))?;
-            i_boot_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-
-            // Default start boundary to now()
-            self.schedule.trigger = Some(i_boot_trigger.cast::<ITrigger>()?);
-        }
-
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::Boot(BootTrigger::default()))
+        });
         Ok(self)
     }
 
@@ -813,24 +860,21 @@ impl ScheduleBuilder<Boot> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ Boot, ComRuntime, ScheduleBuilder };
+    /// use planif::settings::Duration;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Boot, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Boot> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_boot()
     ///     .trigger("MyTrigger", true).unwrap()
-    ///     .delay(Duration { seconds: Some(2), days: Some(5) }).unwrap();
+    ///     .delay(Duration { seconds: Some(2), days: Some(5), ..Default::default() }).unwrap();
     /// ```
-    pub fn delay(self, delay: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_boot_trigger: IBootTrigger = trigger.cast::<IBootTrigger>()?;
-                i_boot_trigger.SetDelay(&BSTR::from(delay.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::Boot(boot) = &mut self.current_trigger()?.kind {
+            boot.delay = Some(delay);
         }
+        Ok(self)
     }
 }
 
@@ -842,21 +886,19 @@ impl ScheduleBuilder<Daily> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, Daily, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Daily, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Daily> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_DAILY)?;
-            let i_daily_trigger: IDailyTrigger = trigger.cast::<IDailyTrigger>()?;
-            i_daily_trigger.SetId(&BSTR::from(id))?;
-            i_daily_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-            self.schedule.trigger = Some(i_daily_trigger.cast::<ITrigger>()?);
-        }
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::Daily(DailyTrigger::default()))
+        });
         Ok(self)
     }
 
@@ -865,24 +907,20 @@ impl ScheduleBuilder<Daily> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, Daily, ScheduleBuilder };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Daily, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Daily> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("MyTrigger", true).unwrap()
     ///     .days_interval(1).unwrap();
     /// ```
-    pub fn days_interval(self, days: i16) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_daily_trigger: IDailyTrigger = i_trigger.cast::<IDailyTrigger>()?;
-                i_daily_trigger.SetDaysInterval(days)?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn days_interval(mut self, days: i16) -> Result<Self, Error> {
+        if let TriggerKind::Daily(daily) = &mut self.current_trigger()?.kind {
+            daily.days_interval = Some(days);
         }
+        Ok(self)
     }
 
     /// Specifies the delay time that is randomly added to the start time of the trigger.
@@ -890,24 +928,21 @@ impl ScheduleBuilder<Daily> {
     /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/taskschedulerschema-randomdelay-timetriggertype-element>
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, Daily, ScheduleBuilder };
+    /// use planif::settings::Duration;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{Daily, ScheduleBuilder};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Daily> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_daily()
     ///     .trigger("MyTrigger", true).unwrap()
-    ///     .random_delay(Duration { seconds: Some(5), days: Some(2) }).unwrap();
+    ///     .random_delay(Duration { seconds: Some(5), days: Some(2), ..Default::default() }).unwrap();
     /// ```
-    pub fn random_delay(self, delay: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_daily_trigger: IDailyTrigger = i_trigger.cast::<IDailyTrigger>()?;
-                i_daily_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn random_delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::Daily(daily) = &mut self.current_trigger()?.kind {
+            daily.random_delay = Some(delay);
         }
+        Ok(self)
     }
 }
 
@@ -918,24 +953,21 @@ impl ScheduleBuilder<Event> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Event };
+    /// use planif::settings::Duration;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Event};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Event> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_event()
     ///     .trigger("MyTrigger", true).unwrap()
-    ///     .delay(Duration { seconds: Some(2), days: Some(5) }).unwrap();
+    ///     .delay(Duration { seconds: Some(2), days: Some(5), ..Default::default() }).unwrap();
     /// ```
-    pub fn delay(self, delay: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_event_trigger: IEventTrigger = trigger.cast::<IEventTrigger>()?;
-                i_event_trigger.SetDelay(&BSTR::from(delay.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::Event(event) = &mut self.current_trigger()?.kind {
+            event.delay = Some(delay);
         }
+        Ok(self)
     }
 
     /// Specifies a query string that identifies the event that fires the trigger.
@@ -944,37 +976,30 @@ impl ScheduleBuilder<Event> {
     /// <https://docs.microsoft.com/en-us/previous-versions//aa385231(v=vs.85)>
     ///
     /// See Subscribing to Events: <https://docs.microsoft.com/en-us/windows/win32/wes/subscribing-to-events>
-    pub fn subscription(self, query: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_event_trigger: IEventTrigger = trigger.cast::<IEventTrigger>()?;
-                i_event_trigger.SetSubscription(&BSTR::from(query))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn subscription(mut self, query: &str) -> Result<Self, Error> {
+        if let TriggerKind::Event(event) = &mut self.current_trigger()?.kind {
+            event.subscription = Some(query.to_string());
         }
+        Ok(self)
     }
 
     /// Create an event trigger.
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Event };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Event};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Event> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_event()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_EVENT)?;
-            let i_event_trigger: IEventTrigger = trigger.cast::<IEventTrigger>()?;
-            i_event_trigger.SetId(&BSTR::from(id))?;
-            i_event_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-            self.schedule.trigger = Some(i_event_trigger.cast::<ITrigger>()?);
-        }
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::Event(EventTrigger::default()))
+        });
         Ok(self)
     }
 
@@ -984,24 +1009,16 @@ impl ScheduleBuilder<Event> {
     ///
     /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/eventtrigger-valuequeries>
     pub fn value_queries(
-        self,
+        mut self,
         queries: Vec<(&str, &str)>,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_event_trigger: IEventTrigger = trigger.cast::<IEventTrigger>()?;
-                let i_task_named_value_collection = i_event_trigger.ValueQueries()?;
-
-                for (name, value) in queries {
-                    i_task_named_value_collection.Create(&BSTR::from(name), &BSTR::from(value))?;
-                }
-
-                i_event_trigger.SetValueQueries(&i_task_named_value_collection)?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    ) -> Result<Self, Error> {
+        if let TriggerKind::Event(event) = &mut self.current_trigger()?.kind {
+            event.value_queries = queries
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
         }
+        Ok(self)
     }
 }
 
@@ -1010,21 +1027,19 @@ impl ScheduleBuilder<Idle> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Idle };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Idle};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Idle> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_idle()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_IDLE)?;
-            let i_idle_trigger: IIdleTrigger = trigger.cast::<IIdleTrigger>()?;
-            i_idle_trigger.SetId(&BSTR::from(id))?;
-            i_idle_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-            self.schedule.trigger = Some(i_idle_trigger.cast::<ITrigger>()?);
-        }
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::Idle)
+        });
         Ok(self)
     }
 }
@@ -1034,22 +1049,19 @@ impl ScheduleBuilder<Logon> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Logon };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Logon};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Logon> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_logon()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_LOGON)?;
-            let i_logon_trigger: ILogonTrigger = trigger.cast::<ILogonTrigger>()?;
-            i_logon_trigger.SetId(&BSTR::from(id))?;
-            i_logon_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-
-            self.schedule.trigger = Some(i_logon_trigger.cast::<ITrigger>()?);
-        }
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::Logon(LogonTrigger::default()))
+        });
         Ok(self)
     }
 
@@ -1059,24 +1071,21 @@ impl ScheduleBuilder<Logon> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Logon };
+    /// use planif::settings::Duration;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Logon};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Logon> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_logon()
     ///     .trigger("MyTrigger", true).unwrap()
-    ///     .delay(Duration { seconds: Some(5), days: Some(2) }).unwrap();
+    ///     .delay(Duration { seconds: Some(5), days: Some(2), ..Default::default() }).unwrap();
     /// ```
-    pub fn delay(self, delay: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_logon_trigger: ILogonTrigger = trigger.cast::<ILogonTrigger>()?;
-                i_logon_trigger.SetDelay(&BSTR::from(delay.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::Logon(logon) = &mut self.current_trigger()?.kind {
+            logon.delay = Some(delay);
         }
+        Ok(self)
     }
 
     /// The identifier of the user. For example, "MyDomain\MyName" or for a local account, "Administrator".
@@ -1089,24 +1098,20 @@ impl ScheduleBuilder<Logon> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Logon };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Logon};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Logon> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_logon()
     ///     .trigger("MyTrigger", true).unwrap()
     ///     .user_id("MyDomain\\User").unwrap();
     /// ```
-    pub fn user_id(self, id: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_logon_trigger: ILogonTrigger = trigger.cast::<ILogonTrigger>()?;
-                i_logon_trigger.SetUserId(&BSTR::from(id))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn user_id(mut self, id: &str) -> Result<Self, Error> {
+        if let TriggerKind::Logon(logon) = &mut self.current_trigger()?.kind {
+            logon.user_id = Some(id.to_string());
         }
+        Ok(self)
     }
 }
 
@@ -1115,71 +1120,57 @@ impl ScheduleBuilder<Monthly> {
     /// # Example
     /// ```
     /// use planif::enums::DayOfMonth;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Monthly };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Monthly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Monthly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly()
     ///     .trigger("MyTrigger", true).unwrap()
     ///     .days_of_month(vec![DayOfMonth::Day(1), DayOfMonth::Day(15),
     ///     DayOfMonth::Day(31)]).unwrap();
     /// ```
-    pub fn days_of_month(self, days: Vec<DayOfMonth>) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            let is_out_of_bounds = days.iter().any(|x| match &x {
-                DayOfMonth::Day(int) => !(&1..=&31).contains(&int),
-                DayOfMonth::Last => false,
-            });
-
-            if is_out_of_bounds {
-                return Err(Box::new(InvalidOperationError {
-                    message:
-                        "Index out of bounds. Days of month must be between 1 and 31 inclusively."
-                            .to_string(),
-                }));
-            }
+    pub fn days_of_month(mut self, days: Vec<DayOfMonth>) -> Result<Self, Error> {
+        let is_out_of_bounds = days.iter().any(|x| match &x {
+            DayOfMonth::Day(int) => !(&1..=&31).contains(&int),
+            DayOfMonth::Last => false,
+        });
 
-            let bitwise = days.into_iter().fold(0, |acc, item| {
-                let day: i32 = item.into();
-                acc + day
-            });
+        if is_out_of_bounds {
+            return Err(Error::InvalidOperation(InvalidOperationError {
+                message:
+                    "Index out of bounds. Days of month must be between 1 and 31 inclusively."
+                        .to_string(),
+            }));
+        }
 
-            unsafe {
-                let i_monthly_trigger: IMonthlyTrigger = i_trigger.cast::<IMonthlyTrigger>()?;
-                i_monthly_trigger.SetDaysOfMonth(bitwise)?;
-            }
+        let bitwise = days.into_iter().fold(0, |acc, item| acc | i32::from(item));
 
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+        if let TriggerKind::Monthly(monthly) = &mut self.current_trigger()?.kind {
+            monthly.days_of_month = Some(bitwise);
         }
+        Ok(self)
     }
 
     /// Set the months of the year during which the task runs.
+    /// Accepts a [Months] set, a single month, or a `Vec` or array of months.
     /// # Example
     /// ```
     /// use planif::enums::Month;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Monthly };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Monthly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Monthly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly()
     ///     .trigger("MyTrigger", true).unwrap()
     ///     .months_of_year(vec![Month::January, Month::June, Month::December]).unwrap();
     /// ```
-    pub fn months_of_year(self, months: Vec<Month>) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            let bitwise: i16 = months.into_iter().fold(0, |acc, item| acc + item as i16);
-
-            unsafe {
-                let i_monthly_trigger: IMonthlyTrigger = i_trigger.cast::<IMonthlyTrigger>()?;
-                i_monthly_trigger.SetMonthsOfYear(bitwise)?;
-            }
-
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn months_of_year(mut self, months: impl Into<Months>) -> Result<Self, Error> {
+        if let TriggerKind::Monthly(monthly) = &mut self.current_trigger()?.kind {
+            monthly.months_of_year = Some(months.into());
         }
+        Ok(self)
     }
 
     /// Specifies the delay time that is randomly added to the start time of the trigger.
@@ -1187,24 +1178,21 @@ impl ScheduleBuilder<Monthly> {
     /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/taskschedulerschema-randomdelay-timetriggertype-element>
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Monthly };
+    /// use planif::settings::Duration;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Monthly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Monthly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly()
     ///     .trigger("MyTrigger", true).unwrap()
-    ///     .random_delay(Duration { days: Some(2), seconds: Some(5) }).unwrap();
+    ///     .random_delay(Duration { days: Some(2), seconds: Some(5), ..Default::default() }).unwrap();
     /// ```
-    pub fn random_delay(self, delay: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_monthly_trigger: IMonthlyTrigger = i_trigger.cast::<IMonthlyTrigger>()?;
-                i_monthly_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn random_delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::Monthly(monthly) = &mut self.current_trigger()?.kind {
+            monthly.random_delay = Some(delay);
         }
+        Ok(self)
     }
 
     /// Sets the task to be run on the last day of the month, regardless of the actual date of
@@ -1212,104 +1200,85 @@ impl ScheduleBuilder<Monthly> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Monthly };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Monthly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Monthly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly()
     ///     .trigger("MyTrigger", true).unwrap()
     ///     .run_on_last_day(true).unwrap();
     /// ```
-    pub fn run_on_last_day(self, is_run: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_monthly_trigger: IMonthlyTrigger = i_trigger.cast::<IMonthlyTrigger>()?;
-                i_monthly_trigger.SetRunOnLastDayOfMonth(VARIANT_BOOL::from(is_run))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn run_on_last_day(mut self, is_run: bool) -> Result<Self, Error> {
+        if let TriggerKind::Monthly(monthly) = &mut self.current_trigger()?.kind {
+            monthly.run_on_last_day = Some(is_run);
         }
+        Ok(self)
     }
 
     /// Creates a trigger based on a monthly schedule, for example, the task starts on specific
     /// days of specific months
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Monthly };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Monthly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Monthly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            self.schedule.force_start_boundary = true;
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_MONTHLY)?;
-            let i_monthly_trigger: IMonthlyTrigger = trigger.cast::<IMonthlyTrigger>()?;
-            i_monthly_trigger.SetId(&BSTR::from(id))?;
-            i_monthly_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-            self.schedule.trigger = Some(i_monthly_trigger.cast::<ITrigger>()?);
-        }
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::Monthly(MonthlyTrigger::default()))
+        });
         Ok(self)
     }
 }
 
 impl ScheduleBuilder<MonthlyDOW> {
     /// Sets the days of the week during which the task runs.
+    /// Accepts a [DaysOfWeek] set, a single day, or a `Vec` or array of days.
     ///
     /// # Example
     /// ```
     /// use planif::enums::DayOfWeek;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, MonthlyDOW };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, MonthlyDOW};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<MonthlyDOW> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly_dow()
     ///     .trigger("MonthlyDOWTrigger", true).unwrap()
     ///     .days_of_week(vec![DayOfWeek::Sunday, DayOfWeek::Thursday]).unwrap();
     /// ```
-    pub fn days_of_week(self, days: Vec<DayOfWeek>) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            let bitwise: i16 = days.into_iter().fold(0, |acc, item| acc + item as i16);
-            unsafe {
-                let i_monthly_dow_trigger: IMonthlyDOWTrigger =
-                    trigger.cast::<IMonthlyDOWTrigger>()?;
-                i_monthly_dow_trigger.SetDaysOfWeek(bitwise)?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn days_of_week(mut self, days: impl Into<DaysOfWeek>) -> Result<Self, Error> {
+        if let TriggerKind::MonthlyDOW(monthly_dow) = &mut self.current_trigger()?.kind {
+            monthly_dow.days_of_week = Some(days.into());
         }
+        Ok(self)
     }
 
     /// Set the months of the year during which the task runs.
+    /// Accepts a [Months] set, a single month, or a `Vec` or array of months.
     /// # Example
     /// ```
     /// use planif::enums::Month;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, MonthlyDOW };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, MonthlyDOW};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<MonthlyDOW> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly_dow()
     ///     .trigger("MyTrigger", true).unwrap()
     ///     .months_of_year(vec![Month::January, Month::June, Month::December]).unwrap();
     /// ```
-    pub fn months_of_year(self, months: Vec<Month>) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            let bitwise: i16 = months.into_iter().fold(0, |acc, item| acc + item as i16);
-
-            unsafe {
-                let i_monthly_dow_trigger: IMonthlyDOWTrigger =
-                    i_trigger.cast::<IMonthlyDOWTrigger>()?;
-                i_monthly_dow_trigger.SetMonthsOfYear(bitwise)?;
-            }
-
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn months_of_year(mut self, months: impl Into<Months>) -> Result<Self, Error> {
+        if let TriggerKind::MonthlyDOW(monthly_dow) = &mut self.current_trigger()?.kind {
+            monthly_dow.months_of_year = Some(months.into());
         }
+        Ok(self)
     }
 
     /// Specifies the delay time that is randomly added to the start time of the trigger.
@@ -1317,25 +1286,21 @@ impl ScheduleBuilder<MonthlyDOW> {
     /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/taskschedulerschema-randomdelay-timetriggertype-element>
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, MonthlyDOW };
+    /// use planif::settings::Duration;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, MonthlyDOW};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<MonthlyDOW> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly_dow()
     ///     .trigger("MyTrigger", true).unwrap()
-    ///     .random_delay(Duration { seconds: Some(2), days: Some(5) }).unwrap();
-    /// ```
-    pub fn random_delay(self, delay: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_monthly_dow_trigger: IMonthlyDOWTrigger =
-                    i_trigger.cast::<IMonthlyDOWTrigger>()?;
-                i_monthly_dow_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    ///     .random_delay(Duration { seconds: Some(2), days: Some(5), ..Default::default() }).unwrap();
+    /// ```
+    pub fn random_delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::MonthlyDOW(monthly_dow) = &mut self.current_trigger()?.kind {
+            monthly_dow.random_delay = Some(delay);
         }
+        Ok(self)
     }
 
     /// Sets the task to be run on the last day of the month, regardless of the actual date of
@@ -1343,55 +1308,42 @@ impl ScheduleBuilder<MonthlyDOW> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, MonthlyDOW };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, MonthlyDOW};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<MonthlyDOW> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly_dow()
     ///     .trigger("MyTrigger", true).unwrap()
     ///     .run_on_last_week(true).unwrap();
     /// ```
-    pub fn run_on_last_week(self, is_run: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_monthly_dow_trigger: IMonthlyDOWTrigger =
-                    trigger.cast::<IMonthlyDOWTrigger>()?;
-                i_monthly_dow_trigger.SetRunOnLastWeekOfMonth(VARIANT_BOOL::from(is_run))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn run_on_last_week(mut self, is_run: bool) -> Result<Self, Error> {
+        if let TriggerKind::MonthlyDOW(monthly_dow) = &mut self.current_trigger()?.kind {
+            monthly_dow.run_on_last_week = Some(is_run);
         }
+        Ok(self)
     }
 
     /// Sets the weeks of the month during which the task runs.
+    /// Accepts a [WeeksOfMonth] set, a single week, or a `Vec` or array of weeks.
     ///
     /// # Example
     /// ```
     /// use planif::enums::WeekOfMonth;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, MonthlyDOW };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, MonthlyDOW};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<MonthlyDOW> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly_dow()
     ///     .trigger("MyTrigger", true).unwrap()
     ///     .weeks_of_month(vec![WeekOfMonth::Third]).unwrap();
     /// ```
-    pub fn weeks_of_month(
-        self,
-        weeks: Vec<WeekOfMonth>,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            let bitwise: i16 = weeks.into_iter().fold(0, |acc, item| acc + item as i16);
-            unsafe {
-                let i_monthly_dow_trigger: IMonthlyDOWTrigger =
-                    trigger.cast::<IMonthlyDOWTrigger>()?;
-                i_monthly_dow_trigger.SetWeeksOfMonth(bitwise)?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn weeks_of_month(mut self, weeks: impl Into<WeeksOfMonth>) -> Result<Self, Error> {
+        if let TriggerKind::MonthlyDOW(monthly_dow) = &mut self.current_trigger()?.kind {
+            monthly_dow.weeks_of_month = Some(weeks.into());
         }
+        Ok(self)
     }
 
     /// Creates a trigger that starts a task on a monthly day-of-week schedule. For example, the
@@ -1399,21 +1351,19 @@ impl ScheduleBuilder<MonthlyDOW> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, MonthlyDOW };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, MonthlyDOW};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<MonthlyDOW> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_monthly_dow()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_MONTHLYDOW)?;
-            let i_monthly_dow_trigger: IMonthlyDOWTrigger = trigger.cast::<IMonthlyDOWTrigger>()?;
-            i_monthly_dow_trigger.SetId(&BSTR::from(id))?;
-            i_monthly_dow_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-            self.schedule.trigger = Some(i_monthly_dow_trigger.cast::<ITrigger>()?);
-        }
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::MonthlyDOW(MonthlyDOWTrigger::default()))
+        });
         Ok(self)
     }
 }
@@ -1425,9 +1375,11 @@ impl ScheduleBuilder<Registration> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Registration };
+    /// use planif::settings::Duration;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Registration};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Registration> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_registration()
     ///     .trigger("MyTrigger", true).unwrap()
@@ -1436,38 +1388,132 @@ impl ScheduleBuilder<Registration> {
     ///         ..Default::default()
     ///     }).unwrap();
     /// ```
-    pub fn delay(self, delay: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_registration_trigger: IRegistrationTrigger =
-                    trigger.cast::<IRegistrationTrigger>()?;
-                i_registration_trigger.SetDelay(&BSTR::from(delay.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::Registration(registration) = &mut self.current_trigger()?.kind {
+            registration.delay = Some(delay);
         }
+        Ok(self)
     }
 
     /// Creates a trigger that starts a task when the task is registered or updated.
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Registration };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Registration};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Registration> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_registration()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_REGISTRATION)?;
-            let i_registration_trigger: IRegistrationTrigger =
-                trigger.cast::<IRegistrationTrigger>()?;
-            i_registration_trigger.SetId(&BSTR::from(id))?;
-            i_registration_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-            self.schedule.trigger = Some(i_registration_trigger.cast::<ITrigger>()?);
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::Registration(RegistrationTrigger::default()))
+        });
+        Ok(self)
+    }
+}
+
+impl ScheduleBuilder<SessionStateChange> {
+    /// Create a session state change trigger.
+    /// The kind of state change which starts the task is set by the
+    /// [state_change](ScheduleBuilder::<SessionStateChange>::state_change) method.
+    ///
+    /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/sessionstatechangetrigger>
+    ///
+    /// # Example
+    /// ```
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, SessionStateChange};
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// let builder: ScheduleBuilder<SessionStateChange> = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_session_state_change()
+    ///     .trigger("MyTrigger", true).unwrap();
+    /// ```
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(
+                id,
+                TriggerKind::SessionStateChange(SessionStateChangeTrigger::default()),
+            )
+        });
+        Ok(self)
+    }
+
+    /// Specifies a value that indicates the amount of time between when the session state changes
+    /// and when the task is started.
+    ///
+    /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/sessionstatechangetrigger-delay>
+    ///
+    /// # Example
+    /// ```
+    /// use planif::settings::Duration;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, SessionStateChange};
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// let builder: ScheduleBuilder<SessionStateChange> = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_session_state_change()
+    ///     .trigger("MyTrigger", true).unwrap()
+    ///     .delay(Duration { minutes: Some(5), ..Default::default() }).unwrap();
+    /// ```
+    pub fn delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::SessionStateChange(session) = &mut self.current_trigger()?.kind {
+            session.delay = Some(delay);
+        }
+        Ok(self)
+    }
+
+    /// The kind of session state change that starts the task.
+    /// _required_
+    ///
+    /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/sessionstatechangetrigger-statechange>
+    ///
+    /// # Example
+    /// ```
+    /// use planif::enums::StateChange;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, SessionStateChange};
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// let builder: ScheduleBuilder<SessionStateChange> = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_session_state_change()
+    ///     .trigger("MyTrigger", true).unwrap()
+    ///     .state_change(StateChange::SessionLock).unwrap();
+    /// ```
+    pub fn state_change(
+        mut self,
+        state_change: StateChange,
+    ) -> Result<Self, Error> {
+        if let TriggerKind::SessionStateChange(session) = &mut self.current_trigger()?.kind {
+            session.state_change = Some(state_change);
+        }
+        Ok(self)
+    }
+
+    /// The user for the Terminal Server session. For example, "MyDomain\MyName".
+    /// When not set, the task is started when the session state of any user changes.
+    ///
+    /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/sessionstatechangetrigger-userid>
+    ///
+    /// # Example
+    /// ```
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, SessionStateChange};
+    ///
+    /// let com = ComRuntime::new().unwrap();
+    /// let builder: ScheduleBuilder<SessionStateChange> = ScheduleBuilder::new(&com).unwrap()
+    ///     .create_session_state_change()
+    ///     .trigger("MyTrigger", true).unwrap()
+    ///     .user_id("MyDomain\\User").unwrap();
+    /// ```
+    pub fn user_id(mut self, id: &str) -> Result<Self, Error> {
+        if let TriggerKind::SessionStateChange(session) = &mut self.current_trigger()?.kind {
+            session.user_id = Some(id.to_string());
         }
         Ok(self)
     }
@@ -1482,22 +1528,19 @@ impl ScheduleBuilder<Time> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Time };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Time};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Time> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_time()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_TIME)?;
-            let i_time_trigger: ITimeTrigger = trigger.cast::<ITimeTrigger>()?;
-            i_time_trigger.SetId(&BSTR::from(id))?;
-            i_time_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-
-            self.schedule.trigger = Some(i_time_trigger.cast::<ITrigger>()?);
-        }
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::Time(TimeTrigger::default()))
+        });
         Ok(self)
     }
 
@@ -1507,24 +1550,21 @@ impl ScheduleBuilder<Time> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Time };
+    /// use planif::settings::Duration;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Time};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Time> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_time()
     ///     .trigger("MyTrigger", true).unwrap()
-    ///     .random_delay(Duration {days: Some(2), seconds: Some(5) }).unwrap();
+    ///     .random_delay(Duration { days: Some(2), seconds: Some(5), ..Default::default() }).unwrap();
     /// ```
-    pub fn random_delay(self, delay: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_time_trigger: ITimeTrigger = i_trigger.cast::<ITimeTrigger>()?;
-                i_time_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn random_delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::Time(time) = &mut self.current_trigger()?.kind {
+            time.random_delay = Some(delay);
         }
+        Ok(self)
     }
 }
 
@@ -1534,49 +1574,42 @@ impl ScheduleBuilder<Weekly> {
     ///
     /// # Example
     /// ```
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Weekly };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Weekly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Weekly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_weekly()
     ///     .trigger("MyTrigger", true).unwrap();
     /// ```
-    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Box<dyn std::error::Error>> {
-        unsafe {
-            let trigger = self.schedule.triggers.Create(TASK_TRIGGER_WEEKLY)?;
-            let i_weekly_trigger: IWeeklyTrigger = trigger.cast::<IWeeklyTrigger>()?;
-            i_weekly_trigger.SetId(&BSTR::from(id))?;
-            i_weekly_trigger.SetEnabled(VARIANT_BOOL::from(enabled))?;
-
-            self.schedule.trigger = Some(i_weekly_trigger.cast::<ITrigger>()?);
-        }
+    pub fn trigger(mut self, id: &str, enabled: bool) -> Result<Self, Error> {
+        self.push_trigger(Trigger {
+            enabled,
+            ..Trigger::new(id, TriggerKind::Weekly(WeeklyTrigger::default()))
+        });
         Ok(self)
     }
 
     /// Sets the days of the week during which the task runs.
+    /// Accepts a [DaysOfWeek] set, a single day, or a `Vec` or array of days.
     ///
     /// # Example
     /// ```
-    /// use planif::enums::DayOfWeek;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Weekly };
+    /// use planif::calendar::DaysOfWeek;
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Weekly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Weekly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_weekly()
     ///     .trigger("MyTrigger", true).unwrap()
-    ///     .days_of_week(vec![DayOfWeek::Sunday, DayOfWeek::Thursday]).unwrap();
+    ///     .days_of_week(DaysOfWeek::weekdays()).unwrap();
     /// ```
-    pub fn days_of_week(self, days: Vec<DayOfWeek>) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            let bitwise: i16 = days.into_iter().fold(0, |acc, item| acc + item as i16);
-            unsafe {
-                let i_weekly_trigger: IWeeklyTrigger = i_trigger.cast::<IWeeklyTrigger>()?;
-                i_weekly_trigger.SetDaysOfWeek(bitwise)?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn days_of_week(mut self, days: impl Into<DaysOfWeek>) -> Result<Self, Error> {
+        if let TriggerKind::Weekly(weekly) = &mut self.current_trigger()?.kind {
+            weekly.days_of_week = Some(days.into());
         }
+        Ok(self)
     }
 
     /// Sets the interval between the weeks in the schedule.
@@ -1585,24 +1618,20 @@ impl ScheduleBuilder<Weekly> {
     /// # Example
     /// ```
     /// use planif::enums::DayOfWeek;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Weekly };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Weekly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Weekly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_weekly()
     ///     .trigger("MyTrigger", true).unwrap()
     ///     .weeks_interval(1).unwrap();
     /// ```
-    pub fn weeks_interval(self, weeks: i16) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_weekly_trigger: IWeeklyTrigger = i_trigger.cast::<IWeeklyTrigger>()?;
-                i_weekly_trigger.SetWeeksInterval(weeks)?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn weeks_interval(mut self, weeks: i16) -> Result<Self, Error> {
+        if let TriggerKind::Weekly(weekly) = &mut self.current_trigger()?.kind {
+            weekly.weeks_interval = Some(weeks);
         }
+        Ok(self)
     }
 
     /// Specifies the delay time that is randomly added to the start time of the trigger.
@@ -1611,30 +1640,27 @@ impl ScheduleBuilder<Weekly> {
     ///
     /// # Example
     /// ```
+    /// use planif::settings::Duration;
     /// use planif::enums::DayOfWeek;
-    /// use planif::schedule_builder::{ ComRuntime, ScheduleBuilder, Weekly };
+    /// use planif::com::ComRuntime;
+    /// use planif::schedule_builder::{ScheduleBuilder, Weekly};
     ///
-    /// let com = ComRuntime::new()?;
+    /// let com = ComRuntime::new().unwrap();
     /// let builder: ScheduleBuilder<Weekly> = ScheduleBuilder::new(&com).unwrap()
     ///     .create_weekly()
     ///     .trigger("MyTrigger", true).unwrap()
-    ///     .random_delay(Duration { seconds: Some(5), days: Some(2) }).unwrap();
+    ///     .random_delay(Duration { seconds: Some(5), days: Some(2), ..Default::default() }).unwrap();
     /// ```
-    pub fn random_delay(self, delay: Duration) -> Result<Self, Box<dyn std::error::Error>> {
-        if let Some(i_trigger) = &self.schedule.trigger {
-            unsafe {
-                let i_weekly_trigger: IWeeklyTrigger = i_trigger.cast::<IWeeklyTrigger>()?;
-                i_weekly_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
-            }
-            Ok(self)
-        } else {
-            Err(trigger_uninitialised_error())
+    pub fn random_delay(mut self, delay: Duration) -> Result<Self, Error> {
+        if let TriggerKind::Weekly(weekly) = &mut self.current_trigger()?.kind {
+            weekly.random_delay = Some(delay);
         }
+        Ok(self)
     }
 }
 
-fn trigger_uninitialised_error() -> Box<dyn std::error::Error> {
-    Box::new(InvalidOperationError {
+fn trigger_uninitialised_error() -> Error {
+    Error::InvalidOperation(InvalidOperationError {
         message: "Trigger has not been created yet. Consider calling ScheduleBuilder.Trigger()"
             .to_string(),
     })
@@ -1642,24 +1668,744 @@ fn trigger_uninitialised_error() -> Box<dyn std::error::Error> {
 
 /* actions */
 /// `Action`s defines the action a scheduled task will take.
-/// Currently, only exec actions are support.
-#[derive(Debug, Clone)]
-pub struct Action {
-    id: BSTR,
-    path: BSTR,
-    working_dir: BSTR,
-    args: BSTR,
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/action>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    /// Executes a command-line operation.
+    Exec(ExecAction),
+    /// Fires a handler implementing the `ITaskHandler` COM interface.
+    ComHandler(ComHandlerAction),
 }
 
 impl Action {
     /// The work items performed by a task are called actions. A task can have a single action
     /// or a maximum of 32 actions. Be aware that when multiple actions are specified, they are executed sequentially.
+    ///
+    /// Creates an [exec action](ExecAction).
     pub fn new(id: &str, path: &str, working_dir: &str, args: &str) -> Self {
-        Self {
+        Action::Exec(ExecAction {
             id: id.into(),
             path: path.into(),
             working_dir: working_dir.into(),
             args: args.into(),
+        })
+    }
+
+    /// Creates a [COM handler action](ComHandlerAction).
+    /// The `class_id` is the GUID of the class implementing `ITaskHandler`, with or without braces,
+    /// and `data` is passed to the handler when it is started.
+    ///
+    /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/comhandleraction>
+    ///
+    /// # Example
+    /// ```
+    /// use planif::schedule_builder::Action;
+    ///
+    /// let action = Action::com_handler(
+    ///     "id",
+    ///     "{0BE8D5D8-C4DF-4DE6-A4E7-FEE01D6F3C49}",
+    ///     "MyData",
+    /// ).unwrap();
+    ///
+    /// assert!(Action::com_handler("id", "not a guid", "").is_err());
+    /// ```
+    pub fn com_handler(
+        id: &str,
+        class_id: &str,
+        data: &str,
+    ) -> Result<Self, Error> {
+        Ok(Action::ComHandler(ComHandlerAction {
+            id: id.into(),
+            class_id: parse_class_id(class_id)?,
+            data: data.into(),
+        }))
+    }
+
+    /// The identifier of the action.
+    pub fn id(&self) -> &str {
+        match self {
+            Action::Exec(exec) => &exec.id,
+            Action::ComHandler(com_handler) => &com_handler.id,
+        }
+    }
+}
+
+/// An action that executes a command-line operation.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/execaction>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecAction {
+    /// The identifier of the action.
+    pub id: String,
+    /// The path to an executable file.
+    pub path: String,
+    /// The directory that contains either the executable file or the files that are used by the executable file.
+    pub working_dir: String,
+    /// The arguments associated with the command-line operation.
+    pub args: String,
+}
+
+/// An action that fires a handler implementing the `ITaskHandler` COM interface.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/comhandleraction>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComHandlerAction {
+    /// The identifier of the action.
+    pub id: String,
+    /// The identifier of the handler class, a GUID in braces (ie: `{0BE8D5D8-C4DF-4DE6-A4E7-FEE01D6F3C49}`).
+    pub class_id: String,
+    /// Additional data that is passed to the handler.
+    pub data: String,
+}
+
+/// Validates a GUID such as `0BE8D5D8-C4DF-4DE6-A4E7-FEE01D6F3C49`, with or without braces, and
+/// returns it in braces.
+pub(crate) fn parse_class_id(class_id: &str) -> Result<String, ParseError> {
+    let guid = class_id
+        .strip_prefix('{')
+        .and_then(|guid| guid.strip_suffix('}'))
+        .unwrap_or(class_id);
+
+    let groups: Vec<&str> = guid.split('-').collect();
+    let is_valid = groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()));
+
+    if !is_valid {
+        return Err(ParseError {
+            message: format!(
+                "'{}' is not a valid class id, expected a GUID such as {{0BE8D5D8-C4DF-4DE6-A4E7-FEE01D6F3C49}}",
+                class_id
+            ),
+            position: None,
+        });
+    }
+    Ok(format!("{{{}}}", guid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::Month;
+    use crate::settings::{Compatibility, MaintenanceSettings};
+
+    fn builder() -> ScheduleBuilder<Base> {
+        ScheduleBuilder::new(&ComRuntime::new().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn daily_definition() {
+        let schedule = builder()
+            .create_daily()
+            .author("Alice")
+            .unwrap()
+            .in_folder("\\My Tasks")
+            .unwrap()
+            .folder_security_descriptor("D:P(A;OICI;FA;;;BA)")
+            .unwrap()
+            .trigger("DailyTrigger", false)
+            .unwrap()
+            .days_interval(2)
+            .unwrap()
+            .start_boundary("2007-01-01T08:00:00")
+            .unwrap()
+            .action(Action::new("action", "notepad.exe", "", ""))
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(schedule.folder(), "\\My Tasks");
+        assert_eq!(
+            schedule.folder_security_descriptor().unwrap().to_string(),
+            "D:P(A;OICI;FA;;;BA)"
+        );
+
+        let definition = schedule.definition();
+        assert_eq!(definition.registration_info.author.as_deref(), Some("Alice"));
+        assert_eq!(definition.actions, vec![Action::new("action", "notepad.exe", "", "")]);
+        assert_eq!(
+            definition.triggers,
+            vec![Trigger {
+                enabled: false,
+                start_boundary: Some("2007-01-01T08:00:00".to_string()),
+                ..Trigger::new(
+                    "DailyTrigger",
+                    TriggerKind::Daily(DailyTrigger {
+                        days_interval: Some(2),
+                        random_delay: None,
+                    })
+                )
+            }]
+        );
+    }
+
+    #[test]
+    fn build_requires_start_boundary() {
+        let result = builder()
+            .create_time()
+            .trigger("TimeTrigger", true)
+            .unwrap()
+            .build();
+
+        assert!(result.is_err());
+
+        let monthly = builder().create_monthly().trigger("MonthlyTrigger", true).unwrap();
+        assert!(matches!(monthly.build(), Err(Error::RequiredProperty(_))));
+        let weekly = builder().create_weekly().trigger("WeeklyTrigger", true).unwrap();
+        assert!(matches!(weekly.build(), Err(Error::RequiredProperty(_))));
+    }
+
+    #[test]
+    fn build_validates_repetition() {
+        let repetition = |duration: Duration, interval: Duration| {
+            builder()
+                .create_boot()
+                .trigger("BootTrigger", true)
+                .unwrap()
+                .repetition(duration, interval, false)
+                .unwrap()
+                .build()
+        };
+        let minutes = |m| Duration { minutes: Some(m), ..Default::default() };
+
+        assert!(repetition(minutes(60), minutes(5)).is_ok());
+        assert!(repetition(Duration::default(), minutes(1)).is_ok());
+
+        match repetition(minutes(5), minutes(60)) {
+            Err(Error::Validation(e)) => assert_eq!(e.field, "triggers[0].repetition.interval"),
+            result => panic!("unexpected result {:?}", result),
+        }
+        match repetition(Duration::default(), Duration { days: Some(32), ..Default::default() }) {
+            Err(Error::Validation(e)) => {
+                assert_eq!(e.field, "triggers[0].repetition.interval");
+                assert_eq!(e.message, "must be between 1 minute and 31 days, found 'P32D'");
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn build_validates_trigger_durations() {
+        let error = |result: Result<Schedule, Error>| match result {
+            Err(Error::Validation(e)) => (e.field, e.message),
+            result => panic!("unexpected result {:?}", result),
+        };
+        let boot = || builder().create_boot().trigger("BootTrigger", true).unwrap();
+        let daily = || {
+            builder()
+                .create_daily()
+                .trigger("DailyTrigger", true)
+                .unwrap()
+                .start_boundary("2007-01-01T08:00:00")
+                .unwrap()
+        };
+
+        let days = |days| Duration { days: Some(days), ..Default::default() };
+        assert!(boot().delay(days(1)).unwrap().build().is_ok());
+        assert_eq!(
+            error(boot().delay(Duration::default()).unwrap().build()),
+            ("triggers[0].delay".to_string(), "must not be empty".to_string())
+        );
+        assert_eq!(
+            error(boot().delay(days(32)).unwrap().build()),
+            (
+                "triggers[0].delay".to_string(),
+                "must be at most 31 days, found 'P32D'".to_string()
+            )
+        );
+        assert_eq!(
+            error(daily().random_delay(Duration::default()).unwrap().build()).0,
+            "triggers[0].random_delay"
+        );
+
+        let mut schedule = boot().build().unwrap();
+        schedule.definition.triggers[0].execution_time_limit = Some(Duration::default());
+        assert_eq!(
+            schedule.definition().validate().unwrap_err().field,
+            "triggers[0].execution_time_limit"
+        );
+    }
+
+    #[test]
+    fn build_validates_settings() {
+        let build = |settings: Settings| {
+            builder()
+                .create_boot()
+                .trigger("BootTrigger", true)
+                .unwrap()
+                .settings(settings)
+                .and_then(|builder| builder.build())
+        };
+        let field = |result: Result<Schedule, Error>| match result {
+            Err(Error::Validation(e)) => e.field,
+            result => panic!("unexpected result {:?}", result),
+        };
+
+        #[allow(deprecated)]
+        let idle_settings = crate::settings::IdleSettings {
+            idle_duration: Some(Duration { seconds: Some(30), ..Default::default() }),
+            ..Default::default()
+        };
+        assert_eq!(
+            field(build(Settings { idle_settings: Some(idle_settings), ..Default::default() })),
+            "settings.idle_settings.idle_duration"
+        );
+        assert_eq!(
+            field(build(Settings {
+                restart_count: Some(3),
+                restart_interval: Some(Duration { days: Some(32), ..Default::default() }),
+                ..Default::default()
+            })),
+            "settings.restart_interval"
+        );
+        assert_eq!(
+            field(build(Settings {
+                restart_count: Some(3),
+                ..Default::default()
+            })),
+            "settings.restart_interval"
+        );
+        assert_eq!(
+            field(build(Settings {
+                restart_count: Some(1000),
+                restart_interval: Some(Duration { minutes: Some(1), ..Default::default() }),
+                ..Default::default()
+            })),
+            "settings.restart_count"
+        );
+
+        let days = |days| Duration { days: Some(days), ..Default::default() };
+        let maintenance = MaintenanceSettings {
+            deadline: Some(days(7)),
+            ..MaintenanceSettings::new(days(7))
+        };
+        assert_eq!(
+            field(build(Settings {
+                maintenance_settings: Some(maintenance),
+                ..Default::default()
+            })),
+            "settings.maintenance_settings.deadline"
+        );
+
+        let maintenance = MaintenanceSettings::new(days(2));
+        assert!(build(Settings {
+            maintenance_settings: Some(maintenance),
+            ..Default::default()
+        })
+        .is_ok());
+        let result = builder()
+            .create_boot()
+            .trigger("BootTrigger", true)
+            .unwrap()
+            .create_daily()
+            .trigger("DailyTrigger", true)
+            .unwrap()
+            .start_boundary("2007-01-01T08:00:00")
+            .unwrap()
+            .settings(Settings {
+                maintenance_settings: Some(maintenance),
+                ..Default::default()
+            })
+            .and_then(|builder| builder.build());
+        assert_eq!(field(result), "triggers[1]");
+        assert_eq!(
+            field(build(Settings {
+                delete_expired_task_after: Some(days(1)),
+                ..Default::default()
+            })),
+            "settings.delete_expired_task_after"
+        );
+        assert_eq!(
+            field(build(Settings {
+                execution_time_limit: Some(Duration::default()),
+                ..Default::default()
+            })),
+            "settings.execution_time_limit"
+        );
+        assert!(build(Settings {
+            execution_time_limit: Some(Duration { seconds: Some(0), ..Default::default() }),
+            ..Default::default()
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn build_validates_compatibility() {
+        let settings = |compatibility| Settings {
+            compatibility: Some(compatibility),
+            ..Default::default()
+        };
+        let time = || {
+            builder()
+                .create_time()
+                .trigger("TimeTrigger", true)
+                .unwrap()
+                .start_boundary("2007-01-01T08:00:00")
+                .unwrap()
+                .action(Action::new("", "notepad.exe", "", ""))
+                .unwrap()
+        };
+
+        let schedule = time().settings(settings(Compatibility::AT)).unwrap().build().unwrap();
+        assert_eq!(schedule.definition().minimum_compatibility(), Compatibility::AT);
+
+        let result = time()
+            .create_logon()
+            .trigger("LogonTrigger", true)
+            .unwrap()
+            .settings(settings(Compatibility::AT))
+            .and_then(|builder| builder.build());
+        match result {
+            Err(Error::Validation(e)) => {
+                assert_eq!(e.field, "settings.compatibility");
+                assert_eq!(
+                    e.message,
+                    "AT is lower than the V1 compatibility required by more than one trigger"
+                );
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        let handler = Action::com_handler("", "{0BE8D5D8-C4DF-4DE6-A4E7-FEE01D6F3C49}", "").unwrap();
+        let result = time()
+            .action(handler)
+            .unwrap()
+            .settings(settings(Compatibility::V1))
+            .and_then(|builder| builder.build());
+        match result {
+            Err(Error::Validation(e)) => assert_eq!(
+                e.message,
+                "V1 is lower than the V2 compatibility required by more than one action"
+            ),
+            result => panic!("unexpected result {:?}", result),
         }
+
+        let volatile = Settings {
+            volatile: Some(true),
+            ..settings(Compatibility::V2_1)
+        };
+        let result = time().settings(volatile).and_then(|builder| builder.build());
+        match result {
+            Err(Error::Validation(e)) => assert_eq!(
+                e.message,
+                "V2_1 is lower than the V2_2 compatibility required by settings.volatile"
+            ),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn boundaries_are_validated() {
+        let daily = || builder().create_daily().trigger("DailyTrigger", true).unwrap();
+
+        assert!(matches!(
+            daily().start_boundary("2007-01-01 08:00"),
+            Err(Error::Parse(_))
+        ));
+
+        let schedule = daily()
+            .start_boundary("2007-01-01T08:00:00.123456789+00:00")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            schedule.definition().triggers[0].start_boundary.as_deref(),
+            Some("2007-01-01T08:00:00+00:00")
+        );
+
+        let result = daily()
+            .start_boundary("2007-01-02T08:00:00")
+            .unwrap()
+            .end_boundary("2007-01-01T08:00:00")
+            .unwrap()
+            .build();
+        match result {
+            Err(Error::Validation(e)) => assert_eq!(e.field, "triggers[0].end_boundary"),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_boundaries() {
+        use chrono::{FixedOffset, NaiveDate, TimeZone};
+
+        let naive = NaiveDate::from_ymd_opt(2007, 1, 1)
+            .unwrap()
+            .and_hms_milli_opt(8, 0, 0, 500)
+            .unwrap();
+        let offset = FixedOffset::west_opt(5 * 3600)
+            .unwrap()
+            .from_local_datetime(&naive)
+            .unwrap()
+            + chrono::Duration::days(1);
+        let schedule = builder()
+            .create_daily()
+            .trigger("DailyTrigger", true)
+            .unwrap()
+            .start_boundary(naive)
+            .unwrap()
+            .end_boundary(offset)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let trigger = &schedule.definition().triggers[0];
+        assert_eq!(trigger.start_boundary.as_deref(), Some("2007-01-01T08:00:00"));
+        assert_eq!(trigger.end_boundary.as_deref(), Some("2007-01-02T08:00:00-05:00"));
+    }
+
+    #[test]
+    fn relative_boundaries() {
+        use crate::clock::FixedClock;
+
+        let now = ZonedDateTime::new("2023-01-01T08:00:00".parse().unwrap(), -5 * 3600);
+        let build = || {
+            builder()
+                .clock(FixedClock::new(now))
+                .unwrap()
+                .create_daily()
+                .trigger("DailyTrigger", true)
+                .unwrap()
+                .start_boundary_in(Duration {
+                    minutes: Some(5),
+                    ..Default::default()
+                })
+                .unwrap()
+                .end_boundary_in(Duration {
+                    days: Some(30),
+                    ..Default::default()
+                })
+                .unwrap()
+        };
+
+        let schedule = build().build().unwrap();
+        let trigger = &schedule.definition().triggers[0];
+        assert_eq!(trigger.start_boundary.as_deref(), Some("2023-01-01T08:05:00-05:00"));
+        assert_eq!(trigger.end_boundary.as_deref(), Some("2023-01-31T08:00:00-05:00"));
+
+        // A boundary set afterwards replaces the relative boundary
+        let schedule = build()
+            .start_boundary("2023-01-02T08:00:00-05:00")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            schedule.definition().triggers[0].start_boundary.as_deref(),
+            Some("2023-01-02T08:00:00-05:00")
+        );
+
+        assert!(matches!(
+            build().start_boundary_in(Duration {
+                months: Some(1),
+                ..Default::default()
+            }),
+            Err(Error::Conversion(_))
+        ));
+        assert!(matches!(
+            build().end_boundary_in(Duration {
+                seconds: Some(usize::MAX),
+                ..Default::default()
+            }),
+            Err(Error::Conversion(_))
+        ));
+
+        // The same error is returned when the boundary is after the year 9999
+        let end_of_time = ZonedDateTime::new("9999-12-31T00:00:00".parse().unwrap(), 0);
+        let result = builder()
+            .clock(FixedClock::new(end_of_time))
+            .unwrap()
+            .create_daily()
+            .trigger("DailyTrigger", true)
+            .unwrap()
+            .start_boundary_in(Duration {
+                days: Some(2),
+                ..Default::default()
+            })
+            .unwrap()
+            .build();
+        assert!(matches!(result, Err(Error::Conversion(_))));
+    }
+
+    #[test]
+    fn build_requires_trigger() {
+        assert!(matches!(
+            builder().create_boot().build(),
+            Err(Error::InvalidOperation(_))
+        ));
+    }
+
+    #[test]
+    fn setter_requires_trigger() {
+        let result = builder().create_logon().user_id("User");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn multiple_triggers() {
+        let schedule = builder()
+            .create_logon()
+            .trigger("LogonTrigger", true)
+            .unwrap()
+            .user_id("User")
+            .unwrap()
+            .create_daily()
+            .trigger("DailyTrigger", true)
+            .unwrap()
+            .start_boundary("2007-01-01T02:00:00")
+            .unwrap()
+            .create_boot()
+            .trigger("BootTrigger", false)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let triggers = &schedule.definition().triggers;
+        assert_eq!(triggers.len(), 3);
+        assert_eq!(
+            triggers[0].kind,
+            TriggerKind::Logon(LogonTrigger {
+                delay: None,
+                user_id: Some("User".to_string()),
+            })
+        );
+        assert_eq!(triggers[0].start_boundary, None);
+        assert_eq!(triggers[1].start_boundary.as_deref(), Some("2007-01-01T02:00:00"));
+        assert!(!triggers[2].enabled);
+    }
+
+    #[test]
+    fn setter_requires_trigger_of_new_kind() {
+        let result = builder()
+            .create_logon()
+            .trigger("LogonTrigger", true)
+            .unwrap()
+            .create_daily()
+            .start_boundary("2007-01-01T02:00:00");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn session_state_change_definition() {
+        let schedule = builder()
+            .create_session_state_change()
+            .trigger("LockTrigger", true)
+            .unwrap()
+            .state_change(StateChange::SessionLock)
+            .unwrap()
+            .user_id("User")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            schedule.definition().triggers[0].kind,
+            TriggerKind::SessionStateChange(SessionStateChangeTrigger {
+                delay: None,
+                state_change: Some(StateChange::SessionLock),
+                user_id: Some("User".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn build_requires_state_change() {
+        let result = builder()
+            .create_session_state_change()
+            .trigger("LockTrigger", true)
+            .unwrap()
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn com_handler_class_id() {
+        let action = Action::com_handler("id", "0be8d5d8-c4df-4de6-a4e7-fee01d6f3c49", "").unwrap();
+        assert_eq!(
+            action,
+            Action::ComHandler(ComHandlerAction {
+                id: "id".to_string(),
+                class_id: "{0be8d5d8-c4df-4de6-a4e7-fee01d6f3c49}".to_string(),
+                data: String::new(),
+            })
+        );
+
+        for class_id in [
+            "",
+            "{}",
+            "{0BE8D5D8-C4DF-4DE6-A4E7-FEE01D6F3C49",
+            "0BE8D5D8C4DF4DE6A4E7FEE01D6F3C49",
+            "{0BE8D5D8-C4DF-4DE6-A4E7-FEE01D6F3C4G}",
+            "{0BE8D5D8-C4DF-4DE6-A4E7F-EE01D6F3C49}",
+        ] {
+            assert!(Action::com_handler("id", class_id, "").is_err(), "{}", class_id);
+        }
+    }
+
+    #[test]
+    fn monthly_masks() {
+        let schedule = builder()
+            .create_monthly()
+            .trigger("MonthlyTrigger", true)
+            .unwrap()
+            .days_of_month(vec![
+                DayOfMonth::Day(1),
+                DayOfMonth::Day(15),
+                DayOfMonth::Day(15),
+                DayOfMonth::Last,
+            ])
+            .unwrap()
+            .months_of_year(vec![Month::January, Month::December, Month::January])
+            .unwrap()
+            .start_boundary("2007-01-01T08:00:00")
+            .unwrap()
+            .build()
+            .unwrap();
+
+        match &schedule.definition().triggers[0].kind {
+            TriggerKind::Monthly(monthly) => {
+                assert_eq!(monthly.days_of_month, Some(0x4001 | i32::MIN));
+                assert_eq!(monthly.months_of_year.map(|m| m.bits()), Some(0x801));
+            }
+            kind => panic!("unexpected trigger kind {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn register_validation() {
+        use crate::credentials::Credentials;
+        use crate::enums::TaskCreationFlags;
+        use crate::settings::LogonType;
+
+        let schedule = || {
+            builder()
+                .create_boot()
+                .trigger("BootTrigger", true)
+                .unwrap()
+                .action(Action::new("", "notepad.exe", "", ""))
+                .unwrap()
+                .build()
+                .unwrap()
+        };
+        let message = |result: Result<(), Error>| match result {
+            Err(Error::InvalidOperation(e)) => e.message,
+            result => panic!("unexpected result {:?}", result),
+        };
+
+        let flags = TaskCreationFlags::ValidateOnly | TaskCreationFlags::Update;
+        let expected = "ValidateOnly cannot be combined with Create, Update or CreateOrUpdate";
+        assert_eq!(message(schedule().register("Task", flags)), expected);
+
+        let credentials = Credentials::new("SYSTEM", None, LogonType::ServiceAccount).unwrap();
+        assert_eq!(
+            message(schedule().register_with_credentials("Task", flags, &credentials)),
+            expected
+        );
     }
 }
@@ -0,0 +1,671 @@
+use crate::error::ParseError;
+use crate::schedule_builder::parse_class_id;
+use crate::settings::is_sid;
+use std::fmt;
+use std::str::FromStr;
+
+/// A security descriptor in the Security Descriptor Definition Language (SDDL), which controls
+/// who may read, run or change a task or a task folder.
+///
+/// The descriptor is parsed and validated without calling Windows: its owner and group, the flags
+/// and ACEs of its DACL and SACL, their access rights and trustees, which are either SIDs such as
+/// `S-1-5-32-544` or well-known aliases such as `BA` or `SY`. Conditional ACEs and resource
+/// attributes are not supported.
+///
+/// See <https://learn.microsoft.com/en-us/windows/win32/secauthz/security-descriptor-string-format>
+///
+/// # Example
+/// ```
+/// use planif::sddl::{AceType, SecurityDescriptor};
+///
+/// let sddl: SecurityDescriptor = "O:BAG:SYD:P(A;;FA;;;BA)(A;;FRFX;;;S-1-5-21-1004-1177-6820-512)"
+///     .parse()
+///     .unwrap();
+///
+/// let dacl = sddl.dacl.as_ref().unwrap();
+/// assert_eq!(dacl.aces[1].ace_type, AceType::Allow);
+/// assert_eq!(dacl.aces[1].rights.mask(), 0x1200a9);
+/// assert_eq!(
+///     sddl.to_string(),
+///     "O:BAG:SYD:P(A;;FA;;;BA)(A;;FRFX;;;S-1-5-21-1004-1177-6820-512)"
+/// );
+/// assert!("D:(A;;FA;;;XX)".parse::<SecurityDescriptor>().is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecurityDescriptor {
+    /// The owner of the object.
+    pub owner: Option<Sid>,
+    /// The primary group of the object.
+    pub group: Option<Sid>,
+    /// The discretionary ACL, which grants or denies access to the object.
+    pub dacl: Option<Acl>,
+    /// The system ACL, which audits the access to the object.
+    pub sacl: Option<Acl>,
+}
+
+/// A SID string such as `S-1-5-32-544`, or the alias of a well-known SID such as `BA`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sid {
+    value: String,
+}
+
+impl Sid {
+    /// Returns the SID as it is written in SDDL.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Whether the SID is the alias of a well-known SID, ie: `SY` for `S-1-5-18`.
+    pub fn is_alias(&self) -> bool {
+        !self.value.starts_with("S-")
+    }
+}
+
+impl fmt::Display for Sid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl FromStr for Sid {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if is_sid(s) || SID_ALIASES.contains(&s) {
+            Ok(Self {
+                value: s.to_string(),
+            })
+        } else {
+            Err(ParseError {
+                message: format!("'{}' is not a SID or a well-known SID alias", s),
+                position: None,
+            })
+        }
+    }
+}
+
+// See https://learn.microsoft.com/en-us/windows/win32/secauthz/sid-strings
+const SID_ALIASES: [&str; 65] = [
+    "AA", "AC", "AN", "AO", "AP", "AS", "AU", "BA", "BG", "BO", "BU", "CA", "CD", "CG", "CN", "CO",
+    "CY", "DA", "DC", "DD", "DG", "DU", "EA", "ED", "EK", "ER", "ES", "HA", "HI", "IS", "IU", "KA",
+    "LA", "LG", "LS", "LU", "LW", "ME", "MP", "MU", "NO", "NS", "NU", "OW", "PA", "PO", "PS", "PU",
+    "RA", "RC", "RD", "RE", "RM", "RO", "RS", "RU", "SA", "SI", "SO", "SS", "SU", "SY", "UD", "WD",
+    "WR",
+];
+
+/// An access control list, the DACL or the SACL of a [SecurityDescriptor].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Acl {
+    /// The control flags of the ACL.
+    pub flags: Vec<AclFlag>,
+    /// The entries of the ACL, in the order they are evaluated.
+    pub aces: Vec<Ace>,
+}
+
+/// The control flags of an [Acl].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AclFlag {
+    /// `P`, the ACEs of the parent are not inherited.
+    Protected,
+    /// `AR`, the ACEs are inherited by the children.
+    AutoInheritRequired,
+    /// `AI`, the ACL was set up for the automatic inheritance of the ACEs.
+    AutoInherited,
+    /// `NO_ACCESS_CONTROL`, the ACL is null and everyone is granted access.
+    NoAccessControl,
+}
+
+const ACL_FLAGS: [(AclFlag, &str); 4] = [
+    (AclFlag::NoAccessControl, "NO_ACCESS_CONTROL"),
+    (AclFlag::Protected, "P"),
+    (AclFlag::AutoInheritRequired, "AR"),
+    (AclFlag::AutoInherited, "AI"),
+];
+
+/// An access control entry of an [Acl], ie: `(A;;FRFX;;;BU)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ace {
+    /// The type of the ACE.
+    pub ace_type: AceType,
+    /// The inheritance and audit flags of the ACE.
+    pub flags: Vec<AceFlag>,
+    /// The access rights granted, denied or audited.
+    pub rights: AccessRights,
+    /// The GUID of the object type, only used by the object ACEs.
+    pub object_type: Option<String>,
+    /// The GUID of the object type inheriting the ACE, only used by the object ACEs.
+    pub inherited_object_type: Option<String>,
+    /// The account the ACE applies to.
+    pub trustee: Sid,
+}
+
+impl Ace {
+    /// Creates an ACE allowing the `rights` to the `trustee`.
+    pub fn allow(rights: AccessRights, trustee: Sid) -> Self {
+        Self::new(AceType::Allow, rights, trustee)
+    }
+
+    /// Creates an ACE denying the `rights` to the `trustee`.
+    pub fn deny(rights: AccessRights, trustee: Sid) -> Self {
+        Self::new(AceType::Deny, rights, trustee)
+    }
+
+    fn new(ace_type: AceType, rights: AccessRights, trustee: Sid) -> Self {
+        Self {
+            ace_type,
+            flags: Vec::new(),
+            rights,
+            object_type: None,
+            inherited_object_type: None,
+            trustee,
+        }
+    }
+}
+
+/// The types of an [Ace] supported by planif.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AceType {
+    /// `A`, allows access.
+    Allow,
+    /// `D`, denies access.
+    Deny,
+    /// `OA`, allows access to an object type.
+    ObjectAllow,
+    /// `OD`, denies access to an object type.
+    ObjectDeny,
+    /// `AU`, audits access.
+    Audit,
+    /// `AL`, raises an alarm on access.
+    Alarm,
+    /// `OU`, audits access to an object type.
+    ObjectAudit,
+    /// `OL`, raises an alarm on access to an object type.
+    ObjectAlarm,
+    /// `ML`, the mandatory integrity label.
+    MandatoryLabel,
+    /// `SP`, the central access policy.
+    ScopedPolicyId,
+}
+
+const ACE_TYPES: [(AceType, &str); 10] = [
+    (AceType::Allow, "A"),
+    (AceType::Deny, "D"),
+    (AceType::ObjectAllow, "OA"),
+    (AceType::ObjectDeny, "OD"),
+    (AceType::Audit, "AU"),
+    (AceType::Alarm, "AL"),
+    (AceType::ObjectAudit, "OU"),
+    (AceType::ObjectAlarm, "OL"),
+    (AceType::MandatoryLabel, "ML"),
+    (AceType::ScopedPolicyId, "SP"),
+];
+
+impl AceType {
+    /// Whether the ACE belongs to a DACL, the other ACEs belong to a SACL.
+    pub fn is_discretionary(self) -> bool {
+        matches!(
+            self,
+            AceType::Allow | AceType::Deny | AceType::ObjectAllow | AceType::ObjectDeny
+        )
+    }
+
+    /// Whether the ACE applies to an object type.
+    pub fn is_object(self) -> bool {
+        matches!(
+            self,
+            AceType::ObjectAllow
+                | AceType::ObjectDeny
+                | AceType::ObjectAudit
+                | AceType::ObjectAlarm
+        )
+    }
+}
+
+/// The flags of an [Ace].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AceFlag {
+    /// `CI`, the ACE is inherited by the child containers.
+    ContainerInherit,
+    /// `OI`, the ACE is inherited by the child objects.
+    ObjectInherit,
+    /// `NP`, the ACE is not propagated past the children.
+    NoPropagateInherit,
+    /// `IO`, the ACE only applies to the children.
+    InheritOnly,
+    /// `ID`, the ACE was inherited.
+    Inherited,
+    /// `SA`, successful accesses are audited.
+    SuccessfulAccess,
+    /// `FA`, failed accesses are audited.
+    FailedAccess,
+    /// `TP`, the ACE cannot be modified.
+    TrustProtected,
+    /// `CR`, the ACE cannot be removed.
+    Critical,
+}
+
+const ACE_FLAGS: [(AceFlag, &str); 9] = [
+    (AceFlag::ContainerInherit, "CI"),
+    (AceFlag::ObjectInherit, "OI"),
+    (AceFlag::NoPropagateInherit, "NP"),
+    (AceFlag::InheritOnly, "IO"),
+    (AceFlag::Inherited, "ID"),
+    (AceFlag::SuccessfulAccess, "SA"),
+    (AceFlag::FailedAccess, "FA"),
+    (AceFlag::TrustProtected, "TP"),
+    (AceFlag::Critical, "CR"),
+];
+
+/// The access rights of an [Ace], written as a hexadecimal mask such as `0x1f01ff` or as a
+/// sequence of rights such as `FRFX`, which is kept when the ACE is formatted.
+///
+/// # Example
+/// ```
+/// use planif::sddl::AccessRights;
+///
+/// let rights: AccessRights = "GRGX".parse().unwrap();
+/// assert_eq!(rights.mask(), 0xa0000000);
+/// assert_eq!(rights.to_string(), "GRGX");
+/// assert_eq!(AccessRights::from(0x1f01ff).to_string(), "0x1f01ff");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccessRights {
+    mask: u32,
+    text: String,
+}
+
+impl AccessRights {
+    /// Returns the access mask.
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+}
+
+impl From<u32> for AccessRights {
+    fn from(mask: u32) -> Self {
+        Self {
+            mask,
+            text: format!("0x{:x}", mask),
+        }
+    }
+}
+
+impl fmt::Display for AccessRights {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl FromStr for AccessRights {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |message: String, position: usize| ParseError {
+            message,
+            position: Some(position),
+        };
+
+        if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            return u32::from_str_radix(hex, 16)
+                .map(|mask| Self {
+                    mask,
+                    text: s.to_string(),
+                })
+                .map_err(|_| error(format!("'{}' is not a hexadecimal access mask", s), 0));
+        }
+
+        if s.is_empty() {
+            return Err(error("The access rights cannot be empty".to_string(), 0));
+        }
+
+        let mut mask = 0;
+        for (position, code) in codes(s) {
+            match ACCESS_RIGHTS.iter().find(|(_, name)| *name == code) {
+                Some((right, _)) => mask |= right,
+                None => {
+                    return Err(error(
+                        format!("'{}' is not an access right", code),
+                        position,
+                    ))
+                }
+            }
+        }
+        Ok(Self {
+            mask,
+            text: s.to_string(),
+        })
+    }
+}
+
+// See https://learn.microsoft.com/en-us/windows/win32/secauthz/ace-strings
+const ACCESS_RIGHTS: [(u32, &str); 27] = [
+    (0x1000_0000, "GA"),
+    (0x8000_0000, "GR"),
+    (0x4000_0000, "GW"),
+    (0x2000_0000, "GX"),
+    (0x0002_0000, "RC"),
+    (0x0001_0000, "SD"),
+    (0x0004_0000, "WD"),
+    (0x0008_0000, "WO"),
+    (0x0000_0010, "RP"),
+    (0x0000_0020, "WP"),
+    (0x0000_0001, "CC"),
+    (0x0000_0002, "DC"),
+    (0x0000_0004, "LC"),
+    (0x0000_0008, "SW"),
+    (0x0000_0080, "LO"),
+    (0x0000_0040, "DT"),
+    (0x0000_0100, "CR"),
+    (0x001f_01ff, "FA"),
+    (0x0012_0089, "FR"),
+    (0x0012_0116, "FW"),
+    (0x0012_00a0, "FX"),
+    (0x000f_003f, "KA"),
+    (0x0002_0019, "KR"),
+    (0x0002_0006, "KW"),
+    (0x0000_0002, "NR"),
+    (0x0000_0001, "NW"),
+    (0x0000_0004, "NX"),
+];
+
+// Splits a sequence of two letter codes, with their position. An odd trailing letter is returned
+// on its own so it is reported as an invalid code.
+fn codes(s: &str) -> Vec<(usize, &str)> {
+    let mut codes = Vec::new();
+    let mut starts = s.char_indices().map(|(i, _)| i).step_by(2).peekable();
+    while let Some(start) = starts.next() {
+        let end = starts.peek().copied().unwrap_or(s.len());
+        codes.push((start, &s[start..end]));
+    }
+    codes
+}
+
+impl fmt::Display for SecurityDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(owner) = &self.owner {
+            write!(f, "O:{}", owner)?;
+        }
+        if let Some(group) = &self.group {
+            write!(f, "G:{}", group)?;
+        }
+        if let Some(dacl) = &self.dacl {
+            write!(f, "D:{}", dacl)?;
+        }
+        if let Some(sacl) = &self.sacl {
+            write!(f, "S:{}", sacl)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Acl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for flag in &self.flags {
+            write!(f, "{}", code(&ACL_FLAGS, *flag))?;
+        }
+        for ace in &self.aces {
+            write!(f, "({})", ace)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Ace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{};", code(&ACE_TYPES, self.ace_type))?;
+        for flag in &self.flags {
+            write!(f, "{}", code(&ACE_FLAGS, *flag))?;
+        }
+        write!(
+            f,
+            ";{};{};{};{}",
+            self.rights,
+            self.object_type.as_deref().unwrap_or_default(),
+            self.inherited_object_type.as_deref().unwrap_or_default(),
+            self.trustee
+        )
+    }
+}
+
+fn code<T: PartialEq>(table: &[(T, &'static str)], value: T) -> &'static str {
+    table
+        .iter()
+        .find(|(item, _)| *item == value)
+        .map(|(_, code)| *code)
+        .expect("every value has a code")
+}
+
+impl FromStr for SecurityDescriptor {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError {
+                message: "The security descriptor is empty".to_string(),
+                position: Some(0),
+            });
+        }
+
+        let mut parser = Parser {
+            input: s,
+            position: 0,
+        };
+        let mut descriptor = SecurityDescriptor::default();
+        while parser.position < s.len() {
+            let start = parser.position;
+            let component = parser.rest().get(..2).unwrap_or_default();
+            parser.position += 2;
+            let duplicate = match component {
+                "O:" => descriptor.owner.replace(parser.owner()?).is_some(),
+                "G:" => descriptor.group.replace(parser.owner()?).is_some(),
+                "D:" => descriptor.dacl.replace(parser.acl(true)?).is_some(),
+                "S:" => descriptor.sacl.replace(parser.acl(false)?).is_some(),
+                _ => return Err(parser.error(start, "expected 'O:', 'G:', 'D:' or 'S:'")),
+            };
+            if duplicate {
+                return Err(
+                    parser.error(start, &format!("'{}' is given more than once", component))
+                );
+            }
+        }
+        Ok(descriptor)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn error(&self, position: usize, message: &str) -> ParseError {
+        ParseError {
+            message: format!("Invalid security descriptor '{}': {}", self.input, message),
+            position: Some(position),
+        }
+    }
+
+    // Moves the errors of the values to their position in the descriptor.
+    fn at<T>(&self, position: usize, result: Result<T, ParseError>) -> Result<T, ParseError> {
+        result.map_err(|e| self.error(position + e.position.unwrap_or_default(), &e.message))
+    }
+
+    // The owner or the group, a SID string or a two letter alias, which is followed by the next
+    // component without a separator.
+    fn owner(&mut self) -> Result<Sid, ParseError> {
+        let rest = self.rest();
+        let len = if rest.starts_with("S-") {
+            rest[1..]
+                .find(|c: char| !(c.is_ascii_digit() || c == '-'))
+                .map_or(rest.len(), |len| len + 1)
+        } else {
+            rest.char_indices()
+                .nth(2)
+                .map_or(rest.len(), |(len, _)| len)
+        };
+        let start = self.position;
+        self.position += len;
+        self.at(start, rest[..len].parse())
+    }
+
+    fn acl(&mut self, discretionary: bool) -> Result<Acl, ParseError> {
+        let mut acl = Acl::default();
+        while let Some((flag, code)) = ACL_FLAGS
+            .iter()
+            .find(|(_, code)| self.rest().starts_with(code))
+        {
+            acl.flags.push(*flag);
+            self.position += code.len();
+        }
+
+        while self.rest().starts_with('(') {
+            let start = self.position + 1;
+            let end = match self.rest().find(')') {
+                Some(end) => self.position + end,
+                None => return Err(self.error(self.position, "the ACE is not closed by ')'")),
+            };
+            acl.aces.push(self.ace(start, end, discretionary)?);
+            self.position = end + 1;
+        }
+        Ok(acl)
+    }
+
+    fn ace(&self, start: usize, end: usize, discretionary: bool) -> Result<Ace, ParseError> {
+        let mut fields = Vec::new();
+        let mut position = start;
+        for field in self.input[start..end].split(';') {
+            fields.push((position, field));
+            position += field.len() + 1;
+        }
+        if fields.len() != 6 {
+            return Err(self.error(
+                start,
+                "an ACE has 6 fields separated by ';', conditional ACEs and resource attributes are not supported",
+            ));
+        }
+
+        let (position, ace_type) = fields[0];
+        let ace_type = match ACE_TYPES.iter().find(|(_, code)| *code == ace_type) {
+            Some((ace_type, _)) => *ace_type,
+            None => {
+                return Err(self.error(
+                    position,
+                    &format!("'{}' is not a supported ACE type", ace_type),
+                ))
+            }
+        };
+        if ace_type.is_discretionary() != discretionary {
+            let message = match discretionary {
+                true => "only access allowed and access denied ACEs are allowed in the DACL",
+                false => "access allowed and access denied ACEs are not allowed in the SACL",
+            };
+            return Err(self.error(position, message));
+        }
+
+        let (position, flags) = fields[1];
+        let flags = codes(flags)
+            .into_iter()
+            .map(|(offset, code)| {
+                ACE_FLAGS
+                    .iter()
+                    .find(|(_, name)| *name == code)
+                    .map(|(flag, _)| *flag)
+                    .ok_or_else(|| {
+                        self.error(position + offset, &format!("'{}' is not an ACE flag", code))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rights = self.at(fields[2].0, fields[2].1.parse())?;
+
+        let mut object_types = [None, None];
+        for (object_type, (position, guid)) in object_types.iter_mut().zip(&fields[3..5]) {
+            if guid.is_empty() {
+                continue;
+            }
+            if !ace_type.is_object() {
+                return Err(self.error(*position, "only object ACEs have an object type"));
+            }
+            if guid.starts_with('{') || parse_class_id(guid).is_err() {
+                return Err(self.error(*position, &format!("'{}' is not a GUID", guid)));
+            }
+            *object_type = Some(guid.to_string());
+        }
+        let [object_type, inherited_object_type] = object_types;
+
+        let trustee = self.at(fields[5].0, fields[5].1.parse())?;
+
+        Ok(Ace {
+            ace_type,
+            flags,
+            rights,
+            object_type,
+            inherited_object_type,
+            trustee,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_security_descriptors() {
+        let sddl = "O:S-1-5-21-1004-1177-6820-500G:DUD:PAI(A;OICI;GRGX;;;AU)(D;;0x1F01FF;;;AN)S:(AU;SAFA;FA;;;WD)(OA;;CR;bf967aba-0de6-11d0-a285-00aa003049e2;;BA)";
+        let error = sddl.parse::<SecurityDescriptor>().unwrap_err();
+        assert_eq!(error.position, Some(94));
+        assert!(error
+            .message
+            .ends_with("access allowed and access denied ACEs are not allowed in the SACL"));
+
+        let sddl = &sddl.replace("S:(AU;SAFA;FA;;;WD)(OA;", "S:(AU;SAFA;FA;;;WD)(OU;");
+        let descriptor: SecurityDescriptor = sddl.parse().unwrap();
+        assert_eq!(descriptor.to_string(), *sddl);
+        assert!(!descriptor.owner.as_ref().unwrap().is_alias());
+        assert!(descriptor.group.as_ref().unwrap().is_alias());
+
+        let dacl = descriptor.dacl.unwrap();
+        assert_eq!(dacl.flags, [AclFlag::Protected, AclFlag::AutoInherited]);
+        assert_eq!(
+            dacl.aces[0].flags,
+            [AceFlag::ObjectInherit, AceFlag::ContainerInherit]
+        );
+        assert_eq!(dacl.aces[0].rights.mask(), 0xa000_0000);
+        assert_eq!(dacl.aces[1].ace_type, AceType::Deny);
+        assert_eq!(dacl.aces[1].rights.mask(), 0x1f_01ff);
+
+        let sacl = descriptor.sacl.unwrap();
+        assert_eq!(
+            sacl.aces[1].object_type.as_deref(),
+            Some("bf967aba-0de6-11d0-a285-00aa003049e2")
+        );
+        assert_eq!(sacl.aces[1].trustee.as_str(), "BA");
+
+        let invalid = [
+            ("", 0),
+            ("X:BA", 0),
+            ("O:BAO:SY", 4),
+            ("O:XY", 2),
+            ("G:S-1-", 2),
+            ("D:(A;;FA;;;BA", 2),
+            ("D:(A;;FA;;BA)", 3),
+            ("D:(XA;;FA;;;BA)", 3),
+            ("D:(A;OIX;FA;;;BA)", 7),
+            ("D:(A;;FAZZ;;;BA)", 8),
+            ("D:(A;;;;;BA)", 6),
+            ("D:(A;;FA;bf967aba-0de6-11d0-a285-00aa003049e2;;BA)", 9),
+            ("D:(OA;;FA;not-a-guid;;BA)", 10),
+            ("D:(A;;FA;;;)", 11),
+            ("D:P(A;;FA;;;BA)junk", 15),
+        ];
+        for (sddl, position) in invalid {
+            let error = sddl.parse::<SecurityDescriptor>().unwrap_err();
+            assert_eq!(error.position, Some(position), "{}: {}", sddl, error);
+        }
+    }
+}
@@ -1,4 +1,8 @@
 use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{ConversionError, Error, ParseError, ValidationError};
+use crate::task_definition::{check_duration, DAY, MINUTE};
 
 /// Values for task compatibility  
 /// Task compatibility, which is set through the Compatibility property, should only be set to `Compatibility.V1`
@@ -8,14 +12,24 @@ use std::fmt;
 /// Tasks compatible with Task Scheduler 1.0 can only have a time trigger, a logon trigger, or a boot trigger, and the
 /// task can only have an executable action.
 ///
+/// The levels are ordered, the lowest level a task can use is given by
+/// [TaskDefinition::minimum_compatibility](crate::task_definition::TaskDefinition::minimum_compatibility).
+///
 /// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/tasksettings-compatibility>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Compatibility {
     /// The task is compatible with the AT command.
     AT = 0,
     /// The task is compatible with Task Scheduler 1.0.
     V1,
-    /// The task is compatible with Task Scheduler 2.0.
+    /// The task is compatible with Task Scheduler 2.0 (Windows Vista, Windows Server 2008).
     V2,
+    /// The task is compatible with Task Scheduler 2.1 (Windows 7, Windows Server 2008 R2).
+    V2_1,
+    /// The task is compatible with Task Scheduler 2.2 (Windows 8, Windows Server 2012).
+    V2_2,
+    /// The task is compatible with Task Scheduler 2.3 (Windows 10, Windows Server 2016).
+    V2_3,
 }
 
 pub(crate) use windows::Win32::System::TaskScheduler::TASK_COMPATIBILITY;
@@ -28,11 +42,12 @@ impl From<Compatibility> for TASK_COMPATIBILITY {
 /// Defines idle settings on a task
 /// # Example
 /// ```
-/// use planif::settings::Settings;
+/// use planif::settings::{IdleSettings, Settings};
 /// // All values are set to `None` when using `new()`
 /// let mut settings = Settings::new();
 /// settings.idle_settings = Some(IdleSettings::new());
 /// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(deprecated)]
 pub struct IdleSettings {
     #[deprecated]
@@ -76,6 +91,7 @@ impl Default for IdleSettings {
 }
 
 /// Values for the instance policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum InstancesPolicy {
     /// Starts a new instance while an existing instance of the task is running.
     Parallel = 0,
@@ -95,6 +111,7 @@ impl From<InstancesPolicy> for TASK_INSTANCES_POLICY {
 }
 
 /// Values for the security logon method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogonType {
     /// The logon method is not specified. Used for non-NT credentials.
     None = 0,
@@ -117,7 +134,45 @@ pub enum LogonType {
     InteractiveTokenOrPassword,
 }
 
+/// Runs the task during Automatic Maintenance, when the computer is idle, instead of on a
+/// schedule. Requires Windows 8 or later.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/maintancesettings>
+///
+/// # Example
+/// ```
+/// use planif::settings::{Duration, MaintenanceSettings};
+///
+/// let maintenance = MaintenanceSettings {
+///     deadline: Some(Duration { days: Some(14), ..Default::default() }),
+///     ..MaintenanceSettings::new(Duration { days: Some(2), ..Default::default() })
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceSettings {
+    /// How often the task is started during regular Automatic Maintenance, at least 1 day.
+    pub period: Duration,
+    /// The delay after which the task is started during emergency Automatic Maintenance if it did
+    /// not complete during regular Automatic Maintenance, at least 1 day and longer than the
+    /// period.
+    pub deadline: Option<Duration>,
+    /// Whether the task is started alone, after the other maintenance tasks.
+    pub exclusive: Option<bool>,
+}
+
+impl MaintenanceSettings {
+    /// Creates maintenance settings started every `period`, without deadline.
+    pub fn new(period: Duration) -> Self {
+        Self {
+            period,
+            deadline: None,
+            exclusive: None,
+        }
+    }
+}
+
 /// Use to set a network profile identifier and name.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NetworkSettings {
     /// GUID value that identifies a network profile.
     pub id: String,
@@ -128,6 +183,7 @@ pub struct NetworkSettings {
 /// Use to set the settings for the principal
 /// # Reference
 /// <https://docs.microsoft.com/en-us/windows/win32/taskschd/principal>
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PrincipalSettings {
     /// Gets or sets the name of the principal that is displayed in the Task Scheduler UI.
     pub display_name: String,
@@ -139,6 +195,12 @@ pub struct PrincipalSettings {
     /// Gets or sets the security logon method that is required to run the tasks that are associated with the principal.
     /// This property is valid only when a user identifier is specified by the [user_id](PrincipalSettings::user_id) property.
     pub logon_type: LogonType,
+    /// Gets or sets the type of security identifier (SID) of the process of the task. When `None`
+    /// the Task Scheduler default is used.
+    pub process_token_sid_type: Option<ProcessTokenSidType>,
+    /// Gets or sets the privileges required by the task, the other privileges of the user are
+    /// removed from the token of the task. When empty, the task has every privilege of the user.
+    pub required_privileges: Vec<Privilege>,
     /// Gets or sets the identifier that is used to specify the privilege level that is required to run the tasks
     /// that are associated with the principal.
     pub run_level: RunLevel,
@@ -147,8 +209,258 @@ pub struct PrincipalSettings {
     pub user_id: Option<String>,
 }
 
+impl PrincipalSettings {
+    /// Checks the combination of the user, the group and the logon type:
+    /// - the user and the group cannot both be set
+    /// - the [Group](LogonType::Group) logon type requires a group, which is a name or a SID such
+    ///   as `S-1-5-32-545`, and a group requires the [Group](LogonType::Group) logon type
+    /// - the [ServiceAccount](LogonType::ServiceAccount) logon type requires the SYSTEM, LOCAL
+    ///   SERVICE or NETWORK SERVICE account
+    /// - the [Password](LogonType::Password), [S4U](LogonType::S4U) and
+    ///   [InteractiveTokenOrPassword](LogonType::InteractiveTokenOrPassword) logon types require a
+    ///   user
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let error = |field: &str, message: &str| {
+            Err(ValidationError {
+                field: format!("principal.{}", field),
+                message: message.to_string(),
+            })
+        };
+
+        if self.user_id.is_some() && self.group_id.is_some() {
+            return error(
+                "group_id",
+                "group_id and user_id are mutually exclusive and cannot both be set",
+            );
+        }
+
+        match (&self.group_id, self.logon_type) {
+            (None, LogonType::Group) => {
+                return error("group_id", "the Group logon type requires a group_id")
+            }
+            (Some(group_id), LogonType::Group) => {
+                if group_id.is_empty() {
+                    return error("group_id", "the group_id cannot be empty");
+                }
+                if group_id.starts_with("S-") && !is_sid(group_id) {
+                    return error(
+                        "group_id",
+                        &format!("'{}' is not a valid SID", group_id),
+                    );
+                }
+            }
+            (Some(_), _) => {
+                return error("logon_type", "a group_id requires the Group logon type")
+            }
+            (None, _) => {}
+        }
+
+        match (&self.user_id, self.logon_type) {
+            (Some(user_id), LogonType::ServiceAccount) if !is_service_account(user_id) => error(
+                "user_id",
+                &format!(
+                    "'{}' is not the SYSTEM, LOCAL SERVICE or NETWORK SERVICE account required by the ServiceAccount logon type",
+                    user_id
+                ),
+            ),
+            (
+                None,
+                LogonType::ServiceAccount
+                | LogonType::Password
+                | LogonType::S4U
+                | LogonType::InteractiveTokenOrPassword,
+            ) => error(
+                "user_id",
+                &format!("the {:?} logon type requires a user_id", self.logon_type),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Builds a [PrincipalSettings] which is validated offline, with constructors for the well-known
+/// service accounts, users and groups.
+///
+/// # Example
+/// ```
+/// use planif::settings::{LogonType, PrincipalBuilder, Privilege, ProcessTokenSidType, RunLevel};
+///
+/// let system = PrincipalBuilder::system().run_level(RunLevel::Highest).build().unwrap();
+/// assert_eq!(system.user_id.as_deref(), Some("S-1-5-18"));
+/// assert_eq!(system.logon_type, LogonType::ServiceAccount);
+///
+/// // Run whether the user is logged on or not, without storing the password
+/// let user = PrincipalBuilder::user("OURDOMAIN\\backup")
+///     .run_whether_logged_on(false)
+///     .build()
+///     .unwrap();
+/// assert_eq!(user.logon_type, LogonType::S4U);
+///
+/// // A group cannot log on with a password
+/// assert!(PrincipalBuilder::group("Users").run_whether_logged_on(true).build().is_err());
+///
+/// // A least-privilege service task
+/// let backup = PrincipalBuilder::local_service()
+///     .process_token_sid_type(ProcessTokenSidType::Unrestricted)
+///     .required_privileges([Privilege::Backup, Privilege::ChangeNotify])
+///     .build()
+///     .unwrap();
+/// assert_eq!(backup.required_privileges.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrincipalBuilder {
+    principal: PrincipalSettings,
+}
+
+impl PrincipalBuilder {
+    /// Creates a principal without user or group, with the `Author` id used by the Task Scheduler.
+    pub fn new() -> Self {
+        Self {
+            principal: PrincipalSettings {
+                display_name: String::new(),
+                group_id: None,
+                id: "Author".to_string(),
+                logon_type: LogonType::None,
+                process_token_sid_type: None,
+                required_privileges: Vec::new(),
+                run_level: RunLevel::LUA,
+                user_id: None,
+            },
+        }
+    }
+
+    /// Runs the task as the Local System account (`S-1-5-18`).
+    pub fn system() -> Self {
+        Self::service_account("S-1-5-18")
+    }
+
+    /// Runs the task as the Local Service account (`S-1-5-19`).
+    pub fn local_service() -> Self {
+        Self::service_account("S-1-5-19")
+    }
+
+    /// Runs the task as the Network Service account (`S-1-5-20`).
+    pub fn network_service() -> Self {
+        Self::service_account("S-1-5-20")
+    }
+
+    fn service_account(sid: &str) -> Self {
+        Self::new()
+            .user_id(sid)
+            .logon_type(LogonType::ServiceAccount)
+    }
+
+    /// Runs the task as the user, ie: `MyDomain\MyName`, only when the user is logged on.
+    pub fn user(user_id: &str) -> Self {
+        Self::new()
+            .user_id(user_id)
+            .logon_type(LogonType::InteractiveToken)
+    }
+
+    /// Runs the task for the members of the group, by name or by SID such as `S-1-5-32-545`,
+    /// when they are logged on.
+    pub fn group(group_id: &str) -> Self {
+        let mut builder = Self::new().logon_type(LogonType::Group);
+        builder.principal.group_id = Some(group_id.to_string());
+        builder
+    }
+
+    /// Runs the task whether the user is logged on or not. When the password is stored, it must be
+    /// given when the task is
+    /// [registered](crate::schedule::Schedule::register_with_credentials), otherwise the task
+    /// has no access to the network or to encrypted files.
+    pub fn run_whether_logged_on(self, store_password: bool) -> Self {
+        self.logon_type(if store_password {
+            LogonType::Password
+        } else {
+            LogonType::S4U
+        })
+    }
+
+    /// Sets the identifier of the principal, used by the actions of the task.
+    pub fn id(mut self, id: &str) -> Self {
+        self.principal.id = id.to_string();
+        self
+    }
+
+    /// Sets the name of the principal displayed in the Task Scheduler UI.
+    pub fn display_name(mut self, display_name: &str) -> Self {
+        self.principal.display_name = display_name.to_string();
+        self
+    }
+
+    /// Sets the user of the principal and removes its group.
+    pub fn user_id(mut self, user_id: &str) -> Self {
+        self.principal.user_id = Some(user_id.to_string());
+        self.principal.group_id = None;
+        self
+    }
+
+    /// Sets the security logon method.
+    pub fn logon_type(mut self, logon_type: LogonType) -> Self {
+        self.principal.logon_type = logon_type;
+        self
+    }
+
+    /// Sets the privilege level of the task.
+    pub fn run_level(mut self, run_level: RunLevel) -> Self {
+        self.principal.run_level = run_level;
+        self
+    }
+
+    /// Sets the type of security identifier (SID) of the process of the task.
+    pub fn process_token_sid_type(mut self, sid_type: ProcessTokenSidType) -> Self {
+        self.principal.process_token_sid_type = Some(sid_type);
+        self
+    }
+
+    /// Adds privileges required by the task, the other privileges of the user are removed from
+    /// the token of the task.
+    pub fn required_privileges(mut self, privileges: impl IntoIterator<Item = Privilege>) -> Self {
+        for privilege in privileges {
+            if !self.principal.required_privileges.contains(&privilege) {
+                self.principal.required_privileges.push(privilege);
+            }
+        }
+        self
+    }
+
+    /// Validates and returns the principal, see [PrincipalSettings::validate].
+    pub fn build(self) -> Result<PrincipalSettings, Error> {
+        self.principal.validate()?;
+        Ok(self.principal)
+    }
+}
+
+impl Default for PrincipalBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Whether the user is the SYSTEM, LOCAL SERVICE or NETWORK SERVICE account, by name or by SID.
+pub(crate) fn is_service_account(user_id: &str) -> bool {
+    let user_id = user_id.to_ascii_uppercase();
+    let user_id = user_id.trim_start_matches("NT AUTHORITY\\");
+    matches!(
+        user_id,
+        "S-1-5-18" | "S-1-5-19" | "S-1-5-20" | "SYSTEM" | "LOCALSERVICE" | "LOCAL SERVICE"
+            | "NETWORKSERVICE" | "NETWORK SERVICE"
+    )
+}
+
+// Whether the string is a SID, ie: `S-1-5-32-545`.
+pub(crate) fn is_sid(sid: &str) -> bool {
+    let mut parts = sid.split('-');
+    parts.next() == Some("S")
+        && parts.next() == Some("1")
+        && parts.clone().count() >= 1
+        && parts.all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
 /// Values for the identifier that is used to specify the privilege level that is required to run the tasks
 /// that are associated with the principal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RunLevel {
     /// Tasks will be run with the highest privileges.
     Highest = 1,
@@ -156,7 +468,177 @@ pub enum RunLevel {
     LUA = 0,
 }
 
-/// Defines all available settings on a task.
+/// The type of security identifier (SID) of the process of the task.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/api/taskschd/ne-taskschd-task_processtokensid_type>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessTokenSidType {
+    /// No changes are made to the process token groups list.
+    None = 0,
+    /// A task SID derived from the task name is added to the process token groups list, and the
+    /// token default discretionary access control list (DACL) is modified to allow only the task
+    /// SID and the LocalSystem full control and the account SID read control.
+    Unrestricted = 1,
+    /// The default process SID of the Task Scheduler.
+    Default = 2,
+}
+
+/// A privilege required by a task. When a principal lists its
+/// [required privileges](PrincipalSettings::required_privileges), the other privileges are removed
+/// from the token of the task.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/taskschedulerschema-privilegetype-simpletype>
+///
+/// # Example
+/// ```
+/// use planif::settings::Privilege;
+///
+/// assert_eq!(Privilege::Backup.to_string(), "SeBackupPrivilege");
+/// assert_eq!("SeDebugPrivilege".parse::<Privilege>().unwrap(), Privilege::Debug);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Privilege {
+    /// Replace a process level token. (`SeAssignPrimaryTokenPrivilege`)
+    AssignPrimaryToken,
+    /// Generate security audits. (`SeAuditPrivilege`)
+    Audit,
+    /// Back up files and directories. (`SeBackupPrivilege`)
+    Backup,
+    /// Bypass traverse checking. (`SeChangeNotifyPrivilege`)
+    ChangeNotify,
+    /// Create global objects. (`SeCreateGlobalPrivilege`)
+    CreateGlobal,
+    /// Create a pagefile. (`SeCreatePagefilePrivilege`)
+    CreatePagefile,
+    /// Create permanent shared objects. (`SeCreatePermanentPrivilege`)
+    CreatePermanent,
+    /// Create symbolic links. (`SeCreateSymbolicLinkPrivilege`)
+    CreateSymbolicLink,
+    /// Create a token object. (`SeCreateTokenPrivilege`)
+    CreateToken,
+    /// Debug programs. (`SeDebugPrivilege`)
+    Debug,
+    /// Enable computer and user accounts to be trusted for delegation. (`SeEnableDelegationPrivilege`)
+    EnableDelegation,
+    /// Impersonate a client after authentication. (`SeImpersonatePrivilege`)
+    Impersonate,
+    /// Increase scheduling priority. (`SeIncreaseBasePriorityPrivilege`)
+    IncreaseBasePriority,
+    /// Adjust memory quotas for a process. (`SeIncreaseQuotaPrivilege`)
+    IncreaseQuota,
+    /// Increase a process working set. (`SeIncreaseWorkingSetPrivilege`)
+    IncreaseWorkingSet,
+    /// Load and unload device drivers. (`SeLoadDriverPrivilege`)
+    LoadDriver,
+    /// Lock pages in memory. (`SeLockMemoryPrivilege`)
+    LockMemory,
+    /// Add workstations to domain. (`SeMachineAccountPrivilege`)
+    MachineAccount,
+    /// Perform volume maintenance tasks. (`SeManageVolumePrivilege`)
+    ManageVolume,
+    /// Profile single process. (`SeProfileSingleProcessPrivilege`)
+    ProfileSingleProcess,
+    /// Modify an object label. (`SeRelabelPrivilege`)
+    Relabel,
+    /// Force shutdown from a remote system. (`SeRemoteShutdownPrivilege`)
+    RemoteShutdown,
+    /// Restore files and directories. (`SeRestorePrivilege`)
+    Restore,
+    /// Manage auditing and security log. (`SeSecurityPrivilege`)
+    Security,
+    /// Shut down the system. (`SeShutdownPrivilege`)
+    Shutdown,
+    /// Synchronize directory service data. (`SeSyncAgentPrivilege`)
+    SyncAgent,
+    /// Modify firmware environment values. (`SeSystemEnvironmentPrivilege`)
+    SystemEnvironment,
+    /// Profile system performance. (`SeSystemProfilePrivilege`)
+    SystemProfile,
+    /// Change the system time. (`SeSystemtimePrivilege`)
+    Systemtime,
+    /// Take ownership of files or other objects. (`SeTakeOwnershipPrivilege`)
+    TakeOwnership,
+    /// Act as part of the operating system. (`SeTcbPrivilege`)
+    Tcb,
+    /// Change the time zone. (`SeTimeZonePrivilege`)
+    TimeZone,
+    /// Access Credential Manager as a trusted caller. (`SeTrustedCredManAccessPrivilege`)
+    TrustedCredManAccess,
+    /// Remove computer from docking station. (`SeUndockPrivilege`)
+    Undock,
+}
+
+const PRIVILEGES: [(Privilege, &str); 34] = [
+    (Privilege::AssignPrimaryToken, "SeAssignPrimaryTokenPrivilege"),
+    (Privilege::Audit, "SeAuditPrivilege"),
+    (Privilege::Backup, "SeBackupPrivilege"),
+    (Privilege::ChangeNotify, "SeChangeNotifyPrivilege"),
+    (Privilege::CreateGlobal, "SeCreateGlobalPrivilege"),
+    (Privilege::CreatePagefile, "SeCreatePagefilePrivilege"),
+    (Privilege::CreatePermanent, "SeCreatePermanentPrivilege"),
+    (Privilege::CreateSymbolicLink, "SeCreateSymbolicLinkPrivilege"),
+    (Privilege::CreateToken, "SeCreateTokenPrivilege"),
+    (Privilege::Debug, "SeDebugPrivilege"),
+    (Privilege::EnableDelegation, "SeEnableDelegationPrivilege"),
+    (Privilege::Impersonate, "SeImpersonatePrivilege"),
+    (Privilege::IncreaseBasePriority, "SeIncreaseBasePriorityPrivilege"),
+    (Privilege::IncreaseQuota, "SeIncreaseQuotaPrivilege"),
+    (Privilege::IncreaseWorkingSet, "SeIncreaseWorkingSetPrivilege"),
+    (Privilege::LoadDriver, "SeLoadDriverPrivilege"),
+    (Privilege::LockMemory, "SeLockMemoryPrivilege"),
+    (Privilege::MachineAccount, "SeMachineAccountPrivilege"),
+    (Privilege::ManageVolume, "SeManageVolumePrivilege"),
+    (Privilege::ProfileSingleProcess, "SeProfileSingleProcessPrivilege"),
+    (Privilege::Relabel, "SeRelabelPrivilege"),
+    (Privilege::RemoteShutdown, "SeRemoteShutdownPrivilege"),
+    (Privilege::Restore, "SeRestorePrivilege"),
+    (Privilege::Security, "SeSecurityPrivilege"),
+    (Privilege::Shutdown, "SeShutdownPrivilege"),
+    (Privilege::SyncAgent, "SeSyncAgentPrivilege"),
+    (Privilege::SystemEnvironment, "SeSystemEnvironmentPrivilege"),
+    (Privilege::SystemProfile, "SeSystemProfilePrivilege"),
+    (Privilege::Systemtime, "SeSystemtimePrivilege"),
+    (Privilege::TakeOwnership, "SeTakeOwnershipPrivilege"),
+    (Privilege::Tcb, "SeTcbPrivilege"),
+    (Privilege::TimeZone, "SeTimeZonePrivilege"),
+    (Privilege::TrustedCredManAccess, "SeTrustedCredManAccessPrivilege"),
+    (Privilege::Undock, "SeUndockPrivilege"),
+];
+
+impl Privilege {
+    /// The name of the privilege constant, ie: `SeBackupPrivilege`.
+    pub fn name(&self) -> &'static str {
+        PRIVILEGES
+            .iter()
+            .find(|(privilege, _)| privilege == self)
+            .map(|(_, name)| *name)
+            .expect("every privilege has a name")
+    }
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Privilege {
+    type Err = ParseError;
+
+    /// Parses the name of a privilege constant, ie: `SeBackupPrivilege`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PRIVILEGES
+            .iter()
+            .find(|(_, name)| *name == s)
+            .map(|(privilege, _)| *privilege)
+            .ok_or_else(|| ParseError {
+                message: format!("'{}' is not a privilege", s),
+                position: None,
+            })
+    }
+}
+
+/// Defines all available settings on a task, see also the [SettingsBuilder].
 ///
 /// # Example
 /// ```
@@ -172,6 +654,7 @@ pub enum RunLevel {
 /// - <https://docs.microsoft.com/en-us/windows/win32/procthread/scheduling-priorities>
 /// - <https://docs.microsoft.com/en-us/windows/win32/taskschd/networksettings>
 /// - <https://docs.microsoft.com/en-us/windows/win32/taskschd/idlesettings>
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Settings {
     /// Gets or sets a Boolean value that indicates that the task can be started by using either the Run command
     /// or the Context menu.
@@ -185,16 +668,22 @@ pub struct Settings {
     /// Gets or sets a Boolean value that indicates that the task will not be started if the computer is running on
     /// battery power.
     pub disallow_start_if_on_batteries: Option<bool>,
+    /// Gets or sets a Boolean value that indicates that the task will not be started if triggered to run in a
+    /// Remote Applications Integrated Locally (RAIL) session. Requires Windows 7 or later.
+    pub disallow_start_on_remote_app_session: Option<bool>,
     /// Gets or sets a Boolean value that indicates that the task is enabled. The task can be performed only when this
     /// setting is True.
     pub enabled: Option<bool>,
     /// Gets or sets the amount of time allowed to complete the task.
-    pub execution_time_limit: Option<String>,
+    pub execution_time_limit: Option<Duration>,
     /// Gets or sets a Boolean value that indicates that the task will not be visible in the UI. However, administrators
     /// can override this setting through the use of a "master switch" that makes all tasks visible in the UI.
     pub hidden: Option<bool>,
     /// Gets or sets the information that specifies how the Task Scheduler performs tasks when the computer is in an idle state.
     pub idle_settings: Option<IdleSettings>,
+    /// Gets or sets the information that specifies how the Task Scheduler runs the task during Automatic
+    /// Maintenance. Maintenance tasks cannot have calendar or idle triggers. Requires Windows 8 or later.
+    pub maintenance_settings: Option<MaintenanceSettings>,
     /// Gets or sets the policy that defines how the Task Scheduler deals with multiple instances of the task.
     pub multiple_instances_policy: Option<InstancesPolicy>,
     /// The network settings object that contains a network profile identifier and name.
@@ -203,11 +692,14 @@ pub struct Settings {
     /// field, then the task will run only if the specified network profile is available.
     pub network_settings: Option<NetworkSettings>,
     /// Gets or sets the priority level of the task.
-    pub priority: Option<i32>,
-    /// Gets or sets the number of times that the Task Scheduler will attempt to restart the task.
+    pub priority: Option<Priority>,
+    /// Gets or sets the number of times that the Task Scheduler will attempt to restart the task,
+    /// between 1 and 999. It is set together with the [restart_interval](Settings::restart_interval).
     pub restart_count: Option<i32>,
-    /// Gets or sets a value that specifies how long the Task Scheduler will attempt to restart the task.
-    pub restart_interval: Option<String>,
+    /// Gets or sets a value that specifies how long the Task Scheduler will attempt to restart the
+    /// task, between 1 minute and 31 days. It is set together with the
+    /// [restart_count](Settings::restart_count).
+    pub restart_interval: Option<Duration>,
     /// Gets or sets a Boolean value that indicates that the Task Scheduler will run the task only if the
     /// computer is in an idle state.
     pub run_only_if_idle: Option<bool>,
@@ -220,6 +712,12 @@ pub struct Settings {
     /// Gets or sets a Boolean value that indicates that the task will be stopped if the computer begins to
     /// run on battery power.
     pub stop_if_going_on_batteries: Option<bool>,
+    /// Gets or sets a Boolean value that indicates that the Unified Scheduling Engine will be utilized to run
+    /// this task. Requires Windows 7 or later.
+    pub use_unified_scheduling_engine: Option<bool>,
+    /// Gets or sets a Boolean value that indicates that the task is automatically disabled every time Windows
+    /// starts. Requires Windows 8 or later.
+    pub volatile: Option<bool>,
     /// Gets or sets a Boolean value that indicates that the Task Scheduler will wake the computer when it is
     /// time to run the task.
     pub wake_to_run: Option<bool>,
@@ -236,10 +734,12 @@ impl Settings {
             compatibility: None,
             delete_expired_task_after: None,
             disallow_start_if_on_batteries: None,
+            disallow_start_on_remote_app_session: None,
             enabled: None,
             execution_time_limit: None,
             hidden: None,
             idle_settings: None,
+            maintenance_settings: None,
             multiple_instances_policy: None,
             network_settings: None,
             priority: None,
@@ -249,6 +749,8 @@ impl Settings {
             run_only_if_network_available: None,
             start_when_available: None,
             stop_if_going_on_batteries: None,
+            use_unified_scheduling_engine: None,
+            volatile: None,
             wake_to_run: None,
             xml_text: None,
         }
@@ -261,8 +763,425 @@ impl Default for Settings {
     }
 }
 
+impl Settings {
+    /// Checks the settings without calling the Task Scheduler:
+    /// - the durations are not empty
+    /// - the idle duration and the idle wait timeout are at least 1 minute
+    /// - the restart count and the restart interval are set together
+    /// - the restart count is between 1 and 999
+    /// - the restart interval is between 1 minute and 31 days
+    /// - the maintenance period is at least 1 day and the maintenance deadline is longer than the
+    ///   period
+    ///
+    /// # Example
+    /// ```
+    /// use planif::settings::{Duration, Settings};
+    ///
+    /// let settings = Settings {
+    ///     restart_interval: Some(Duration { minutes: Some(5), ..Default::default() }),
+    ///     ..Default::default()
+    /// };
+    /// let error = settings.validate().unwrap_err();
+    /// assert_eq!(error.field, "settings.restart_count");
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(limit) = &self.execution_time_limit {
+            check_duration("settings.execution_time_limit", limit, 0, None)?;
+        }
+        if let Some(delay) = &self.delete_expired_task_after {
+            check_duration("settings.delete_expired_task_after", delay, 0, None)?;
+        }
+
+        #[allow(deprecated)]
+        if let Some(idle_settings) = &self.idle_settings {
+            if let Some(duration) = &idle_settings.idle_duration {
+                check_duration("settings.idle_settings.idle_duration", duration, MINUTE, None)?;
+            }
+            if let Some(timeout) = &idle_settings.wait_timeout {
+                check_duration("settings.idle_settings.wait_timeout", timeout, MINUTE, None)?;
+            }
+        }
+
+        let error = |field: &str, message: String| {
+            Err(ValidationError {
+                field: format!("settings.{}", field),
+                message,
+            })
+        };
+
+        if let Some(maintenance) = &self.maintenance_settings {
+            let period = &maintenance.period;
+            check_duration("settings.maintenance_settings.period", period, DAY, None)?;
+            if let Some(deadline) = &maintenance.deadline {
+                check_duration("settings.maintenance_settings.deadline", deadline, DAY, None)?;
+                if deadline.seconds_range().0 <= period.seconds_range().1 {
+                    return error(
+                        "maintenance_settings.deadline",
+                        format!(
+                            "the deadline {} must be longer than the period {}",
+                            deadline, period
+                        ),
+                    );
+                }
+            }
+        }
+
+        match (self.restart_count, &self.restart_interval) {
+            (Some(_), None) => {
+                error("restart_interval", "is required with the restart count".to_string())
+            }
+            (None, Some(_)) => {
+                error("restart_count", "is required with the restart interval".to_string())
+            }
+            (Some(count), _) if !(1..=999).contains(&count) => error(
+                "restart_count",
+                format!("must be between 1 and 999, found {}", count),
+            ),
+            (_, Some(interval)) => {
+                check_duration("settings.restart_interval", interval, MINUTE, Some(31 * DAY))
+            }
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Builds the [Settings] of a task, which are validated by [Settings::validate].
+///
+/// # Example
+/// ```
+/// use planif::settings::{Duration, InstancesPolicy, Priority, SettingsBuilder};
+///
+/// let settings = SettingsBuilder::new()
+///     .execution_time_limit("2h".parse().unwrap())
+///     .priority(Priority::BelowNormal)
+///     .multiple_instances_policy(InstancesPolicy::IgnoreNew)
+///     .restart_on_failure(3, Duration { minutes: Some(10), ..Default::default() })
+///     .stop_if_going_on_batteries(false)
+///     .build()
+///     .unwrap();
+/// assert_eq!(settings.restart_count, Some(3));
+///
+/// // The Task Scheduler waits at least a minute before restarting a task
+/// let restart = SettingsBuilder::new()
+///     .restart_on_failure(3, "30s".parse().unwrap())
+///     .build();
+/// assert!(restart.is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SettingsBuilder {
+    settings: Settings,
+}
+
+impl SettingsBuilder {
+    /// Creates a builder where all the settings are unset, the Task Scheduler uses its defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the task can be started with the Run command or the context menu.
+    pub fn allow_demand_start(mut self, allow: bool) -> Self {
+        self.settings.allow_demand_start = Some(allow);
+        self
+    }
+
+    /// Sets whether the task may be terminated with `TerminateProcess`.
+    pub fn allow_hard_terminate(mut self, allow: bool) -> Self {
+        self.settings.allow_hard_terminate = Some(allow);
+        self
+    }
+
+    /// Sets the version of the Task Scheduler the task is compatible with.
+    pub fn compatibility(mut self, compatibility: Compatibility) -> Self {
+        self.settings.compatibility = Some(compatibility);
+        self
+    }
+
+    /// Deletes the task once it has expired and this delay has passed.
+    pub fn delete_expired_task_after(mut self, delay: Duration) -> Self {
+        self.settings.delete_expired_task_after = Some(delay);
+        self
+    }
+
+    /// Sets whether the task is not started when the computer is running on batteries.
+    pub fn disallow_start_if_on_batteries(mut self, disallow: bool) -> Self {
+        self.settings.disallow_start_if_on_batteries = Some(disallow);
+        self
+    }
+
+    /// Sets whether the task is not started in a Remote Applications Integrated Locally (RAIL)
+    /// session.
+    pub fn disallow_start_on_remote_app_session(mut self, disallow: bool) -> Self {
+        self.settings.disallow_start_on_remote_app_session = Some(disallow);
+        self
+    }
+
+    /// Sets whether the task is enabled.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.settings.enabled = Some(enabled);
+        self
+    }
+
+    /// Sets the amount of time allowed to complete the task, `PT0S` lets it run indefinitely.
+    pub fn execution_time_limit(mut self, limit: Duration) -> Self {
+        self.settings.execution_time_limit = Some(limit);
+        self
+    }
+
+    /// Sets whether the task is hidden in the UI.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.settings.hidden = Some(hidden);
+        self
+    }
+
+    /// Sets how the task is run when the computer is idle.
+    pub fn idle_settings(mut self, idle_settings: IdleSettings) -> Self {
+        self.settings.idle_settings = Some(idle_settings);
+        self
+    }
+
+    /// Runs the task during Automatic Maintenance.
+    pub fn maintenance_settings(mut self, maintenance_settings: MaintenanceSettings) -> Self {
+        self.settings.maintenance_settings = Some(maintenance_settings);
+        self
+    }
+
+    /// Sets how a new instance is handled when the task is already running.
+    pub fn multiple_instances_policy(mut self, policy: InstancesPolicy) -> Self {
+        self.settings.multiple_instances_policy = Some(policy);
+        self
+    }
+
+    /// Sets the network profile required to run the task.
+    pub fn network_settings(mut self, network_settings: NetworkSettings) -> Self {
+        self.settings.network_settings = Some(network_settings);
+        self
+    }
+
+    /// Sets the priority of the task.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.settings.priority = Some(priority);
+        self
+    }
+
+    /// Restarts the task up to `count` times, waiting `interval` between the attempts, when it
+    /// fails.
+    pub fn restart_on_failure(mut self, count: i32, interval: Duration) -> Self {
+        self.settings.restart_count = Some(count);
+        self.settings.restart_interval = Some(interval);
+        self
+    }
+
+    /// Sets whether the task is only run when the computer is idle.
+    pub fn run_only_if_idle(mut self, idle: bool) -> Self {
+        self.settings.run_only_if_idle = Some(idle);
+        self
+    }
+
+    /// Sets whether the task is only run when a network is available.
+    pub fn run_only_if_network_available(mut self, available: bool) -> Self {
+        self.settings.run_only_if_network_available = Some(available);
+        self
+    }
+
+    /// Sets whether the task is started as soon as possible after a missed start.
+    pub fn start_when_available(mut self, start: bool) -> Self {
+        self.settings.start_when_available = Some(start);
+        self
+    }
+
+    /// Sets whether the task is stopped when the computer switches to batteries.
+    pub fn stop_if_going_on_batteries(mut self, stop: bool) -> Self {
+        self.settings.stop_if_going_on_batteries = Some(stop);
+        self
+    }
+
+    /// Sets whether the task is run by the Unified Scheduling Engine.
+    pub fn use_unified_scheduling_engine(mut self, unified: bool) -> Self {
+        self.settings.use_unified_scheduling_engine = Some(unified);
+        self
+    }
+
+    /// Sets whether the task is disabled every time Windows starts.
+    pub fn volatile(mut self, volatile: bool) -> Self {
+        self.settings.volatile = Some(volatile);
+        self
+    }
+
+    /// Sets whether the computer is woken up to run the task.
+    pub fn wake_to_run(mut self, wake: bool) -> Self {
+        self.settings.wake_to_run = Some(wake);
+        self
+    }
+
+    /// Validates and returns the settings, see [Settings::validate].
+    pub fn build(self) -> Result<Settings, Error> {
+        self.settings.validate()?;
+        Ok(self.settings)
+    }
+}
+
+/// The priority of a task, which sets the priority class of its process and the priority of its
+/// thread. Tasks are created with the [BelowNormal](Priority::BelowNormal) priority.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/tasksettings-priority>
+///
+/// # Example
+/// ```
+/// use planif::settings::{Priority, ProcessPriorityClass, ThreadPriority};
+///
+/// let priority = Priority::try_from(4).unwrap();
+/// assert_eq!(priority, Priority::NormalHigh);
+/// assert_eq!(priority.priority_class(), ProcessPriorityClass::Normal);
+/// assert_eq!(priority.thread_priority(), ThreadPriority::Normal);
+/// assert!(Priority::try_from(11).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Priority {
+    /// `REALTIME_PRIORITY_CLASS` and `THREAD_PRIORITY_TIME_CRITICAL`.
+    Realtime = 0,
+    /// `HIGH_PRIORITY_CLASS` and `THREAD_PRIORITY_HIGHEST`.
+    High = 1,
+    /// `ABOVE_NORMAL_PRIORITY_CLASS` and `THREAD_PRIORITY_ABOVE_NORMAL`.
+    AboveNormal = 2,
+    /// `ABOVE_NORMAL_PRIORITY_CLASS` and `THREAD_PRIORITY_ABOVE_NORMAL`, below
+    /// [AboveNormal](Priority::AboveNormal).
+    AboveNormalLow = 3,
+    /// `NORMAL_PRIORITY_CLASS` and `THREAD_PRIORITY_NORMAL`, above [Normal](Priority::Normal).
+    NormalHigh = 4,
+    /// `NORMAL_PRIORITY_CLASS` and `THREAD_PRIORITY_NORMAL`.
+    Normal = 5,
+    /// `NORMAL_PRIORITY_CLASS` and `THREAD_PRIORITY_NORMAL`, below [Normal](Priority::Normal).
+    NormalLow = 6,
+    /// `BELOW_NORMAL_PRIORITY_CLASS` and `THREAD_PRIORITY_BELOW_NORMAL`, the default of tasks.
+    BelowNormal = 7,
+    /// `BELOW_NORMAL_PRIORITY_CLASS` and `THREAD_PRIORITY_BELOW_NORMAL`, below
+    /// [BelowNormal](Priority::BelowNormal).
+    BelowNormalLow = 8,
+    /// `IDLE_PRIORITY_CLASS` and `THREAD_PRIORITY_LOWEST`.
+    Lowest = 9,
+    /// `IDLE_PRIORITY_CLASS` and `THREAD_PRIORITY_IDLE`.
+    Idle = 10,
+}
+
+const PRIORITIES: [Priority; 11] = [
+    Priority::Realtime,
+    Priority::High,
+    Priority::AboveNormal,
+    Priority::AboveNormalLow,
+    Priority::NormalHigh,
+    Priority::Normal,
+    Priority::NormalLow,
+    Priority::BelowNormal,
+    Priority::BelowNormalLow,
+    Priority::Lowest,
+    Priority::Idle,
+];
+
+impl Priority {
+    /// The priority class of the process of the task.
+    pub fn priority_class(self) -> ProcessPriorityClass {
+        match self {
+            Priority::Realtime => ProcessPriorityClass::Realtime,
+            Priority::High => ProcessPriorityClass::High,
+            Priority::AboveNormal | Priority::AboveNormalLow => ProcessPriorityClass::AboveNormal,
+            Priority::NormalHigh | Priority::Normal | Priority::NormalLow => {
+                ProcessPriorityClass::Normal
+            }
+            Priority::BelowNormal | Priority::BelowNormalLow => ProcessPriorityClass::BelowNormal,
+            Priority::Lowest | Priority::Idle => ProcessPriorityClass::Idle,
+        }
+    }
+
+    /// The priority of the thread of the task.
+    pub fn thread_priority(self) -> ThreadPriority {
+        match self {
+            Priority::Realtime => ThreadPriority::TimeCritical,
+            Priority::High => ThreadPriority::Highest,
+            Priority::AboveNormal | Priority::AboveNormalLow => ThreadPriority::AboveNormal,
+            Priority::NormalHigh | Priority::Normal | Priority::NormalLow => ThreadPriority::Normal,
+            Priority::BelowNormal | Priority::BelowNormalLow => ThreadPriority::BelowNormal,
+            Priority::Lowest => ThreadPriority::Lowest,
+            Priority::Idle => ThreadPriority::Idle,
+        }
+    }
+}
+
+impl TryFrom<i32> for Priority {
+    type Error = ConversionError;
+
+    /// Converts a priority level of the Task Scheduler, between 0 and 10.
+    fn try_from(level: i32) -> Result<Self, Self::Error> {
+        usize::try_from(level)
+            .ok()
+            .and_then(|level| PRIORITIES.get(level))
+            .copied()
+            .ok_or_else(|| ConversionError {
+                message: format!("{} is not a priority between 0 and 10", level),
+            })
+    }
+}
+
+/// The priority classes of processes.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/procthread/scheduling-priorities>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessPriorityClass {
+    /// `REALTIME_PRIORITY_CLASS`
+    Realtime,
+    /// `HIGH_PRIORITY_CLASS`
+    High,
+    /// `ABOVE_NORMAL_PRIORITY_CLASS`
+    AboveNormal,
+    /// `NORMAL_PRIORITY_CLASS`
+    Normal,
+    /// `BELOW_NORMAL_PRIORITY_CLASS`
+    BelowNormal,
+    /// `IDLE_PRIORITY_CLASS`
+    Idle,
+}
+
+/// The priorities of threads within the priority class of their process.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/procthread/scheduling-priorities>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThreadPriority {
+    /// `THREAD_PRIORITY_TIME_CRITICAL`
+    TimeCritical,
+    /// `THREAD_PRIORITY_HIGHEST`
+    Highest,
+    /// `THREAD_PRIORITY_ABOVE_NORMAL`
+    AboveNormal,
+    /// `THREAD_PRIORITY_NORMAL`
+    Normal,
+    /// `THREAD_PRIORITY_BELOW_NORMAL`
+    BelowNormal,
+    /// `THREAD_PRIORITY_LOWEST`
+    Lowest,
+    /// `THREAD_PRIORITY_IDLE`
+    Idle,
+}
+
 /// Represents a duration of time.
-#[derive(Debug, Clone, Copy)]
+///
+/// A `Duration` can be parsed from the ISO 8601 form used by the Task Scheduler (ie: `P1DT2H`,
+/// `PT0S` or `P2W`) or from a shorter form such as `90s`, `1h30m` or `2d`. Weeks are converted to
+/// days.
+///
+/// # Example
+/// ```
+/// use planif::settings::Duration;
+///
+/// let duration: Duration = "1h30m".parse().unwrap();
+/// assert_eq!(duration, Duration { hours: Some(1), minutes: Some(30), ..Default::default() });
+/// assert_eq!(duration.to_string(), "PT1H30M");
+///
+/// let duration: Duration = "P1W".parse().unwrap();
+/// assert_eq!(duration.days, Some(7));
+///
+/// let error = "PT1H30X".parse::<Duration>().unwrap_err();
+/// assert_eq!(error.position, Some(6));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(missing_docs)]
 pub struct Duration {
     pub days: Option<usize>,
@@ -285,6 +1204,12 @@ impl Duration {
             years: None,
         }
     }
+
+    /// Whether no value of the duration is given, ie: `Duration::default()`. An empty repetition
+    /// duration repeats a trigger indefinitely, other durations cannot be empty.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::new()
+    }
 }
 
 macro_rules! format_duration {
@@ -299,6 +1224,7 @@ macro_rules! format_duration {
 
 impl fmt::Display for Duration {
     /// Formats a duration to a string similar to the duration of the ISO 8601 spec.
+    /// Zero values are omitted, except a duration of zero seconds which is formatted as `PT0S`.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut s = String::new();
 
@@ -308,13 +1234,20 @@ impl fmt::Display for Duration {
         format_duration!(s, self.months, "M");
         format_duration!(s, self.days, "D");
 
-        if self.hours.is_some() || self.minutes.is_some() || self.seconds.is_some() {
-            s = format!("{}T", s);
-            format_duration!(s, self.hours, "H");
-            format_duration!(s, self.minutes, "M");
-            format_duration!(s, self.seconds, "S");
+        let mut time = String::new();
+        format_duration!(time, self.hours, "H");
+        format_duration!(time, self.minutes, "M");
+        format_duration!(time, self.seconds, "S");
+        if !time.is_empty() {
+            s = format!("{}T{}", s, time);
         }
+
         if s.is_empty() {
+            // PT0S is meaningful to the Task Scheduler, ie: an execution time limit of PT0S
+            // lets the task run indefinitely.
+            if self.seconds == Some(0) {
+                return write!(f, "PT0S");
+            }
             return write!(f, "")
         }
 
@@ -322,12 +1255,420 @@ impl fmt::Display for Duration {
     }
 }
 
+impl FromStr for Duration {
+    type Err = ParseError;
+
+    /// Parses an ISO 8601 duration (ie: `P1Y2M3DT4H5M6S`) or a duration such as `1h30m`.
+    ///
+    /// The units of the short form are `y` (years), `mo` (months), `w` (weeks), `d` (days),
+    /// `h` (hours), `m` (minutes) and `s` (seconds) and may be separated by spaces. In both forms,
+    /// the units must appear from the largest to the smallest and at most once.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes().first() {
+            Some(b'P') => parse_iso_8601(s),
+            Some(c) if c.is_ascii_digit() => parse_short(s),
+            _ => Err(duration_error(
+                s,
+                0,
+                "expected an ISO 8601 duration such as P1DT2H or a duration such as 1h30m",
+            )),
+        }
+    }
+}
+
+// Units of a duration, from the largest to the smallest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Unit {
+    Years,
+    Months,
+    Weeks,
+    Days,
+    Hours,
+    Minutes,
+    Seconds,
+}
+
+fn duration_error(s: &str, position: usize, message: &str) -> ParseError {
+    ParseError {
+        message: format!("Invalid duration '{}': {}", s, message),
+        position: Some(position),
+    }
+}
+
+struct DurationParser<'a> {
+    input: &'a str,
+    position: usize,
+    duration: Duration,
+    last_unit: Option<Unit>,
+}
+
+impl<'a> DurationParser<'a> {
+    fn new(input: &'a str, position: usize) -> Self {
+        Self {
+            input,
+            position,
+            duration: Duration::new(),
+            last_unit: None,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn error(&self, position: usize, message: &str) -> ParseError {
+        duration_error(self.input, position, message)
+    }
+
+    fn number(&mut self) -> Result<usize, ParseError> {
+        let start = self.position;
+        let digits = self
+            .rest()
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest().len());
+        if digits == 0 {
+            return Err(self.error(start, "expected a number"));
+        }
+        self.position += digits;
+
+        if self.rest().starts_with(['.', ',']) {
+            return Err(self.error(self.position, "fractions are not supported"));
+        }
+        self.input[start..self.position]
+            .parse()
+            .map_err(|_| self.error(start, "the number is too large"))
+    }
+
+    fn unit_token(&mut self) -> &'a str {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    fn set(&mut self, unit: Unit, value: usize, position: usize) -> Result<(), ParseError> {
+        if self.last_unit.is_some_and(|last| last >= unit) {
+            return Err(self.error(
+                position,
+                "units must appear from the largest to the smallest and at most once",
+            ));
+        }
+        self.last_unit = Some(unit);
+
+        let field = match unit {
+            Unit::Years => &mut self.duration.years,
+            Unit::Months => &mut self.duration.months,
+            Unit::Weeks | Unit::Days => &mut self.duration.days,
+            Unit::Hours => &mut self.duration.hours,
+            Unit::Minutes => &mut self.duration.minutes,
+            Unit::Seconds => &mut self.duration.seconds,
+        };
+        let value = match unit {
+            Unit::Weeks => value.checked_mul(7),
+            _ => Some(value),
+        };
+        // Weeks are stored as days, the days may follow them
+        *field = value
+            .and_then(|value| value.checked_add(field.unwrap_or(0)))
+            .map(Some)
+            .ok_or_else(|| duration_error(self.input, position, "the number is too large"))?;
+        Ok(())
+    }
+}
+
+fn parse_iso_8601(s: &str) -> Result<Duration, ParseError> {
+    let mut parser = DurationParser::new(s, 1);
+    let mut is_time = false;
+
+    while !parser.rest().is_empty() {
+        if parser.rest().starts_with('T') {
+            if is_time {
+                return Err(parser.error(parser.position, "'T' appears more than once"));
+            }
+            is_time = true;
+            parser.position += 1;
+            if parser.rest().is_empty() {
+                return Err(parser.error(
+                    parser.position,
+                    "expected hours, minutes or seconds after 'T'",
+                ));
+            }
+            continue;
+        }
+
+        let value = parser.number()?;
+        let position = parser.position;
+        let unit = match (parser.rest().chars().next(), is_time) {
+            (Some('Y'), false) => Unit::Years,
+            (Some('M'), false) => Unit::Months,
+            (Some('W'), false) => Unit::Weeks,
+            (Some('D'), false) => Unit::Days,
+            (Some('H'), true) => Unit::Hours,
+            (Some('M'), true) => Unit::Minutes,
+            (Some('S'), true) => Unit::Seconds,
+            (Some('H' | 'S'), false) => {
+                return Err(parser.error(position, "hours and seconds must follow 'T'"))
+            }
+            (Some('Y' | 'W' | 'D'), true) => {
+                return Err(parser.error(position, "years, weeks and days must precede 'T'"))
+            }
+            (Some(_), _) => {
+                return Err(parser.error(position, "expected one of the units Y, M, W, D, H or S"))
+            }
+            (None, _) => return Err(parser.error(position, "expected a unit")),
+        };
+        parser.position += 1;
+        parser.set(unit, value, position)?;
+    }
+
+    if parser.last_unit.is_none() {
+        return Err(parser.error(s.len(), "expected at least one value"));
+    }
+    Ok(parser.duration)
+}
+
+fn parse_short(s: &str) -> Result<Duration, ParseError> {
+    let mut parser = DurationParser::new(s, 0);
+
+    while !parser.rest().is_empty() {
+        let value = parser.number()?;
+        let position = parser.position;
+        let unit = match parser.unit_token() {
+            "y" => Unit::Years,
+            "mo" => Unit::Months,
+            "w" => Unit::Weeks,
+            "d" => Unit::Days,
+            "h" => Unit::Hours,
+            "m" => Unit::Minutes,
+            "s" => Unit::Seconds,
+            "" => return Err(parser.error(position, "expected a unit")),
+            _ => {
+                return Err(parser.error(
+                    position,
+                    "expected one of the units y, mo, w, d, h, m or s",
+                ))
+            }
+        };
+        parser.set(unit, value, position)?;
+
+        let spaces = parser.rest().len() - parser.rest().trim_start_matches(' ').len();
+        parser.position += spaces;
+        if spaces > 0 && parser.rest().is_empty() {
+            return Err(parser.error(parser.position - spaces, "unexpected trailing spaces"));
+        }
+    }
+    Ok(parser.duration)
+}
+
 impl Default for Duration {
     fn default() -> Self {
         Self::new()
     }
 }
 
+const SECONDS_PER_MINUTE: u64 = 60;
+const SECONDS_PER_HOUR: u64 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
+
+impl Duration {
+    /// Returns the length of the duration in seconds.
+    /// Years and months have no fixed length, durations using them cannot be converted.
+    pub(crate) fn total_seconds(&self) -> Result<u64, ConversionError> {
+        if self.years.unwrap_or(0) > 0 || self.months.unwrap_or(0) > 0 {
+            return Err(ConversionError {
+                message: format!(
+                    "{} has years or months which do not have a fixed length",
+                    self
+                ),
+            });
+        }
+
+        [
+            (self.days, SECONDS_PER_DAY),
+            (self.hours, SECONDS_PER_HOUR),
+            (self.minutes, SECONDS_PER_MINUTE),
+            (self.seconds, 1),
+        ]
+        .iter()
+        .try_fold(0u64, |total, (value, unit)| {
+            (value.unwrap_or(0) as u64)
+                .checked_mul(*unit)
+                .and_then(|seconds| total.checked_add(seconds))
+        })
+        .ok_or_else(|| ConversionError {
+            message: format!("{} is too long", self),
+        })
+    }
+
+    /// Returns the shortest and longest length of the duration in seconds. Months are between 28
+    /// and 31 days and years between 365 and 366 days. The lengths saturate at `u64::MAX`.
+    pub(crate) fn seconds_range(&self) -> (u64, u64) {
+        let length = |month: u64, year: u64| {
+            [
+                (self.years, year),
+                (self.months, month),
+                (self.days, SECONDS_PER_DAY),
+                (self.hours, SECONDS_PER_HOUR),
+                (self.minutes, SECONDS_PER_MINUTE),
+                (self.seconds, 1),
+            ]
+            .iter()
+            .fold(0u64, |total, (value, unit)| {
+                total.saturating_add((value.unwrap_or(0) as u64).saturating_mul(*unit))
+            })
+        };
+        (
+            length(28 * SECONDS_PER_DAY, 365 * SECONDS_PER_DAY),
+            length(31 * SECONDS_PER_DAY, 366 * SECONDS_PER_DAY),
+        )
+    }
+
+    // Splits seconds into days, hours, minutes and seconds, ie: 5400 seconds is 1h30m.
+    fn from_seconds(seconds: u64) -> Result<Self, ConversionError> {
+        let non_zero = |value: u64| -> Result<Option<usize>, ConversionError> {
+            match value {
+                0 => Ok(None),
+                value => usize::try_from(value).map(Some).map_err(|_| ConversionError {
+                    message: format!("{} seconds is too long", seconds),
+                }),
+            }
+        };
+
+        if seconds == 0 {
+            return Ok(Duration {
+                seconds: Some(0),
+                ..Default::default()
+            });
+        }
+
+        Ok(Duration {
+            days: non_zero(seconds / SECONDS_PER_DAY)?,
+            hours: non_zero(seconds % SECONDS_PER_DAY / SECONDS_PER_HOUR)?,
+            minutes: non_zero(seconds % SECONDS_PER_HOUR / SECONDS_PER_MINUTE)?,
+            seconds: non_zero(seconds % SECONDS_PER_MINUTE)?,
+            ..Default::default()
+        })
+    }
+}
+
+fn fraction_error() -> ConversionError {
+    ConversionError {
+        message: "fractions of a second cannot be represented".to_string(),
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn negative_error() -> ConversionError {
+    ConversionError {
+        message: "negative durations cannot be represented".to_string(),
+    }
+}
+
+/// Converts a [std::time::Duration], the value is split in days, hours, minutes and seconds.
+///
+/// # Example
+/// ```
+/// use planif::settings::Duration;
+///
+/// let duration = Duration::try_from(std::time::Duration::from_secs(90 * 60)).unwrap();
+/// assert_eq!(duration, Duration { hours: Some(1), minutes: Some(30), ..Default::default() });
+///
+/// assert!(Duration::try_from(std::time::Duration::from_millis(1500)).is_err());
+/// ```
+impl TryFrom<std::time::Duration> for Duration {
+    type Error = ConversionError;
+
+    fn try_from(duration: std::time::Duration) -> Result<Self, Self::Error> {
+        if duration.subsec_nanos() != 0 {
+            return Err(fraction_error());
+        }
+        Duration::from_seconds(duration.as_secs())
+    }
+}
+
+/// Converts to a [std::time::Duration]. Durations with years or months cannot be converted.
+///
+/// # Example
+/// ```
+/// use planif::settings::Duration;
+///
+/// let duration = Duration { days: Some(1), minutes: Some(1), ..Default::default() };
+/// assert_eq!(std::time::Duration::try_from(duration).unwrap().as_secs(), 86_460);
+///
+/// let duration = Duration { months: Some(1), ..Default::default() };
+/// assert!(std::time::Duration::try_from(duration).is_err());
+/// ```
+impl TryFrom<Duration> for std::time::Duration {
+    type Error = ConversionError;
+
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        Ok(std::time::Duration::from_secs(duration.total_seconds()?))
+    }
+}
+
+/// Converts a [chrono::Duration], the value is split in days, hours, minutes and seconds.
+#[cfg(feature = "chrono")]
+impl TryFrom<chrono::Duration> for Duration {
+    type Error = ConversionError;
+
+    fn try_from(duration: chrono::Duration) -> Result<Self, Self::Error> {
+        if duration < chrono::Duration::zero() {
+            return Err(negative_error());
+        }
+        if duration.subsec_nanos() != 0 {
+            return Err(fraction_error());
+        }
+        Duration::from_seconds(duration.num_seconds() as u64)
+    }
+}
+
+/// Converts to a [chrono::Duration]. Durations with years or months cannot be converted.
+#[cfg(feature = "chrono")]
+impl TryFrom<Duration> for chrono::Duration {
+    type Error = ConversionError;
+
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        i64::try_from(duration.total_seconds()?)
+            .ok()
+            .and_then(chrono::Duration::try_seconds)
+            .ok_or_else(|| ConversionError {
+                message: format!("{} is too long", duration),
+            })
+    }
+}
+
+/// Converts a [time::Duration], the value is split in days, hours, minutes and seconds.
+#[cfg(feature = "time")]
+impl TryFrom<time::Duration> for Duration {
+    type Error = ConversionError;
+
+    fn try_from(duration: time::Duration) -> Result<Self, Self::Error> {
+        if duration.is_negative() {
+            return Err(negative_error());
+        }
+        if duration.subsec_nanoseconds() != 0 {
+            return Err(fraction_error());
+        }
+        Duration::from_seconds(duration.whole_seconds() as u64)
+    }
+}
+
+/// Converts to a [time::Duration]. Durations with years or months cannot be converted.
+#[cfg(feature = "time")]
+impl TryFrom<Duration> for time::Duration {
+    type Error = ConversionError;
+
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        i64::try_from(duration.total_seconds()?)
+            .map(time::Duration::seconds)
+            .map_err(|_| ConversionError {
+                message: format!("{} is too long", duration),
+            })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,4 +1716,187 @@ mod tests {
 
         assert_eq!("", d.to_string());
     }
+
+    #[test]
+    fn duration_zero_seconds() {
+        let d = Duration {
+            seconds: Some(0),
+            ..Default::default()
+        };
+        assert_eq!("PT0S", d.to_string());
+
+        let d = Duration {
+            days: Some(1),
+            seconds: Some(0),
+            ..Default::default()
+        };
+        assert_eq!("P1D", d.to_string());
+    }
+
+    #[test]
+    fn parse_iso_8601_duration() {
+        let d: Duration = "P1Y2M3DT4H5M6S".parse().unwrap();
+        assert_eq!(
+            d,
+            Duration {
+                years: Some(1),
+                months: Some(2),
+                days: Some(3),
+                hours: Some(4),
+                minutes: Some(5),
+                seconds: Some(6),
+            }
+        );
+        assert_eq!("P1Y2M3DT4H5M6S", d.to_string());
+
+        let d: Duration = "PT0S".parse().unwrap();
+        assert_eq!("PT0S", d.to_string());
+
+        let d: Duration = "P1W2D".parse().unwrap();
+        assert_eq!(d.days, Some(9));
+
+        let d: Duration = "PT90M".parse().unwrap();
+        assert_eq!(d.minutes, Some(90));
+    }
+
+    #[test]
+    fn parse_short_duration() {
+        let d: Duration = "90s".parse().unwrap();
+        assert_eq!(d.seconds, Some(90));
+
+        let d: Duration = "1h30m".parse().unwrap();
+        assert_eq!("PT1H30M", d.to_string());
+
+        let d: Duration = "2d 12h".parse().unwrap();
+        assert_eq!("P2DT12H", d.to_string());
+
+        let d: Duration = "1y6mo2w".parse().unwrap();
+        assert_eq!("P1Y6M14D", d.to_string());
+    }
+
+    #[test]
+    fn parse_duration_errors() {
+        let cases = [
+            ("", 0),
+            ("P", 1),
+            ("PT", 2),
+            ("P1H", 2),
+            ("PT1D", 3),
+            ("P1DT2H3X", 7),
+            ("P1DT1HT", 6),
+            ("PT1M1H", 5),
+            ("P1D1D", 4),
+            ("PT1.5S", 3),
+            ("P99999999999999999999D", 1),
+            ("1h30", 4),
+            ("1x", 1),
+            ("30m1h", 4),
+            ("1h ", 2),
+            ("h", 0),
+            ("-P1D", 0),
+        ];
+
+        for (s, position) in cases {
+            let error = s.parse::<Duration>().unwrap_err();
+            assert_eq!(error.position, Some(position), "{}: {}", s, error);
+        }
+    }
+
+    #[test]
+    fn std_duration_conversions() {
+        let d = Duration::try_from(std::time::Duration::from_secs(90 * 60 + 5)).unwrap();
+        assert_eq!("PT1H30M5S", d.to_string());
+
+        let d = Duration::try_from(std::time::Duration::from_secs(2 * 86_400)).unwrap();
+        assert_eq!("P2D", d.to_string());
+
+        let d = Duration::try_from(std::time::Duration::ZERO).unwrap();
+        assert_eq!("PT0S", d.to_string());
+
+        let d = Duration {
+            days: Some(1),
+            hours: Some(25),
+            ..Default::default()
+        };
+        assert_eq!(
+            std::time::Duration::try_from(d).unwrap(),
+            std::time::Duration::from_secs(49 * 3600)
+        );
+
+        let d = Duration {
+            years: Some(1),
+            ..Default::default()
+        };
+        assert!(std::time::Duration::try_from(d).is_err());
+
+        let d = Duration {
+            days: Some(usize::MAX),
+            ..Default::default()
+        };
+        assert!(std::time::Duration::try_from(d).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_duration_conversions() {
+        let d = Duration::try_from(chrono::Duration::minutes(90)).unwrap();
+        assert_eq!("PT1H30M", d.to_string());
+        assert_eq!(chrono::Duration::try_from(d).unwrap(), chrono::Duration::minutes(90));
+
+        assert!(Duration::try_from(chrono::Duration::seconds(-1)).is_err());
+        assert!(Duration::try_from(chrono::Duration::milliseconds(1500)).is_err());
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_duration_conversions() {
+        let d = Duration::try_from(time::Duration::minutes(90)).unwrap();
+        assert_eq!("PT1H30M", d.to_string());
+        assert_eq!(time::Duration::try_from(d).unwrap(), time::Duration::minutes(90));
+
+        assert!(Duration::try_from(time::Duration::seconds(-1)).is_err());
+        assert!(Duration::try_from(time::Duration::milliseconds(1500)).is_err());
+    }
+
+    #[test]
+    fn principal_builder() {
+        let field = |builder: PrincipalBuilder| match builder.build() {
+            Err(Error::Validation(e)) => e.field,
+            result => panic!("unexpected result {:?}", result),
+        };
+
+        let local_service = PrincipalBuilder::local_service().build().unwrap();
+        assert_eq!(local_service.user_id.as_deref(), Some("S-1-5-19"));
+        assert_eq!(local_service.id, "Author");
+
+        let group = PrincipalBuilder::group("S-1-5-32-545").build().unwrap();
+        assert_eq!(group.logon_type, LogonType::Group);
+        assert_eq!(group.user_id, None);
+
+        let user = PrincipalBuilder::group("Users")
+            .user_id("Alice")
+            .logon_type(LogonType::InteractiveToken)
+            .build()
+            .unwrap();
+        assert_eq!(user.group_id, None);
+
+        assert_eq!(
+            field(PrincipalBuilder::new().logon_type(LogonType::Group)),
+            "principal.group_id"
+        );
+        assert_eq!(field(PrincipalBuilder::group("S-1-5-x")), "principal.group_id");
+        assert_eq!(
+            field(PrincipalBuilder::user("Alice").logon_type(LogonType::ServiceAccount)),
+            "principal.user_id"
+        );
+        assert_eq!(
+            field(PrincipalBuilder::new().run_whether_logged_on(true)),
+            "principal.user_id"
+        );
+        assert_eq!(
+            field(PrincipalBuilder::group("Users").run_whether_logged_on(false)),
+            "principal.logon_type"
+        );
+        assert!(is_service_account("NT AUTHORITY\\Network Service"));
+    }
 }
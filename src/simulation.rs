@@ -0,0 +1,496 @@
+use crate::datetime::DateTime;
+use crate::error::{ConversionError, Error};
+use crate::settings::{Duration, InstancesPolicy};
+use crate::task_definition::TaskDefinition;
+use std::ops::RangeInclusive;
+
+// The execution time limit of the Task Scheduler when none is set.
+const DEFAULT_EXECUTION_TIME_LIMIT: i64 = 72 * 3600;
+
+/// Simulates the executions of one or more tasks over a date range, to review when they run
+/// before they are registered.
+///
+/// The executions are computed from the [occurrences](Trigger::occurrences) of the calendar
+/// triggers, including their repetitions and end boundaries. Each execution may start at any time
+/// in the random delay of its trigger and is assumed to run until its execution time limit, the
+/// [InstancesPolicy] of the task then decides whether an execution which starts while another
+/// instance of the task runs is started in parallel, queued, skipped or stops the running instance.
+/// Triggers started by an event, such as boot or logon triggers, and disabled tasks are ignored.
+///
+/// # Example
+/// ```
+/// use planif::com::ComRuntime;
+/// use planif::schedule_builder::ScheduleBuilder;
+/// use planif::settings::{Duration, InstancesPolicy, Settings};
+/// use planif::simulation::{Outcome, Simulator};
+///
+/// let com = ComRuntime::new().unwrap();
+/// let schedule = ScheduleBuilder::new(&com).unwrap()
+///     .create_daily()
+///     .trigger("Backup", true).unwrap()
+///     .start_boundary("2023-01-01T01:00:00").unwrap()
+///     .repetition(
+///         Duration { hours: Some(4), ..Default::default() },
+///         Duration { hours: Some(1), ..Default::default() },
+///         false,
+///     ).unwrap()
+///     .execution_time_limit(Duration { minutes: Some(90), ..Default::default() }).unwrap()
+///     .settings(Settings {
+///         multiple_instances_policy: Some(InstancesPolicy::IgnoreNew),
+///         ..Default::default()
+///     }).unwrap()
+///     .build().unwrap();
+///
+/// let timeline = Simulator::new()
+///     .task("Backup", schedule.definition())
+///     .run("2023-01-01T00:00:00".parse().unwrap(), "2023-01-02T00:00:00".parse().unwrap())
+///     .unwrap();
+///
+/// let outcomes: Vec<Outcome> = timeline.executions.iter().map(|e| e.outcome).collect();
+/// assert_eq!(outcomes, vec![
+///     Outcome::Started,  // 01:00
+///     Outcome::Skipped,  // 02:00
+///     Outcome::Started,  // 03:00
+///     Outcome::Skipped,  // 04:00
+///     Outcome::Started,  // 05:00
+/// ]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Simulator<'a> {
+    tasks: Vec<(String, &'a TaskDefinition)>,
+}
+
+impl<'a> Simulator<'a> {
+    /// Creates a simulator without tasks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a task to the simulation, `name` identifies its executions in the timeline.
+    pub fn task(mut self, name: &str, definition: &'a TaskDefinition) -> Self {
+        self.tasks.push((name.to_string(), definition));
+        self
+    }
+
+    /// Returns the executions of the tasks scheduled from `from` (inclusive) to `to` (exclusive),
+    /// ordered by their scheduled time. The executions scheduled earlier which may still be
+    /// running at `from` are included, since they decide whether the first executions in the range
+    /// overlap, are queued or are skipped.
+    pub fn run(&self, from: DateTime, to: DateTime) -> Result<Timeline, Error> {
+        let mut executions = Vec::new();
+        for (name, definition) in &self.tasks {
+            simulate(name, definition, &from, &to, &mut executions)?;
+        }
+        executions.sort_by(|a, b| (a.scheduled, &a.task).cmp(&(b.scheduled, &b.task)));
+        Ok(Timeline {
+            from,
+            to,
+            executions,
+        })
+    }
+}
+
+/// The executions of the tasks of a [Simulator] over a date range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeline {
+    /// The start of the simulated range, inclusive.
+    pub from: DateTime,
+    /// The end of the simulated range, exclusive.
+    pub to: DateTime,
+    /// The executions, ordered by their scheduled time.
+    pub executions: Vec<Execution>,
+}
+
+/// An execution of a task in a [Timeline].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Execution {
+    /// The name of the task given to the [Simulator].
+    pub task: String,
+    /// The identifier of the trigger starting the execution.
+    pub trigger: String,
+    /// The occurrence of the trigger.
+    pub scheduled: DateTime,
+    /// The range in which the execution starts, which is the random delay of the trigger after
+    /// the scheduled time, or the end of the running instance for a queued execution. `None` when
+    /// the execution never starts: it is skipped or queued after an instance without time limit.
+    pub start: Option<RangeInclusive<DateTime>>,
+    /// The latest time at which the execution is stopped, by its execution time limit or by the
+    /// next execution. `None` when the execution never starts or has no time limit.
+    pub end: Option<DateTime>,
+    /// What happens to the execution.
+    pub outcome: Outcome,
+    /// Whether the execution is stopped by the next execution of the task, with the
+    /// [StopExisting](InstancesPolicy::StopExisting) policy.
+    pub stopped: bool,
+}
+
+/// What happens to an [Execution], depending on the [InstancesPolicy] of the task when another
+/// instance of the task may still be running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The execution starts, no other instance of the task is running.
+    Started,
+    /// The execution starts while another instance of the task may be running.
+    Overlapping,
+    /// The execution starts when the running instance of the task ends.
+    Queued,
+    /// The execution does not start because another instance of the task may be running.
+    Skipped,
+    /// The execution stops the running instance of the task and starts.
+    StoppedExisting,
+}
+
+fn simulate(
+    name: &str,
+    definition: &TaskDefinition,
+    from: &DateTime,
+    to: &DateTime,
+    executions: &mut Vec<Execution>,
+) -> Result<(), Error> {
+    if definition.settings.enabled == Some(false) {
+        return Ok(());
+    }
+
+    let default_limit = match &definition.settings.execution_time_limit {
+        Some(limit) => seconds(limit)?,
+        None => DEFAULT_EXECUTION_TIME_LIMIT,
+    };
+
+    let mut triggers = Vec::new();
+    for trigger in definition.triggers.iter().filter(|t| t.kind.is_calendar()) {
+        let limit = match &trigger.execution_time_limit {
+            Some(limit) => seconds(limit)?,
+            None => default_limit,
+        };
+        let delay = match trigger.kind.random_delay() {
+            Some(delay) => seconds(delay)?,
+            None => 0,
+        };
+        triggers.push((trigger, delay, limit));
+    }
+
+    // The executions scheduled before `from` which may still be running at `from` are simulated,
+    // an execution without time limit is assumed to run for the default execution time limit.
+    // Executions scheduled earlier are not, even though they may have delayed the later ones.
+    let lookback = triggers
+        .iter()
+        .map(|(_, delay, limit)| match limit {
+            0 => delay.saturating_add(DEFAULT_EXECUTION_TIME_LIMIT),
+            limit => delay.saturating_add(*limit),
+        })
+        .max()
+        .unwrap_or(0);
+    let mut occurrences = Vec::new();
+    for (trigger, delay, limit) in triggers {
+        for scheduled in trigger
+            .occurrences(from.saturating_add_seconds(-lookback.saturating_add(1)))?
+            .take_while(|time| time < to)
+        {
+            occurrences.push((scheduled, trigger, delay, limit));
+        }
+    }
+    occurrences.sort_by_key(|(scheduled, ..)| *scheduled);
+
+    let policy = definition
+        .settings
+        .multiple_instances_policy
+        .unwrap_or(InstancesPolicy::IgnoreNew);
+    // When the running instances end, `Some(None)` when an instance has no time limit.
+    let mut busy_until: Option<Option<DateTime>> = None;
+    let mut running: Option<usize> = None;
+    let mut simulated: Vec<Execution> = Vec::new();
+
+    for (scheduled, trigger, delay, limit) in occurrences {
+        let window = scheduled..=scheduled.saturating_add_seconds(delay);
+        let is_busy = busy_until.is_some_and(|end| end.map_or(true, |end| scheduled < end));
+
+        let (outcome, start) = match policy {
+            _ if !is_busy => (Outcome::Started, Some(window)),
+            InstancesPolicy::Parallel => (Outcome::Overlapping, Some(window)),
+            InstancesPolicy::IgnoreNew => (Outcome::Skipped, None),
+            InstancesPolicy::Queue => (Outcome::Queued, busy_until.flatten().map(|end| end..=end)),
+            InstancesPolicy::StopExisting => {
+                if let Some(execution) = running.map(|i| &mut simulated[i]) {
+                    execution.stopped = true;
+                    execution.end = Some(scheduled);
+                }
+                (Outcome::StoppedExisting, Some(window))
+            }
+        };
+
+        let end = match &start {
+            Some(start) if limit > 0 => Some(start.end().saturating_add_seconds(limit)),
+            _ => None,
+        };
+        if start.is_some() {
+            busy_until = match (outcome, busy_until) {
+                (Outcome::Overlapping, Some(busy)) => Some(busy.zip(end).map(|(a, b)| a.max(b))),
+                _ => Some(end),
+            };
+            running = Some(simulated.len());
+        }
+
+        simulated.push(Execution {
+            task: name.to_string(),
+            trigger: trigger.id.clone(),
+            scheduled,
+            start,
+            end,
+            outcome,
+            stopped: false,
+        });
+    }
+
+    // Keeps the executions scheduled in the range and the earlier ones still running at `from`.
+    executions.extend(simulated.into_iter().filter(|execution| {
+        execution.scheduled >= *from
+            || (execution.start.is_some() && !matches!(execution.end, Some(end) if end <= *from))
+    }));
+    Ok(())
+}
+
+fn seconds(duration: &Duration) -> Result<i64, Error> {
+    let seconds = duration.total_seconds()?;
+    i64::try_from(seconds).map_err(|_| {
+        Error::Conversion(ConversionError {
+            message: format!("{} is too long", duration),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+    use crate::task_definition::{RepetitionPattern, TimeTrigger, Trigger, TriggerKind};
+
+    fn minutes(minutes: usize) -> Duration {
+        Duration {
+            minutes: Some(minutes),
+            ..Default::default()
+        }
+    }
+
+    // Starts at 08:00 and every 30 minutes until 09:00, and runs for up to 45 minutes.
+    fn definition(policy: InstancesPolicy) -> TaskDefinition {
+        let mut definition = TaskDefinition::new();
+        definition.settings = Settings {
+            multiple_instances_policy: Some(policy),
+            ..Default::default()
+        };
+        definition.triggers.push(Trigger {
+            start_boundary: Some("2023-01-01T08:00:00".to_string()),
+            execution_time_limit: Some(minutes(45)),
+            repetition: Some(RepetitionPattern {
+                duration: minutes(60),
+                interval: minutes(30),
+                stop_at_duration_end: false,
+            }),
+            ..Trigger::new(
+                "Time",
+                TriggerKind::Time(TimeTrigger {
+                    random_delay: Some(minutes(5)),
+                }),
+            )
+        });
+        definition
+    }
+
+    fn simulate(policy: InstancesPolicy) -> Vec<(Outcome, Option<String>, Option<String>)> {
+        let definition = definition(policy);
+        Simulator::new()
+            .task("Task", &definition)
+            .run(
+                "2023-01-01T00:00:00".parse().unwrap(),
+                "2023-01-02T00:00:00".parse().unwrap(),
+            )
+            .unwrap()
+            .executions
+            .into_iter()
+            .map(|e| {
+                let start = e.start.map(|s| format!("{}..{}", s.start(), s.end()));
+                (e.outcome, start, e.end.map(|e| e.to_string()))
+            })
+            .collect()
+    }
+
+    fn some(s: &str) -> Option<String> {
+        Some(s.to_string())
+    }
+
+    #[test]
+    fn instances_policies() {
+        assert_eq!(
+            simulate(InstancesPolicy::Parallel),
+            vec![
+                (
+                    Outcome::Started,
+                    some("2023-01-01T08:00:00..2023-01-01T08:05:00"),
+                    some("2023-01-01T08:50:00")
+                ),
+                (
+                    Outcome::Overlapping,
+                    some("2023-01-01T08:30:00..2023-01-01T08:35:00"),
+                    some("2023-01-01T09:20:00")
+                ),
+                (
+                    Outcome::Overlapping,
+                    some("2023-01-01T09:00:00..2023-01-01T09:05:00"),
+                    some("2023-01-01T09:50:00")
+                ),
+            ]
+        );
+
+        assert_eq!(
+            simulate(InstancesPolicy::IgnoreNew),
+            vec![
+                (
+                    Outcome::Started,
+                    some("2023-01-01T08:00:00..2023-01-01T08:05:00"),
+                    some("2023-01-01T08:50:00")
+                ),
+                (Outcome::Skipped, None, None),
+                (
+                    Outcome::Started,
+                    some("2023-01-01T09:00:00..2023-01-01T09:05:00"),
+                    some("2023-01-01T09:50:00")
+                ),
+            ]
+        );
+
+        assert_eq!(
+            simulate(InstancesPolicy::Queue),
+            vec![
+                (
+                    Outcome::Started,
+                    some("2023-01-01T08:00:00..2023-01-01T08:05:00"),
+                    some("2023-01-01T08:50:00")
+                ),
+                (
+                    Outcome::Queued,
+                    some("2023-01-01T08:50:00..2023-01-01T08:50:00"),
+                    some("2023-01-01T09:35:00")
+                ),
+                (
+                    Outcome::Queued,
+                    some("2023-01-01T09:35:00..2023-01-01T09:35:00"),
+                    some("2023-01-01T10:20:00")
+                ),
+            ]
+        );
+
+        assert_eq!(
+            simulate(InstancesPolicy::StopExisting),
+            vec![
+                (
+                    Outcome::Started,
+                    some("2023-01-01T08:00:00..2023-01-01T08:05:00"),
+                    some("2023-01-01T08:30:00")
+                ),
+                (
+                    Outcome::StoppedExisting,
+                    some("2023-01-01T08:30:00..2023-01-01T08:35:00"),
+                    some("2023-01-01T09:00:00")
+                ),
+                (
+                    Outcome::StoppedExisting,
+                    some("2023-01-01T09:00:00..2023-01-01T09:05:00"),
+                    some("2023-01-01T09:50:00")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn simulation_range() {
+        let definition = definition(InstancesPolicy::Parallel);
+        let timeline = Simulator::new()
+            .task("Task", &definition)
+            .run(
+                "2023-01-01T08:30:00".parse().unwrap(),
+                "2023-01-01T09:00:00".parse().unwrap(),
+            )
+            .unwrap();
+
+        // The execution of 08:00 runs until 08:50 at the latest
+        let scheduled: Vec<(String, Outcome)> = timeline
+            .executions
+            .iter()
+            .map(|e| (e.scheduled.to_string(), e.outcome))
+            .collect();
+        assert_eq!(
+            scheduled,
+            vec![
+                ("2023-01-01T08:00:00".to_string(), Outcome::Started),
+                ("2023-01-01T08:30:00".to_string(), Outcome::Overlapping),
+            ]
+        );
+    }
+
+    #[test]
+    fn executions_running_at_the_start_of_the_range() {
+        // Starts at 23:00 and every hour, and runs for up to 3 hours.
+        let mut definition = TaskDefinition::new();
+        definition.settings.execution_time_limit = Some(minutes(180));
+        definition.triggers.push(Trigger {
+            start_boundary: Some("2022-12-31T23:00:00".to_string()),
+            repetition: Some(RepetitionPattern {
+                duration: Duration::default(),
+                interval: minutes(60),
+                stop_at_duration_end: false,
+            }),
+            ..Trigger::new("Time", TriggerKind::Time(Default::default()))
+        });
+
+        let timeline = Simulator::new()
+            .task("Task", &definition)
+            .run(
+                "2023-01-01T00:00:00".parse().unwrap(),
+                "2023-01-01T03:00:00".parse().unwrap(),
+            )
+            .unwrap();
+        let outcomes: Vec<(String, Outcome)> = timeline
+            .executions
+            .iter()
+            .map(|e| (e.scheduled.to_string(), e.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("2022-12-31T23:00:00".to_string(), Outcome::Started),
+                ("2023-01-01T00:00:00".to_string(), Outcome::Skipped),
+                ("2023-01-01T01:00:00".to_string(), Outcome::Skipped),
+                ("2023-01-01T02:00:00".to_string(), Outcome::Started),
+            ]
+        );
+
+        // An execution which ended before the range is not part of the timeline
+        definition.triggers[0].repetition = None;
+        let scheduled = |from: &str| {
+            Simulator::new()
+                .task("Task", &definition)
+                .run(from.parse().unwrap(), "2023-01-01T03:00:00".parse().unwrap())
+                .unwrap()
+                .executions
+                .iter()
+                .map(|e| e.scheduled.to_string())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(scheduled("2023-01-01T01:59:59"), vec!["2022-12-31T23:00:00"]);
+        assert!(scheduled("2023-01-01T02:00:00").is_empty());
+    }
+
+    #[test]
+    fn durations_out_of_range() {
+        let mut definition = definition(InstancesPolicy::Parallel);
+        definition.settings.execution_time_limit = Some(Duration {
+            seconds: Some(usize::MAX),
+            ..Default::default()
+        });
+        let result = Simulator::new().task("Task", &definition).run(
+            "2023-01-01T00:00:00".parse().unwrap(),
+            "2023-01-02T00:00:00".parse().unwrap(),
+        );
+        assert!(matches!(result, Err(Error::Conversion(_))));
+    }
+}
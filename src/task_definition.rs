@@ -0,0 +1,1076 @@
+use crate::calendar::{DaysOfWeek, Months, WeeksOfMonth};
+use crate::datetime::Boundary;
+use crate::enums::StateChange;
+use crate::error::ValidationError;
+use crate::schedule_builder::Action;
+use crate::sddl::SecurityDescriptor;
+use crate::settings::{Compatibility, Duration, LogonType, PrincipalSettings, RunLevel, Settings};
+
+/// A platform independent representation of a scheduled task.
+///
+/// The [schedule builders](crate::schedule_builder::ScheduleBuilder) populate a `TaskDefinition`
+/// which is only translated to the Windows Task Scheduler COM objects when the
+/// [Schedule](crate::schedule::Schedule) is registered. This allows a task to be built and inspected
+/// on any platform.
+///
+/// # Example
+/// ```
+/// use planif::com::ComRuntime;
+/// use planif::schedule_builder::{Action, ScheduleBuilder};
+/// use planif::task_definition::TriggerKind;
+///
+/// let com = ComRuntime::new().unwrap();
+/// let schedule = ScheduleBuilder::new(&com).unwrap()
+///     .create_daily()
+///     .trigger("DailyTrigger", true).unwrap()
+///     .days_interval(2).unwrap()
+///     .start_boundary("2007-01-01T08:00:00").unwrap()
+///     .action(Action::new("id", "notepad.exe", "", "")).unwrap()
+///     .build().unwrap();
+///
+/// let definition = schedule.definition();
+/// assert_eq!(definition.triggers.len(), 1);
+/// assert!(matches!(definition.triggers[0].kind, TriggerKind::Daily(_)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskDefinition {
+    /// The registration information of the task.
+    pub registration_info: RegistrationInfo,
+    /// The principal under which the task runs. When `None` the Task Scheduler default is used.
+    pub principal: Option<PrincipalSettings>,
+    /// The settings of the task. Settings left to `None` keep the Task Scheduler default.
+    pub settings: Settings,
+    /// The triggers which start the task.
+    pub triggers: Vec<Trigger>,
+    /// The actions performed when the task runs.
+    pub actions: Vec<Action>,
+}
+
+impl TaskDefinition {
+    /// Creates an empty task definition.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks the values which the Task Scheduler would reject when the task is registered.
+    ///
+    /// The start and end boundaries of the triggers must be in the format accepted by the Task
+    /// Scheduler (see [Boundary]) and the end boundary must be after the start boundary.
+    ///
+    /// The following durations are checked, an empty duration is only accepted as the repetition
+    /// duration:
+    /// - the repetition interval of a trigger is between 1 minute and 31 days and is not longer
+    ///   than the repetition duration
+    /// - the repetition duration of a trigger is at least 1 minute, unless it is empty
+    /// - the delay and the random delay of a trigger are at most 31 days
+    /// - the execution time limit of a trigger is not empty
+    ///
+    /// The following combinations are checked:
+    /// - a task deleted after it expires has at least one trigger with an end boundary
+    /// - a maintenance task has no calendar or idle trigger, which would start it outside of
+    ///   Automatic Maintenance
+    /// - the declared [compatibility](Settings::compatibility) is not lower than the
+    ///   [minimum compatibility](TaskDefinition::minimum_compatibility) of the task
+    ///
+    /// The principal is checked by [PrincipalSettings::validate] and the settings by
+    /// [Settings::validate].
+    ///
+    /// [Building](crate::schedule_builder::ScheduleBuilder::build) a schedule validates its
+    /// definition.
+    ///
+    /// # Example
+    /// ```
+    /// use planif::settings::Duration;
+    /// use planif::task_definition::{RepetitionPattern, TaskDefinition, Trigger, TriggerKind};
+    ///
+    /// let mut definition = TaskDefinition::new();
+    /// definition.triggers.push(Trigger {
+    ///     repetition: Some(RepetitionPattern {
+    ///         duration: Duration { hours: Some(1), ..Default::default() },
+    ///         interval: Duration { seconds: Some(30), ..Default::default() },
+    ///         stop_at_duration_end: false,
+    ///     }),
+    ///     ..Trigger::new("BootTrigger", TriggerKind::Boot(Default::default()))
+    /// });
+    ///
+    /// let error = definition.validate().unwrap_err();
+    /// assert_eq!(error.field, "triggers[0].repetition.interval");
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        for (i, trigger) in self.triggers.iter().enumerate() {
+            let field = format!("triggers[{}]", i);
+            validate_boundaries(&field, trigger)?;
+            validate_trigger_durations(&field, trigger)?;
+            if let Some(repetition) = &trigger.repetition {
+                validate_repetition(&format!("{}.repetition", field), repetition)?;
+            }
+        }
+        if let Some(principal) = &self.principal {
+            principal.validate()?;
+        }
+        self.validate_settings()
+    }
+
+    fn validate_settings(&self) -> Result<(), ValidationError> {
+        let settings = &self.settings;
+        settings.validate()?;
+
+        if let Some(declared) = settings.compatibility {
+            let (required, reason) = self.compatibility_requirement();
+            if declared < required {
+                return Err(ValidationError {
+                    field: "settings.compatibility".to_string(),
+                    message: format!(
+                        "{:?} is lower than the {:?} compatibility required by {}",
+                        declared, required, reason
+                    ),
+                });
+            }
+        }
+
+        if settings.maintenance_settings.is_some() {
+            let incompatible = self
+                .triggers
+                .iter()
+                .position(|t| t.kind.is_calendar() || t.kind == TriggerKind::Idle);
+            if let Some(i) = incompatible {
+                return Err(ValidationError {
+                    field: format!("triggers[{}]", i),
+                    message: "maintenance tasks are started by Automatic Maintenance and cannot have calendar or idle triggers".to_string(),
+                });
+            }
+        }
+
+        if settings.delete_expired_task_after.is_some()
+            && self.triggers.iter().all(|t| t.end_boundary.is_none())
+        {
+            return Err(ValidationError {
+                field: "settings.delete_expired_task_after".to_string(),
+                message: "requires at least one trigger with an end boundary".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the lowest [compatibility](Settings::compatibility) level which supports the
+    /// features used by the task:
+    /// - AT: a single time trigger and a single exec action
+    /// - Task Scheduler 1.0: time, logon and boot triggers and a single exec action
+    /// - Task Scheduler 2.0: the other triggers, COM handler actions and several actions, the
+    ///   delays, value queries, end boundaries, execution time limits and repetitions of the
+    ///   triggers, the highest run level and the S4U and group logon types of the principal, the
+    ///   `delete_expired_task_after`, `multiple_instances_policy`, `network_settings`,
+    ///   `restart_count`, `restart_interval` and `start_when_available` settings
+    /// - Task Scheduler 2.1: the process token SID type and the required privileges of the
+    ///   principal, the `disallow_start_on_remote_app_session` and
+    ///   `use_unified_scheduling_engine` settings
+    /// - Task Scheduler 2.2: the `volatile` and `maintenance_settings` settings
+    /// - Task Scheduler 2.3: no feature modelled by planif requires it, the level is never
+    ///   returned but a task declaring it may use every feature
+    ///
+    /// # Example
+    /// ```
+    /// use planif::settings::{Compatibility, Duration, MaintenanceSettings};
+    /// use planif::task_definition::{TaskDefinition, Trigger, TriggerKind};
+    ///
+    /// let mut definition = TaskDefinition::new();
+    /// definition.triggers.push(Trigger::new("Boot", TriggerKind::Boot(Default::default())));
+    /// assert_eq!(definition.minimum_compatibility(), Compatibility::V1);
+    ///
+    /// let period = Duration { days: Some(1), ..Default::default() };
+    /// definition.settings.maintenance_settings = Some(MaintenanceSettings::new(period));
+    /// assert_eq!(definition.minimum_compatibility(), Compatibility::V2_2);
+    ///
+    /// // A declared compatibility lower than the minimum is rejected
+    /// definition.settings.compatibility = Some(Compatibility::V2);
+    /// assert_eq!(definition.validate().unwrap_err().field, "settings.compatibility");
+    /// ```
+    pub fn minimum_compatibility(&self) -> Compatibility {
+        self.compatibility_requirement().0
+    }
+
+    // The minimum compatibility and the first feature which requires it.
+    fn compatibility_requirement(&self) -> (Compatibility, String) {
+        // Keeps the first feature of the highest level
+        self.compatibility_requirements().into_iter().fold(
+            (Compatibility::AT, "the task".to_string()),
+            |required, requirement| match requirement.0 > required.0 {
+                true => requirement,
+                false => required,
+            },
+        )
+    }
+
+    // The features of the task which require a compatibility level above AT.
+    fn compatibility_requirements(&self) -> Vec<(Compatibility, String)> {
+        let mut requirements = Vec::new();
+
+        if self.triggers.len() > 1 {
+            requirements.push((Compatibility::V1, "more than one trigger".to_string()));
+        }
+        for (i, trigger) in self.triggers.iter().enumerate() {
+            let (level, name) = match trigger.kind {
+                TriggerKind::Time(_) => (Compatibility::AT, "time"),
+                TriggerKind::Boot(_) => (Compatibility::V1, "boot"),
+                TriggerKind::Logon(_) => (Compatibility::V1, "logon"),
+                TriggerKind::Daily(_) => (Compatibility::V2, "daily"),
+                TriggerKind::Event(_) => (Compatibility::V2, "event"),
+                TriggerKind::Idle => (Compatibility::V2, "idle"),
+                TriggerKind::Monthly(_) => (Compatibility::V2, "monthly"),
+                TriggerKind::MonthlyDOW(_) => (Compatibility::V2, "monthly day-of-week"),
+                TriggerKind::Registration(_) => (Compatibility::V2, "registration"),
+                TriggerKind::SessionStateChange(_) => (Compatibility::V2, "session state change"),
+                TriggerKind::Weekly(_) => (Compatibility::V2, "weekly"),
+            };
+            requirements.push((level, format!("the {} trigger triggers[{}]", name, i)));
+
+            let value_queries = match &trigger.kind {
+                TriggerKind::Event(event) => !event.value_queries.is_empty(),
+                _ => false,
+            };
+            let features = [
+                ("delay", trigger.kind.delay().is_some()),
+                ("random_delay", trigger.kind.random_delay().is_some()),
+                ("value_queries", value_queries),
+                ("end_boundary", trigger.end_boundary.is_some()),
+                ("execution_time_limit", trigger.execution_time_limit.is_some()),
+                ("repetition", trigger.repetition.is_some()),
+            ];
+            for (name, is_set) in features {
+                if is_set {
+                    requirements.push((Compatibility::V2, format!("triggers[{}].{}", i, name)));
+                }
+            }
+        }
+
+        if self.actions.len() > 1 {
+            requirements.push((Compatibility::V2, "more than one action".to_string()));
+        }
+        for (i, action) in self.actions.iter().enumerate() {
+            if let Action::ComHandler(_) = action {
+                requirements.push((
+                    Compatibility::V2,
+                    format!("the COM handler action actions[{}]", i),
+                ));
+            }
+        }
+
+        if let Some(principal) = &self.principal {
+            if principal.run_level == RunLevel::Highest {
+                requirements.push((Compatibility::V2, "principal.run_level".to_string()));
+            }
+            if matches!(principal.logon_type, LogonType::S4U | LogonType::Group) {
+                requirements.push((Compatibility::V2, "principal.logon_type".to_string()));
+            }
+            if principal.process_token_sid_type.is_some() {
+                requirements.push((
+                    Compatibility::V2_1,
+                    "principal.process_token_sid_type".to_string(),
+                ));
+            }
+            if !principal.required_privileges.is_empty() {
+                requirements.push((
+                    Compatibility::V2_1,
+                    "principal.required_privileges".to_string(),
+                ));
+            }
+        }
+
+        let settings = [
+            (
+                Compatibility::V2,
+                "delete_expired_task_after",
+                self.settings.delete_expired_task_after.is_some(),
+            ),
+            (
+                Compatibility::V2,
+                "multiple_instances_policy",
+                self.settings.multiple_instances_policy.is_some(),
+            ),
+            (Compatibility::V2, "network_settings", self.settings.network_settings.is_some()),
+            (Compatibility::V2, "restart_count", self.settings.restart_count.is_some()),
+            (Compatibility::V2, "restart_interval", self.settings.restart_interval.is_some()),
+            (
+                Compatibility::V2,
+                "start_when_available",
+                self.settings.start_when_available.is_some(),
+            ),
+            (
+                Compatibility::V2_1,
+                "disallow_start_on_remote_app_session",
+                self.settings.disallow_start_on_remote_app_session.is_some(),
+            ),
+            (
+                Compatibility::V2_1,
+                "use_unified_scheduling_engine",
+                self.settings.use_unified_scheduling_engine.is_some(),
+            ),
+            (
+                Compatibility::V2_2,
+                "maintenance_settings",
+                self.settings.maintenance_settings.is_some(),
+            ),
+            (Compatibility::V2_2, "volatile", self.settings.volatile.is_some()),
+        ];
+        for (level, name, is_set) in settings {
+            if is_set {
+                requirements.push((level, format!("settings.{}", name)));
+            }
+        }
+
+        requirements
+    }
+}
+
+pub(crate) const MINUTE: u64 = 60;
+pub(crate) const DAY: u64 = 24 * 60 * MINUTE;
+
+fn validate_boundaries(field: &str, trigger: &Trigger) -> Result<(), ValidationError> {
+    let parse = |name: &str, boundary: &Option<String>| match boundary {
+        Some(boundary) => boundary
+            .parse::<Boundary>()
+            .map(Some)
+            .map_err(|e| ValidationError {
+                field: format!("{}.{}", field, name),
+                message: e.to_string(),
+            }),
+        None => Ok(None),
+    };
+
+    let start = parse("start_boundary", &trigger.start_boundary)?;
+    let end = parse("end_boundary", &trigger.end_boundary)?;
+    if let (Some(start), Some(end)) = (start, end) {
+        if !end.is_after(&start) {
+            return Err(ValidationError {
+                field: format!("{}.end_boundary", field),
+                message: format!(
+                    "must be after the start boundary {}, found {}",
+                    start, end
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn validate_trigger_durations(field: &str, trigger: &Trigger) -> Result<(), ValidationError> {
+    if let Some(delay) = trigger.kind.delay() {
+        check_duration(&format!("{}.delay", field), delay, 0, Some(31 * DAY))?;
+    }
+    if let Some(delay) = trigger.kind.random_delay() {
+        check_duration(&format!("{}.random_delay", field), delay, 0, Some(31 * DAY))?;
+    }
+    if let Some(limit) = &trigger.execution_time_limit {
+        check_duration(&format!("{}.execution_time_limit", field), limit, 0, None)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_repetition(field: &str, repetition: &RepetitionPattern) -> Result<(), ValidationError> {
+    let interval_field = format!("{}.interval", field);
+    check_duration(&interval_field, &repetition.interval, MINUTE, Some(31 * DAY))?;
+
+    // An empty duration repeats the task indefinitely
+    let (_, duration_max) = repetition.duration.seconds_range();
+    if duration_max == 0 {
+        return Ok(());
+    }
+    check_duration(&format!("{}.duration", field), &repetition.duration, MINUTE, None)?;
+
+    let (interval_min, _) = repetition.interval.seconds_range();
+    if interval_min > duration_max {
+        return Err(ValidationError {
+            field: interval_field,
+            message: format!(
+                "the interval {} must not be longer than the repetition duration {}",
+                repetition.interval, repetition.duration
+            ),
+        });
+    }
+    Ok(())
+}
+
+// Checks that the duration is not empty and is between `min` and `max` seconds, inclusively.
+pub(crate) fn check_duration(
+    field: &str,
+    duration: &Duration,
+    min: u64,
+    max: Option<u64>,
+) -> Result<(), ValidationError> {
+    if duration.is_empty() {
+        return Err(ValidationError {
+            field: field.to_string(),
+            message: "must not be empty".to_string(),
+        });
+    }
+
+    let (shortest, longest) = duration.seconds_range();
+    let is_valid = shortest >= min && max.map_or(true, |max| longest <= max);
+    if is_valid {
+        return Ok(());
+    }
+
+    let limits = match max {
+        Some(max) if min == 0 => format!("at most {}", describe(max)),
+        Some(max) => format!("between {} and {}", describe(min), describe(max)),
+        None => format!("at least {}", describe(min)),
+    };
+    Err(ValidationError {
+        field: field.to_string(),
+        message: format!("must be {}, found '{}'", limits, duration),
+    })
+}
+
+fn describe(seconds: u64) -> String {
+    match seconds {
+        s if s % DAY == 0 => format!("{} days", s / DAY),
+        MINUTE => "1 minute".to_string(),
+        s => format!("{} seconds", s),
+    }
+}
+
+/// Administrative information about a task.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/registrationinfo>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistrationInfo {
+    /// The author of the task.
+    pub author: Option<String>,
+    /// The date and time when the task is registered.
+    pub date: Option<String>,
+    /// The description of the task.
+    pub description: Option<String>,
+    /// Any additional documentation for the task.
+    pub documentation: Option<String>,
+    /// Who may read, run or change the task, it is applied when the task is registered.
+    pub security_descriptor: Option<SecurityDescriptor>,
+    /// Where the task originated from, for example a component, service or application name.
+    pub source: Option<String>,
+    /// The URI of the task.
+    pub uri: Option<String>,
+    /// The version number of the task.
+    pub version: Option<String>,
+}
+
+/// A trigger and the settings common to every trigger type.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/trigger>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trigger {
+    /// The identifier of the trigger.
+    pub id: String,
+    /// Whether the trigger is enabled.
+    pub enabled: bool,
+    /// The date and time when the trigger is activated.
+    pub start_boundary: Option<String>,
+    /// The date and time when the trigger is deactivated.
+    pub end_boundary: Option<String>,
+    /// The maximum amount of time that the task launched by the trigger is allowed to run.
+    pub execution_time_limit: Option<Duration>,
+    /// How often the task is run and how long the repetition pattern is repeated after the task is started.
+    pub repetition: Option<RepetitionPattern>,
+    /// The type of the trigger and its type specific settings.
+    pub kind: TriggerKind,
+}
+
+impl Trigger {
+    /// Creates an enabled trigger of the given kind with no boundaries, time limit or repetition.
+    pub fn new(id: &str, kind: TriggerKind) -> Self {
+        Self {
+            id: id.to_string(),
+            enabled: true,
+            start_boundary: None,
+            end_boundary: None,
+            execution_time_limit: None,
+            repetition: None,
+            kind,
+        }
+    }
+}
+
+/// Defines how often a task is run and how long the repetition pattern is repeated.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/repetitionpattern>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepetitionPattern {
+    /// How long the pattern is repeated.
+    pub duration: Duration,
+    /// The amount of time between each restart of the task.
+    pub interval: Duration,
+    /// Whether a running instance of the task is stopped at the end of the repetition duration.
+    pub stop_at_duration_end: bool,
+}
+
+/// The type specific settings of a [Trigger].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriggerKind {
+    /// Starts the task when the system is booted.
+    Boot(BootTrigger),
+    /// Starts the task based on a daily schedule.
+    Daily(DailyTrigger),
+    /// Starts the task when a system event occurs.
+    Event(EventTrigger),
+    /// Starts the task when the computer goes into an idle state.
+    Idle,
+    /// Starts the task when a user logs on.
+    Logon(LogonTrigger),
+    /// Starts the task based on a monthly schedule.
+    Monthly(MonthlyTrigger),
+    /// Starts the task based on a monthly day-of-week schedule.
+    MonthlyDOW(MonthlyDOWTrigger),
+    /// Starts the task when the task is registered or updated.
+    Registration(RegistrationTrigger),
+    /// Starts the task when a user session is connected, disconnected, locked or unlocked.
+    SessionStateChange(SessionStateChangeTrigger),
+    /// Starts the task at a specific time of day.
+    Time(TimeTrigger),
+    /// Starts the task based on a weekly schedule.
+    Weekly(WeeklyTrigger),
+}
+
+impl TriggerKind {
+    // The delay of the triggers started by an event.
+    pub(crate) fn delay(&self) -> Option<&Duration> {
+        match self {
+            TriggerKind::Boot(boot) => boot.delay.as_ref(),
+            TriggerKind::Event(event) => event.delay.as_ref(),
+            TriggerKind::Logon(logon) => logon.delay.as_ref(),
+            TriggerKind::Registration(registration) => registration.delay.as_ref(),
+            TriggerKind::SessionStateChange(session) => session.delay.as_ref(),
+            _ => None,
+        }
+    }
+
+    // The random delay of the calendar triggers.
+    pub(crate) fn random_delay(&self) -> Option<&Duration> {
+        match self {
+            TriggerKind::Daily(daily) => daily.random_delay.as_ref(),
+            TriggerKind::Monthly(monthly) => monthly.random_delay.as_ref(),
+            TriggerKind::MonthlyDOW(monthly_dow) => monthly_dow.random_delay.as_ref(),
+            TriggerKind::Time(time) => time.random_delay.as_ref(),
+            TriggerKind::Weekly(weekly) => weekly.random_delay.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the Task Scheduler requires a start boundary for this kind of trigger, the calendar
+    /// triggers and the event trigger.
+    pub fn requires_start_boundary(&self) -> bool {
+        matches!(
+            self,
+            TriggerKind::Daily(_)
+                | TriggerKind::Event(_)
+                | TriggerKind::Monthly(_)
+                | TriggerKind::MonthlyDOW(_)
+                | TriggerKind::Time(_)
+                | TriggerKind::Weekly(_)
+        )
+    }
+
+    /// Whether the trigger starts the task on a calendar schedule, rather than when an event occurs.
+    /// The [occurrences](Trigger::occurrences) of calendar triggers can be computed.
+    pub fn is_calendar(&self) -> bool {
+        matches!(
+            self,
+            TriggerKind::Daily(_)
+                | TriggerKind::Monthly(_)
+                | TriggerKind::MonthlyDOW(_)
+                | TriggerKind::Time(_)
+                | TriggerKind::Weekly(_)
+        )
+    }
+}
+
+/// Settings of a boot trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/boottrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BootTrigger {
+    /// The amount of time between when the system is booted and when the task is started.
+    pub delay: Option<Duration>,
+}
+
+/// Settings of a daily trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/dailytrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DailyTrigger {
+    /// The interval between the days in the schedule.
+    pub days_interval: Option<i16>,
+    /// The delay time that is randomly added to the start time of the trigger.
+    pub random_delay: Option<Duration>,
+}
+
+/// Settings of an event trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/eventtrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventTrigger {
+    /// The amount of time between when the event occurs and when the task is started.
+    pub delay: Option<Duration>,
+    /// The query string that identifies the event that fires the trigger.
+    pub subscription: Option<String>,
+    /// Named XPath queries whose values are passed to the task.
+    pub value_queries: Vec<(String, String)>,
+}
+
+/// Settings of a logon trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/logontrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogonTrigger {
+    /// The amount of time between when the user logs on and when the task is started.
+    pub delay: Option<Duration>,
+    /// The identifier of the user. When `None`, the task is started when any user logs on.
+    pub user_id: Option<String>,
+}
+
+/// Settings of a monthly trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/monthlytrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonthlyTrigger {
+    /// Bitwise mask of the days of the month during which the task runs.
+    pub days_of_month: Option<i32>,
+    /// The months of the year during which the task runs.
+    pub months_of_year: Option<Months>,
+    /// The delay time that is randomly added to the start time of the trigger.
+    pub random_delay: Option<Duration>,
+    /// Whether the task runs on the last day of the month.
+    pub run_on_last_day: Option<bool>,
+}
+
+/// Settings of a monthly day-of-week trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/monthlydowtrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonthlyDOWTrigger {
+    /// The days of the week during which the task runs.
+    pub days_of_week: Option<DaysOfWeek>,
+    /// The months of the year during which the task runs.
+    pub months_of_year: Option<Months>,
+    /// The delay time that is randomly added to the start time of the trigger.
+    pub random_delay: Option<Duration>,
+    /// Whether the task runs on the last week of the month.
+    pub run_on_last_week: Option<bool>,
+    /// The weeks of the month during which the task runs.
+    pub weeks_of_month: Option<WeeksOfMonth>,
+}
+
+/// Settings of a registration trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/registrationtrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistrationTrigger {
+    /// The amount of time between when the task is registered and when the task is started.
+    pub delay: Option<Duration>,
+}
+
+/// Settings of a session state change trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/sessionstatechangetrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionStateChangeTrigger {
+    /// The amount of time between when the session state changes and when the task is started.
+    pub delay: Option<Duration>,
+    /// The kind of session state change that starts the task.
+    pub state_change: Option<StateChange>,
+    /// The user for the session. When `None`, the task is started for the sessions of any user.
+    pub user_id: Option<String>,
+}
+
+/// Settings of a time trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/timetrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimeTrigger {
+    /// The delay time that is randomly added to the start time of the trigger.
+    pub random_delay: Option<Duration>,
+}
+
+/// Settings of a weekly trigger.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/weeklytrigger>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WeeklyTrigger {
+    /// The days of the week during which the task runs.
+    pub days_of_week: Option<DaysOfWeek>,
+    /// The delay time that is randomly added to the start time of the trigger.
+    pub random_delay: Option<Duration>,
+    /// The interval between the weeks in the schedule.
+    pub weeks_interval: Option<i16>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{
+        InstancesPolicy, MaintenanceSettings, NetworkSettings, Privilege, PrincipalBuilder,
+        ProcessTokenSidType,
+    };
+
+    fn minutes(minutes: usize) -> Duration {
+        Duration {
+            minutes: Some(minutes),
+            ..Default::default()
+        }
+    }
+
+    // A task compatible with the AT command, a single time trigger and a single exec action.
+    fn at() -> TaskDefinition {
+        let mut definition = TaskDefinition::new();
+        definition.triggers.push(Trigger {
+            start_boundary: Some("2023-01-01T08:00:00".to_string()),
+            ..Trigger::new("Time", TriggerKind::Time(Default::default()))
+        });
+        definition.actions.push(Action::new("", "notepad.exe", "", ""));
+        definition
+    }
+
+    fn requirement(definition: &TaskDefinition) -> (Compatibility, String) {
+        let (level, reason) = definition.compatibility_requirement();
+        assert_eq!(definition.minimum_compatibility(), level);
+        (level, reason)
+    }
+
+    fn requires(level: Compatibility, reason: &str) -> (Compatibility, String) {
+        (level, reason.to_string())
+    }
+
+    fn trigger(kind: TriggerKind) -> TaskDefinition {
+        let mut definition = at();
+        definition.triggers[0].kind = kind;
+        definition
+    }
+
+    #[test]
+    fn at_compatibility() {
+        assert_eq!(requirement(&at()), requires(Compatibility::AT, "the task"));
+        assert_eq!(
+            requirement(&TaskDefinition::new()),
+            requires(Compatibility::AT, "the task")
+        );
+    }
+
+    #[test]
+    fn more_than_one_trigger() {
+        let mut definition = at();
+        definition.triggers.push(definition.triggers[0].clone());
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V1, "more than one trigger")
+        );
+    }
+
+    #[test]
+    fn trigger_kinds() {
+        let kinds = [
+            (TriggerKind::Boot(Default::default()), Compatibility::V1, "boot"),
+            (TriggerKind::Logon(Default::default()), Compatibility::V1, "logon"),
+            (TriggerKind::Daily(Default::default()), Compatibility::V2, "daily"),
+            (TriggerKind::Event(Default::default()), Compatibility::V2, "event"),
+            (TriggerKind::Idle, Compatibility::V2, "idle"),
+            (TriggerKind::Monthly(Default::default()), Compatibility::V2, "monthly"),
+            (
+                TriggerKind::MonthlyDOW(Default::default()),
+                Compatibility::V2,
+                "monthly day-of-week",
+            ),
+            (TriggerKind::Registration(Default::default()), Compatibility::V2, "registration"),
+            (
+                TriggerKind::SessionStateChange(Default::default()),
+                Compatibility::V2,
+                "session state change",
+            ),
+            (TriggerKind::Weekly(Default::default()), Compatibility::V2, "weekly"),
+        ];
+        for (kind, level, name) in kinds {
+            let reason = format!("the {} trigger triggers[0]", name);
+            assert_eq!(requirement(&trigger(kind)), requires(level, &reason));
+        }
+    }
+
+    #[test]
+    fn trigger_delay() {
+        let definition = trigger(TriggerKind::Boot(BootTrigger {
+            delay: Some(minutes(5)),
+        }));
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2, "triggers[0].delay")
+        );
+    }
+
+    #[test]
+    fn trigger_random_delay() {
+        let definition = trigger(TriggerKind::Time(TimeTrigger {
+            random_delay: Some(minutes(5)),
+        }));
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2, "triggers[0].random_delay")
+        );
+    }
+
+    #[test]
+    fn event_value_queries() {
+        let mut definition = trigger(TriggerKind::Event(EventTrigger {
+            value_queries: vec![("id".to_string(), "Event/System/EventID".to_string())],
+            ..Default::default()
+        }));
+        // The first feature of the highest level is reported
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2, "the event trigger triggers[0]")
+        );
+
+        definition.settings.compatibility = Some(Compatibility::V1);
+        let error = definition.validate().unwrap_err();
+        assert_eq!(error.field, "settings.compatibility");
+
+        assert!(definition
+            .compatibility_requirements()
+            .contains(&requires(Compatibility::V2, "triggers[0].value_queries")));
+    }
+
+    #[test]
+    fn trigger_end_boundary() {
+        let mut definition = at();
+        definition.triggers[0].end_boundary = Some("2023-02-01T08:00:00".to_string());
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2, "triggers[0].end_boundary")
+        );
+    }
+
+    #[test]
+    fn trigger_execution_time_limit() {
+        let mut definition = at();
+        definition.triggers[0].execution_time_limit = Some(minutes(30));
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2, "triggers[0].execution_time_limit")
+        );
+    }
+
+    #[test]
+    fn trigger_repetition() {
+        let mut definition = at();
+        definition.triggers[0].repetition = Some(RepetitionPattern {
+            duration: minutes(60),
+            interval: minutes(5),
+            stop_at_duration_end: false,
+        });
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2, "triggers[0].repetition")
+        );
+    }
+
+    #[test]
+    fn more_than_one_action() {
+        let mut definition = at();
+        definition.actions.push(definition.actions[0].clone());
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2, "more than one action")
+        );
+    }
+
+    #[test]
+    fn com_handler_action() {
+        let mut definition = at();
+        definition.actions[0] =
+            Action::com_handler("", "{0BE8D5D8-C4DF-4DE6-A4E7-FEE01D6F3C49}", "").unwrap();
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2, "the COM handler action actions[0]")
+        );
+    }
+
+    #[test]
+    fn principal_run_level() {
+        let mut definition = at();
+        let mut principal = PrincipalBuilder::user("User").build().unwrap();
+        principal.run_level = RunLevel::Highest;
+        definition.principal = Some(principal);
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2, "principal.run_level")
+        );
+    }
+
+    #[test]
+    fn principal_logon_type() {
+        let principals = [
+            PrincipalBuilder::user("User").run_whether_logged_on(false),
+            PrincipalBuilder::group("Users"),
+        ];
+        for principal in principals {
+            let principal = principal.build().unwrap();
+            assert!(matches!(principal.logon_type, LogonType::S4U | LogonType::Group));
+            let mut definition = at();
+            definition.principal = Some(principal);
+            assert_eq!(
+                requirement(&definition),
+                requires(Compatibility::V2, "principal.logon_type")
+            );
+        }
+
+        let mut definition = at();
+        definition.principal = Some(PrincipalBuilder::user("User").build().unwrap());
+        assert_eq!(requirement(&definition).0, Compatibility::AT);
+    }
+
+    #[test]
+    fn principal_process_token_sid_type() {
+        let mut definition = at();
+        let mut principal = PrincipalBuilder::local_service().build().unwrap();
+        principal.process_token_sid_type = Some(ProcessTokenSidType::Unrestricted);
+        definition.principal = Some(principal);
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2_1, "principal.process_token_sid_type")
+        );
+    }
+
+    #[test]
+    fn principal_required_privileges() {
+        let mut definition = at();
+        let mut principal = PrincipalBuilder::local_service().build().unwrap();
+        principal.required_privileges = vec![Privilege::Backup];
+        definition.principal = Some(principal);
+        assert_eq!(
+            requirement(&definition),
+            requires(Compatibility::V2_1, "principal.required_privileges")
+        );
+    }
+
+    #[test]
+    fn settings() {
+        let settings = [
+            (
+                Compatibility::V2,
+                "settings.delete_expired_task_after",
+                Settings {
+                    delete_expired_task_after: Some(minutes(5)),
+                    ..Default::default()
+                },
+            ),
+            (
+                Compatibility::V2,
+                "settings.multiple_instances_policy",
+                Settings {
+                    multiple_instances_policy: Some(InstancesPolicy::Queue),
+                    ..Default::default()
+                },
+            ),
+            (
+                Compatibility::V2,
+                "settings.network_settings",
+                Settings {
+                    network_settings: Some(NetworkSettings {
+                        id: "{F3E8B5A0-0000-0000-0000-000000000000}".to_string(),
+                        name: "Office".to_string(),
+                    }),
+                    ..Default::default()
+                },
+            ),
+            (
+                Compatibility::V2,
+                "settings.restart_count",
+                Settings {
+                    restart_count: Some(3),
+                    restart_interval: Some(minutes(5)),
+                    ..Default::default()
+                },
+            ),
+            (
+                Compatibility::V2,
+                "settings.restart_interval",
+                Settings {
+                    restart_interval: Some(minutes(5)),
+                    ..Default::default()
+                },
+            ),
+            (
+                Compatibility::V2,
+                "settings.start_when_available",
+                Settings {
+                    start_when_available: Some(true),
+                    ..Default::default()
+                },
+            ),
+            (
+                Compatibility::V2_1,
+                "settings.disallow_start_on_remote_app_session",
+                Settings {
+                    disallow_start_on_remote_app_session: Some(true),
+                    ..Default::default()
+                },
+            ),
+            (
+                Compatibility::V2_1,
+                "settings.use_unified_scheduling_engine",
+                Settings {
+                    use_unified_scheduling_engine: Some(true),
+                    ..Default::default()
+                },
+            ),
+            (
+                Compatibility::V2_2,
+                "settings.maintenance_settings",
+                Settings {
+                    maintenance_settings: Some(MaintenanceSettings::new(Duration {
+                        days: Some(1),
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                },
+            ),
+            (
+                Compatibility::V2_2,
+                "settings.volatile",
+                Settings {
+                    volatile: Some(false),
+                    ..Default::default()
+                },
+            ),
+        ];
+        for (level, reason, settings) in settings {
+            let mut definition = at();
+            definition.settings = settings;
+            assert_eq!(requirement(&definition), requires(level, reason));
+        }
+    }
+
+    #[test]
+    fn v2_3_compatibility() {
+        let mut definition = trigger(TriggerKind::Boot(Default::default()));
+        definition.settings.volatile = Some(true);
+        definition.settings.compatibility = Some(Compatibility::V2_3);
+        assert_eq!(requirement(&definition).0, Compatibility::V2_2);
+        assert!(definition.validate().is_ok());
+    }
+
+    #[test]
+    fn declared_compatibility_is_enforced() {
+        let mut definition = trigger(TriggerKind::Boot(Default::default()));
+        let mut principal = PrincipalBuilder::user("User").build().unwrap();
+        principal.run_level = RunLevel::Highest;
+        definition.principal = Some(principal);
+        definition.settings.restart_count = Some(3);
+        definition.settings.restart_interval = Some(minutes(5));
+        definition.settings.compatibility = Some(Compatibility::V1);
+
+        let error = definition.validate().unwrap_err();
+        assert_eq!(error.field, "settings.compatibility");
+        assert_eq!(
+            error.message,
+            "V1 is lower than the V2 compatibility required by principal.run_level"
+        );
+
+        definition.settings.compatibility = Some(Compatibility::V2);
+        assert!(definition.validate().is_ok());
+    }
+}
@@ -0,0 +1,642 @@
+// Translation of a `TaskDefinition` to the Windows Task Scheduler COM objects.
+// Only called on Windows, but compiled everywhere so it is type checked on every platform.
+#![cfg_attr(not(windows), allow(dead_code))]
+use std::mem::ManuallyDrop;
+use windows::core::{ComInterface, BSTR};
+use windows::Win32::Foundation::VARIANT_BOOL;
+use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_ALL, VARIANT, VT_BSTR};
+use windows::Win32::System::Ole::VariantClear;
+use windows::Win32::System::TaskScheduler::{
+    IAction, IActionCollection, IBootTrigger, IComHandlerAction, IDailyTrigger, IEventTrigger, IExecAction, IIdleSettings,
+    ILogonTrigger, IMonthlyDOWTrigger, IMonthlyTrigger, INetworkSettings, IPrincipal2,
+    IRegistrationTrigger, ISessionStateChangeTrigger, ITaskDefinition, ITaskFolder, ITaskService, ITaskSettings,
+    ITaskSettings2, ITaskSettings3,
+    ITimeTrigger, ITrigger, IWeeklyTrigger, TaskScheduler, TASK_ACTION_COM_HANDLER, TASK_ACTION_EXEC,
+    TASK_LOGON_TYPE, TASK_PROCESSTOKENSID_TYPE, TASK_RUNLEVEL_TYPE,
+    TASK_SESSION_STATE_CHANGE_TYPE, TASK_TRIGGER_BOOT, TASK_TRIGGER_DAILY, TASK_TRIGGER_EVENT,
+    TASK_TRIGGER_IDLE, TASK_TRIGGER_LOGON, TASK_TRIGGER_MONTHLY, TASK_TRIGGER_MONTHLYDOW,
+    TASK_TRIGGER_REGISTRATION, TASK_TRIGGER_SESSION_STATE_CHANGE, TASK_TRIGGER_TIME,
+    TASK_TRIGGER_TYPE2, TASK_TRIGGER_WEEKLY,
+};
+
+use crate::credentials::{zeroize, Credentials};
+use crate::error::{Error, TaskSchedulerError};
+use crate::schedule_builder::Action;
+use crate::sddl::SecurityDescriptor;
+use crate::settings::{LogonType, PrincipalSettings, Settings};
+use crate::task_definition::{TaskDefinition, Trigger, TriggerKind};
+
+/// Translates the task definition to the Task Scheduler's COM objects and registers it in `folder`.
+pub(crate) unsafe fn register(
+    definition: &TaskDefinition,
+    folder: &str,
+    folder_sddl: Option<&SecurityDescriptor>,
+    task_name: &str,
+    flags: i32,
+    credentials: Option<&Credentials>,
+) -> Result<(), Error> {
+    let task_service: ITaskService = CoCreateInstance(&TaskScheduler, None, CLSCTX_ALL)
+        .operation("CoCreateInstance(TaskScheduler)")?;
+    task_service
+        .Connect(
+            VARIANT::default(),
+            VARIANT::default(),
+            VARIANT::default(),
+            VARIANT::default(),
+        )
+        .operation("ITaskService::Connect")?;
+
+    // check if folder exists, if not make it
+    let task_folder: ITaskFolder = match task_service.GetFolder(&BSTR::from(folder)) {
+        Ok(x) => x,
+        Err(_) => {
+            let mut sddl = folder_sddl
+                .map(|sddl| variant(&sddl.to_string()))
+                .unwrap_or_default();
+            let created = task_service
+                .GetFolder(&BSTR::from("\\"))
+                .and_then(|root| {
+                    root.CreateFolder(&BSTR::from(folder.trim_start_matches('\\')), sddl.clone())
+                })
+                .operation("ITaskFolder::CreateFolder");
+            VariantClear(&mut sddl).operation("VariantClear")?;
+            created?
+        }
+    };
+
+    let task_definition: ITaskDefinition =
+        task_service.NewTask(0).operation("ITaskService::NewTask")?;
+    apply_definition(&task_definition, definition)?;
+
+    let (logon_type, account) = account(definition, credentials);
+    let mut user_id = account.map(variant).unwrap_or_default();
+    let mut password = credentials
+        .and_then(|c| c.password())
+        .map(|p| variant(p.expose()))
+        .unwrap_or_default();
+    let mut sddl = definition
+        .registration_info
+        .security_descriptor
+        .as_ref()
+        .map(|sddl| variant(&sddl.to_string()))
+        .unwrap_or_default();
+    let registered = task_folder
+        .RegisterTaskDefinition(
+            &BSTR::from(task_name),
+            &task_definition,
+            flags,
+            user_id.clone(),
+            password.clone(),
+            TASK_LOGON_TYPE(logon_type as i32),
+            sddl.clone(),
+        )
+        .operation("ITaskFolder::RegisterTaskDefinition");
+
+    // Overwrites the password before freeing it, the variants are copied without their strings
+    // when the call is made.
+    if password.Anonymous.Anonymous.vt == VT_BSTR {
+        // The string is written through the pointer allocated by `SysAllocStringLen`, which the
+        // transparent BSTR holds, rather than through the shared slice returned by `as_wide()`.
+        let bstr: &BSTR = &password.Anonymous.Anonymous.Anonymous.bstrVal;
+        let raw = *(bstr as *const BSTR as *const *mut u16);
+        zeroize(raw, bstr.len());
+    }
+    VariantClear(&mut user_id).operation("VariantClear")?;
+    VariantClear(&mut password).operation("VariantClear")?;
+    VariantClear(&mut sddl).operation("VariantClear")?;
+    registered?;
+
+    Ok(())
+}
+
+// The logon type and the user or group the task is registered with. Without credentials the
+// principal of the task is used, and the interactive token of the current user when the task has
+// no principal.
+fn account<'a>(
+    definition: &'a TaskDefinition,
+    credentials: Option<&'a Credentials>,
+) -> (LogonType, Option<&'a str>) {
+    match (credentials, &definition.principal) {
+        (Some(credentials), _) => (credentials.logon_type(), Some(credentials.user_id())),
+        (None, Some(principal)) => (
+            principal.logon_type,
+            principal.user_id.as_deref().or(principal.group_id.as_deref()),
+        ),
+        (None, None) => (LogonType::InteractiveToken, None),
+    }
+}
+
+// A VARIANT holding a BSTR, which must be freed with `VariantClear`. The intermediate UTF-16
+// buffer is overwritten with zeros as it may hold a password, it is allocated once since a UTF-8
+// string never has fewer bytes than UTF-16 code units.
+fn variant(value: &str) -> VARIANT {
+    let mut wide = Vec::with_capacity(value.len());
+    wide.extend(value.encode_utf16());
+    let bstr = BSTR::from_wide(&wide).expect("the BSTR is allocated");
+    unsafe { zeroize(wide.as_mut_ptr(), wide.len()) };
+
+    let mut variant = VARIANT::default();
+    unsafe {
+        let inner = &mut *variant.Anonymous.Anonymous;
+        inner.vt = VT_BSTR;
+        inner.Anonymous.bstrVal = ManuallyDrop::new(bstr);
+    }
+    variant
+}
+
+// Attaches the failing operation to the errors returned by the COM.
+trait Operation<T> {
+    fn operation(self, operation: &str) -> Result<T, Error>;
+}
+
+impl<T> Operation<T> for windows::core::Result<T> {
+    fn operation(self, operation: &str) -> Result<T, Error> {
+        self.map_err(|e| {
+            Error::TaskScheduler(TaskSchedulerError::new(
+                operation,
+                e.code().0,
+                &e.message().to_string(),
+            ))
+        })
+    }
+}
+
+unsafe fn apply_definition(
+    task_definition: &ITaskDefinition,
+    definition: &TaskDefinition,
+) -> Result<(), Error> {
+    apply_registration_info(task_definition, definition).operation("IRegistrationInfo")?;
+
+    if let Some(principal) = &definition.principal {
+        apply_principal(task_definition, principal).operation("IPrincipal")?;
+    }
+
+    apply_settings(task_definition, &definition.settings).operation("ITaskSettings")?;
+
+    let triggers = task_definition
+        .Triggers()
+        .operation("ITaskDefinition::Triggers")?;
+    for trigger in &definition.triggers {
+        triggers
+            .Create(trigger_type(&trigger.kind))
+            .and_then(|i_trigger| apply_trigger(&i_trigger, trigger))
+            .operation(&format!("ITrigger '{}'", trigger.id))?;
+    }
+
+    let actions = task_definition
+        .Actions()
+        .operation("ITaskDefinition::Actions")?;
+    for action in &definition.actions {
+        apply_action(&actions, action).operation(&format!("IAction '{}'", action.id()))?;
+    }
+
+    Ok(())
+}
+
+unsafe fn apply_registration_info(
+    task_definition: &ITaskDefinition,
+    definition: &TaskDefinition,
+) -> windows::core::Result<()> {
+    let registration_info = task_definition.RegistrationInfo()?;
+    if let Some(author) = &definition.registration_info.author {
+        registration_info.SetAuthor(&BSTR::from(author))?;
+    }
+    if let Some(date) = &definition.registration_info.date {
+        registration_info.SetDate(&BSTR::from(date))?;
+    }
+    if let Some(description) = &definition.registration_info.description {
+        registration_info.SetDescription(&BSTR::from(description))?;
+    }
+    if let Some(documentation) = &definition.registration_info.documentation {
+        registration_info.SetDocumentation(&BSTR::from(documentation))?;
+    }
+    if let Some(sddl) = &definition.registration_info.security_descriptor {
+        let mut sddl = variant(&sddl.to_string());
+        let result = registration_info.SetSecurityDescriptor(sddl.clone());
+        VariantClear(&mut sddl)?;
+        result?;
+    }
+    if let Some(source) = &definition.registration_info.source {
+        registration_info.SetSource(&BSTR::from(source))?;
+    }
+    if let Some(uri) = &definition.registration_info.uri {
+        registration_info.SetURI(&BSTR::from(uri))?;
+    }
+    if let Some(version) = &definition.registration_info.version {
+        registration_info.SetVersion(&BSTR::from(version))?;
+    }
+    Ok(())
+}
+
+unsafe fn apply_action(actions: &IActionCollection, action: &Action) -> windows::core::Result<()> {
+    match action {
+        Action::Exec(exec) => {
+            let i_action: IAction = actions.Create(TASK_ACTION_EXEC)?;
+            let i_exec_action: IExecAction = i_action.cast()?;
+
+            i_exec_action.SetPath(&BSTR::from(&exec.path))?;
+            i_exec_action.SetId(&BSTR::from(&exec.id))?;
+            i_exec_action.SetWorkingDirectory(&BSTR::from(&exec.working_dir))?;
+            i_exec_action.SetArguments(&BSTR::from(&exec.args))?;
+        }
+        Action::ComHandler(com_handler) => {
+            let i_action: IAction = actions.Create(TASK_ACTION_COM_HANDLER)?;
+            let i_com_handler_action: IComHandlerAction = i_action.cast()?;
+
+            i_com_handler_action.SetId(&BSTR::from(&com_handler.id))?;
+            i_com_handler_action.SetClassId(&BSTR::from(&com_handler.class_id))?;
+            i_com_handler_action.SetData(&BSTR::from(&com_handler.data))?;
+        }
+    }
+    Ok(())
+}
+
+unsafe fn apply_principal(
+    task_definition: &ITaskDefinition,
+    settings: &PrincipalSettings,
+) -> windows::core::Result<()> {
+    let principal = task_definition.Principal()?;
+    principal.SetDisplayName(&BSTR::from(&settings.display_name))?;
+
+    if let Some(gid) = &settings.group_id {
+        principal.SetGroupId(&BSTR::from(gid))?;
+    } else if let Some(uid) = &settings.user_id {
+        principal.SetUserId(&BSTR::from(uid))?;
+    }
+
+    principal.SetId(&BSTR::from(&settings.id))?;
+    principal.SetLogonType(TASK_LOGON_TYPE(settings.logon_type as i32))?;
+    principal.SetRunLevel(TASK_RUNLEVEL_TYPE(settings.run_level as i32))?;
+
+    if settings.process_token_sid_type.is_some() || !settings.required_privileges.is_empty() {
+        let principal2: IPrincipal2 = principal.cast()?;
+        if let Some(sid_type) = settings.process_token_sid_type {
+            principal2.SetProcessTokenSidType(TASK_PROCESSTOKENSID_TYPE(sid_type as i32))?;
+        }
+        for privilege in &settings.required_privileges {
+            principal2.AddRequiredPrivilege(&BSTR::from(privilege.name()))?;
+        }
+    }
+    task_definition.SetPrincipal(&principal)
+}
+
+unsafe fn apply_settings(
+    task_definition: &ITaskDefinition,
+    settings: &Settings,
+) -> windows::core::Result<()> {
+    let task_settings: ITaskSettings = task_definition.Settings()?;
+    // Handle idle settings
+    if let Some(s) = &settings.idle_settings {
+        let idle_settings: IIdleSettings = task_settings.IdleSettings()?;
+
+        #[allow(deprecated)]
+        if let Some(setting) = s.idle_duration {
+            idle_settings.SetIdleDuration(&BSTR::from(setting.to_string()))?;
+        }
+
+        if let Some(setting) = s.restart_on_idle {
+            idle_settings.SetRestartOnIdle(VARIANT_BOOL::from(setting))?;
+        }
+
+        if let Some(setting) = s.stop_on_idle_end {
+            idle_settings.SetStopOnIdleEnd(VARIANT_BOOL::from(setting))?;
+        }
+
+        #[allow(deprecated)]
+        if let Some(setting) = s.wait_timeout {
+            idle_settings.SetWaitTimeout(&BSTR::from(setting.to_string()))?;
+        }
+
+        task_settings.SetIdleSettings(&idle_settings)?;
+    }
+
+    // Handle Network Settings
+    if let Some(s) = &settings.network_settings {
+        let network_settings: INetworkSettings = task_settings.NetworkSettings()?;
+        network_settings.SetId(&BSTR::from(&s.id))?;
+        network_settings.SetName(&BSTR::from(&s.name))?;
+        task_settings.SetNetworkSettings(&network_settings)?;
+    }
+
+    // Handle settings
+    if let Some(s) = settings.allow_demand_start {
+        task_settings.SetAllowDemandStart(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = settings.allow_hard_terminate {
+        task_settings.SetAllowHardTerminate(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = settings.compatibility {
+        task_settings.SetCompatibility(s.into())?;
+    }
+
+    if let Some(s) = settings.delete_expired_task_after {
+        task_settings.SetDeleteExpiredTaskAfter(&BSTR::from(s.to_string()))?;
+    }
+
+    if let Some(s) = settings.disallow_start_if_on_batteries {
+        task_settings.SetDisallowStartIfOnBatteries(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = settings.enabled {
+        task_settings.SetEnabled(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = settings.execution_time_limit {
+        task_settings.SetExecutionTimeLimit(&BSTR::from(s.to_string()))?;
+    }
+
+    if let Some(s) = settings.hidden {
+        task_settings.SetHidden(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = settings.multiple_instances_policy {
+        task_settings.SetMultipleInstances(s.into())?;
+    }
+
+    if let Some(s) = settings.priority {
+        task_settings.SetPriority(s as i32)?;
+    }
+
+    if let Some(s) = settings.restart_count {
+        task_settings.SetRestartCount(s)?;
+    }
+
+    if let Some(s) = settings.restart_interval {
+        task_settings.SetRestartInterval(&BSTR::from(s.to_string()))?;
+    }
+
+    if let Some(s) = settings.run_only_if_idle {
+        task_settings.SetRunOnlyIfIdle(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = settings.run_only_if_network_available {
+        task_settings.SetRunOnlyIfNetworkAvailable(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = settings.start_when_available {
+        task_settings.SetStartWhenAvailable(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = settings.stop_if_going_on_batteries {
+        task_settings.SetStopIfGoingOnBatteries(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = settings.wake_to_run {
+        task_settings.SetWakeToRun(VARIANT_BOOL::from(s))?;
+    }
+
+    if let Some(s) = &settings.xml_text {
+        task_settings.SetXmlText(&BSTR::from(s))?;
+    }
+
+    if settings.disallow_start_on_remote_app_session.is_some()
+        || settings.use_unified_scheduling_engine.is_some()
+    {
+        let task_settings2: ITaskSettings2 = task_settings.cast()?;
+        if let Some(s) = settings.disallow_start_on_remote_app_session {
+            task_settings2.SetDisallowStartOnRemoteAppSession(VARIANT_BOOL::from(s))?;
+        }
+        if let Some(s) = settings.use_unified_scheduling_engine {
+            task_settings2.SetUseUnifiedSchedulingEngine(VARIANT_BOOL::from(s))?;
+        }
+    }
+
+    if settings.volatile.is_some() || settings.maintenance_settings.is_some() {
+        let task_settings3: ITaskSettings3 = task_settings.cast()?;
+        if let Some(s) = settings.volatile {
+            task_settings3.SetVolatile(VARIANT_BOOL::from(s))?;
+        }
+        if let Some(s) = &settings.maintenance_settings {
+            let maintenance_settings = task_settings3.CreateMaintenanceSettings()?;
+            maintenance_settings.SetPeriod(&BSTR::from(s.period.to_string()))?;
+            if let Some(deadline) = s.deadline {
+                maintenance_settings.SetDeadline(&BSTR::from(deadline.to_string()))?;
+            }
+            if let Some(exclusive) = s.exclusive {
+                maintenance_settings.SetExclusive(VARIANT_BOOL::from(exclusive))?;
+            }
+            task_settings3.SetMaintenanceSettings(&maintenance_settings)?;
+        }
+    }
+
+    task_definition.SetSettings(&task_settings)
+}
+
+fn trigger_type(kind: &TriggerKind) -> TASK_TRIGGER_TYPE2 {
+    match kind {
+        TriggerKind::Boot(_) => TASK_TRIGGER_BOOT,
+        TriggerKind::Daily(_) => TASK_TRIGGER_DAILY,
+        TriggerKind::Event(_) => TASK_TRIGGER_EVENT,
+        TriggerKind::Idle => TASK_TRIGGER_IDLE,
+        TriggerKind::Logon(_) => TASK_TRIGGER_LOGON,
+        TriggerKind::Monthly(_) => TASK_TRIGGER_MONTHLY,
+        TriggerKind::MonthlyDOW(_) => TASK_TRIGGER_MONTHLYDOW,
+        TriggerKind::Registration(_) => TASK_TRIGGER_REGISTRATION,
+        TriggerKind::SessionStateChange(_) => TASK_TRIGGER_SESSION_STATE_CHANGE,
+        TriggerKind::Time(_) => TASK_TRIGGER_TIME,
+        TriggerKind::Weekly(_) => TASK_TRIGGER_WEEKLY,
+    }
+}
+
+unsafe fn apply_trigger(i_trigger: &ITrigger, trigger: &Trigger) -> windows::core::Result<()> {
+    i_trigger.SetId(&BSTR::from(&trigger.id))?;
+    i_trigger.SetEnabled(VARIANT_BOOL::from(trigger.enabled))?;
+
+    if let Some(start) = &trigger.start_boundary {
+        i_trigger.SetStartBoundary(&BSTR::from(start))?;
+    }
+
+    if let Some(end) = &trigger.end_boundary {
+        i_trigger.SetEndBoundary(&BSTR::from(end))?;
+    }
+
+    if let Some(time_limit) = trigger.execution_time_limit {
+        i_trigger.SetExecutionTimeLimit(&BSTR::from(time_limit.to_string()))?;
+    }
+
+    if let Some(pattern) = trigger.repetition {
+        let repetition = i_trigger.Repetition()?;
+        repetition.SetDuration(&BSTR::from(pattern.duration.to_string()))?;
+        repetition.SetInterval(&BSTR::from(pattern.interval.to_string()))?;
+        repetition.SetStopAtDurationEnd(VARIANT_BOOL::from(pattern.stop_at_duration_end))?;
+    }
+
+    match &trigger.kind {
+        TriggerKind::Boot(boot) => {
+            let i_boot_trigger: IBootTrigger = i_trigger.cast()?;
+            if let Some(delay) = boot.delay {
+                i_boot_trigger.SetDelay(&BSTR::from(delay.to_string()))?;
+            }
+        }
+        TriggerKind::Daily(daily) => {
+            let i_daily_trigger: IDailyTrigger = i_trigger.cast()?;
+            if let Some(days) = daily.days_interval {
+                i_daily_trigger.SetDaysInterval(days)?;
+            }
+            if let Some(delay) = daily.random_delay {
+                i_daily_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
+            }
+        }
+        TriggerKind::Event(event) => {
+            let i_event_trigger: IEventTrigger = i_trigger.cast()?;
+            if let Some(delay) = event.delay {
+                i_event_trigger.SetDelay(&BSTR::from(delay.to_string()))?;
+            }
+            if let Some(query) = &event.subscription {
+                i_event_trigger.SetSubscription(&BSTR::from(query))?;
+            }
+            if !event.value_queries.is_empty() {
+                let i_task_named_value_collection = i_event_trigger.ValueQueries()?;
+                for (name, value) in &event.value_queries {
+                    i_task_named_value_collection.Create(&BSTR::from(name), &BSTR::from(value))?;
+                }
+                i_event_trigger.SetValueQueries(&i_task_named_value_collection)?;
+            }
+        }
+        TriggerKind::Idle => {}
+        TriggerKind::Logon(logon) => {
+            let i_logon_trigger: ILogonTrigger = i_trigger.cast()?;
+            if let Some(delay) = logon.delay {
+                i_logon_trigger.SetDelay(&BSTR::from(delay.to_string()))?;
+            }
+            if let Some(id) = &logon.user_id {
+                i_logon_trigger.SetUserId(&BSTR::from(id))?;
+            }
+        }
+        TriggerKind::Monthly(monthly) => {
+            let i_monthly_trigger: IMonthlyTrigger = i_trigger.cast()?;
+            if let Some(days) = monthly.days_of_month {
+                i_monthly_trigger.SetDaysOfMonth(days)?;
+            }
+            if let Some(months) = monthly.months_of_year {
+                i_monthly_trigger.SetMonthsOfYear(months.bits())?;
+            }
+            if let Some(delay) = monthly.random_delay {
+                i_monthly_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
+            }
+            if let Some(is_run) = monthly.run_on_last_day {
+                i_monthly_trigger.SetRunOnLastDayOfMonth(VARIANT_BOOL::from(is_run))?;
+            }
+        }
+        TriggerKind::MonthlyDOW(monthly_dow) => {
+            let i_monthly_dow_trigger: IMonthlyDOWTrigger = i_trigger.cast()?;
+            if let Some(days) = monthly_dow.days_of_week {
+                i_monthly_dow_trigger.SetDaysOfWeek(days.bits())?;
+            }
+            if let Some(months) = monthly_dow.months_of_year {
+                i_monthly_dow_trigger.SetMonthsOfYear(months.bits())?;
+            }
+            if let Some(delay) = monthly_dow.random_delay {
+                i_monthly_dow_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
+            }
+            if let Some(is_run) = monthly_dow.run_on_last_week {
+                i_monthly_dow_trigger.SetRunOnLastWeekOfMonth(VARIANT_BOOL::from(is_run))?;
+            }
+            if let Some(weeks) = monthly_dow.weeks_of_month {
+                i_monthly_dow_trigger.SetWeeksOfMonth(weeks.bits())?;
+            }
+        }
+        TriggerKind::Registration(registration) => {
+            let i_registration_trigger: IRegistrationTrigger = i_trigger.cast()?;
+            if let Some(delay) = registration.delay {
+                i_registration_trigger.SetDelay(&BSTR::from(delay.to_string()))?;
+            }
+        }
+        TriggerKind::SessionStateChange(session) => {
+            let i_session_trigger: ISessionStateChangeTrigger = i_trigger.cast()?;
+            if let Some(delay) = session.delay {
+                i_session_trigger.SetDelay(&BSTR::from(delay.to_string()))?;
+            }
+            if let Some(state_change) = session.state_change {
+                i_session_trigger
+                    .SetStateChange(TASK_SESSION_STATE_CHANGE_TYPE(state_change as i32))?;
+            }
+            if let Some(id) = &session.user_id {
+                i_session_trigger.SetUserId(&BSTR::from(id))?;
+            }
+        }
+        TriggerKind::Time(time) => {
+            let i_time_trigger: ITimeTrigger = i_trigger.cast()?;
+            if let Some(delay) = time.random_delay {
+                i_time_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
+            }
+        }
+        TriggerKind::Weekly(weekly) => {
+            let i_weekly_trigger: IWeeklyTrigger = i_trigger.cast()?;
+            if let Some(days) = weekly.days_of_week {
+                i_weekly_trigger.SetDaysOfWeek(days.bits())?;
+            }
+            if let Some(delay) = weekly.random_delay {
+                i_weekly_trigger.SetRandomDelay(&BSTR::from(delay.to_string()))?;
+            }
+            if let Some(weeks) = weekly.weeks_interval {
+                i_weekly_trigger.SetWeeksInterval(weeks)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::PrincipalBuilder;
+
+    fn account_of(principal: Option<PrincipalBuilder>) -> (LogonType, Option<String>) {
+        let definition = TaskDefinition {
+            principal: principal.map(|principal| principal.build().unwrap()),
+            ..Default::default()
+        };
+        let (logon_type, account) = account(&definition, None);
+        (logon_type, account.map(str::to_string))
+    }
+
+    #[test]
+    fn account_of_the_principal() {
+        let some = |account: &str| Some(account.to_string());
+        assert_eq!(account_of(None), (LogonType::InteractiveToken, None));
+        assert_eq!(
+            account_of(Some(PrincipalBuilder::system())),
+            (LogonType::ServiceAccount, some("S-1-5-18"))
+        );
+        assert_eq!(
+            account_of(Some(PrincipalBuilder::local_service())),
+            (LogonType::ServiceAccount, some("S-1-5-19"))
+        );
+        assert_eq!(
+            account_of(Some(PrincipalBuilder::network_service())),
+            (LogonType::ServiceAccount, some("S-1-5-20"))
+        );
+        assert_eq!(
+            account_of(Some(PrincipalBuilder::group("S-1-5-32-545"))),
+            (LogonType::Group, some("S-1-5-32-545"))
+        );
+        assert_eq!(
+            account_of(Some(PrincipalBuilder::user("Domain\\User"))),
+            (LogonType::InteractiveToken, some("Domain\\User"))
+        );
+        assert_eq!(
+            account_of(Some(PrincipalBuilder::user("Domain\\User").run_whether_logged_on(false))),
+            (LogonType::S4U, some("Domain\\User"))
+        );
+    }
+
+    #[test]
+    fn account_of_the_credentials() {
+        let definition = TaskDefinition {
+            principal: Some(PrincipalBuilder::system().build().unwrap()),
+            ..Default::default()
+        };
+        let credentials =
+            Credentials::new("Domain\\User", Some("hunter2".into()), LogonType::Password)
+                .unwrap();
+        assert_eq!(
+            account(&definition, Some(&credentials)),
+            (LogonType::Password, Some("Domain\\User"))
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::datetime::{DateTime, ZonedDateTime};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A time zone, used to compute the [occurrences](crate::occurrence) of triggers across daylight
+/// saving time transitions.
+///
+/// Only [utc_offset](TimeZone::utc_offset) needs to be implemented. It is implemented for
+/// [FixedOffset] and [Utc], for `chrono::Local`, `chrono::Utc` and `chrono::FixedOffset` with the
+/// `chrono` feature, and for the IANA time zones of `chrono_tz::Tz` with the `chrono-tz` feature.
+///
+/// # Example
+/// ```
+/// use planif::datetime::DateTime;
+/// use planif::time_zone::TimeZone;
+///
+/// // Central European Time, with the daylight saving time of 2023
+/// struct Paris;
+///
+/// impl TimeZone for Paris {
+///     fn utc_offset(&self, utc: &DateTime) -> i32 {
+///         let summer_start = DateTime::new(2023, 3, 26, 1, 0, 0).unwrap();
+///         let summer_end = DateTime::new(2023, 10, 29, 1, 0, 0).unwrap();
+///         if (summer_start..summer_end).contains(utc) { 7200 } else { 3600 }
+///     }
+/// }
+///
+/// // 02:30 is skipped, the clock jumps from 02:00 to 03:00
+/// let skipped = Paris.at_local(&"2023-03-26T02:30:00".parse().unwrap());
+/// assert_eq!(skipped.to_string(), "2023-03-26T03:00:00+02:00");
+///
+/// // 02:30 happens twice, the earliest is used
+/// let repeated = Paris.at_local(&"2023-10-29T02:30:00".parse().unwrap());
+/// assert_eq!(repeated.to_string(), "2023-10-29T02:30:00+02:00");
+/// ```
+pub trait TimeZone {
+    /// Returns the offset from UTC in seconds at the UTC date and time, positive east of Greenwich.
+    fn utc_offset(&self, utc: &DateTime) -> i32;
+
+    /// Returns the date and time on the wall clock of the time zone at the UTC date and time.
+    fn at_utc(&self, utc: &DateTime) -> ZonedDateTime {
+        let offset = self.utc_offset(utc);
+        ZonedDateTime::new(utc.saturating_add_seconds(offset as i64), offset)
+    }
+
+    /// Returns the date and time of the wall clock of the time zone with its offset from UTC.
+    ///
+    /// When the wall clock is set back, the times which happen twice resolve to the earliest. When
+    /// the wall clock is set forward, the skipped times resolve to the end of the transition, ie:
+    /// 02:30 resolves to 03:00 when the clock jumps from 02:00 to 03:00.
+    fn at_local(&self, local: &DateTime) -> ZonedDateTime {
+        // The offsets before and after a transition, assuming there is at most one transition in
+        // two days.
+        let before = self.utc_offset(&local.saturating_add_seconds(-SECONDS_PER_DAY));
+        let after = self.utc_offset(&local.saturating_add_seconds(SECONDS_PER_DAY));
+
+        let candidates = [before, after].into_iter().filter(|offset| {
+            let utc = local.saturating_add_seconds(-(*offset as i64));
+            self.utc_offset(&utc) == *offset
+        });
+        if let Some(offset) = candidates.max() {
+            return ZonedDateTime::new(*local, offset);
+        }
+
+        // The local time is skipped, find the first instant with the offset after the transition.
+        let mut low = local.saturating_add_seconds(-(after as i64));
+        let mut high = local.saturating_add_seconds(-(before as i64));
+        while high.seconds_since(&low) > 1 {
+            let middle = low.saturating_add_seconds(high.seconds_since(&low) / 2);
+            if self.utc_offset(&middle) == after {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        self.at_utc(&high)
+    }
+}
+
+impl<Z: TimeZone + ?Sized> TimeZone for &Z {
+    fn utc_offset(&self, utc: &DateTime) -> i32 {
+        (**self).utc_offset(utc)
+    }
+}
+
+/// Coordinated Universal Time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Utc;
+
+impl TimeZone for Utc {
+    fn utc_offset(&self, _utc: &DateTime) -> i32 {
+        0
+    }
+}
+
+/// A time zone with a constant offset from UTC, without daylight saving time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedOffset {
+    offset: i32,
+}
+
+impl FixedOffset {
+    /// Creates a time zone `seconds` ahead of UTC, negative west of Greenwich.
+    pub fn east(seconds: i32) -> Self {
+        Self { offset: seconds }
+    }
+
+    /// The offset from UTC in seconds.
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+}
+
+impl TimeZone for FixedOffset {
+    fn utc_offset(&self, _utc: &DateTime) -> i32 {
+        self.offset
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn naive_utc(utc: &DateTime) -> chrono::NaiveDateTime {
+    chrono::DateTime::from_timestamp(utc.timestamp(), 0)
+        .expect("the years 1 to 9999 are supported by chrono")
+        .naive_utc()
+}
+
+#[cfg(feature = "chrono")]
+impl TimeZone for chrono::Local {
+    fn utc_offset(&self, utc: &DateTime) -> i32 {
+        use chrono::{Offset, TimeZone};
+        self.offset_from_utc_datetime(&naive_utc(utc))
+            .fix()
+            .local_minus_utc()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TimeZone for chrono::Utc {
+    fn utc_offset(&self, _utc: &DateTime) -> i32 {
+        0
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TimeZone for chrono::FixedOffset {
+    fn utc_offset(&self, _utc: &DateTime) -> i32 {
+        self.local_minus_utc()
+    }
+}
+
+#[cfg(feature = "chrono-tz")]
+impl TimeZone for chrono_tz::Tz {
+    fn utc_offset(&self, utc: &DateTime) -> i32 {
+        use chrono::{Offset, TimeZone};
+        self.offset_from_utc_datetime(&naive_utc(utc))
+            .fix()
+            .local_minus_utc()
+    }
+}
+
+// Eastern Time with the daylight saving time of 2023
+#[cfg(test)]
+pub(crate) struct NewYork;
+
+#[cfg(test)]
+impl TimeZone for NewYork {
+    fn utc_offset(&self, utc: &DateTime) -> i32 {
+        let summer_start = DateTime::new(2023, 3, 12, 7, 0, 0).unwrap();
+        let summer_end = DateTime::new(2023, 11, 5, 6, 0, 0).unwrap();
+        if (summer_start..summer_end).contains(utc) {
+            -4 * 3600
+        } else {
+            -5 * 3600
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(s: &str) -> DateTime {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn resolve_local_times() {
+        let resolve = |s| NewYork.at_local(&local(s)).to_string();
+
+        assert_eq!(resolve("2023-01-01T08:00:00"), "2023-01-01T08:00:00-05:00");
+        assert_eq!(resolve("2023-07-01T08:00:00"), "2023-07-01T08:00:00-04:00");
+        assert_eq!(resolve("2023-03-12T01:59:59"), "2023-03-12T01:59:59-05:00");
+        assert_eq!(resolve("2023-03-12T02:30:00"), "2023-03-12T03:00:00-04:00");
+        assert_eq!(resolve("2023-03-12T03:00:00"), "2023-03-12T03:00:00-04:00");
+        assert_eq!(resolve("2023-11-05T01:30:00"), "2023-11-05T01:30:00-04:00");
+        assert_eq!(resolve("2023-11-05T02:00:00"), "2023-11-05T02:00:00-05:00");
+    }
+
+    #[cfg(feature = "chrono-tz")]
+    #[test]
+    fn chrono_tz_time_zones() {
+        let paris = chrono_tz::Europe::Paris;
+        assert_eq!(
+            paris.at_local(&local("2023-03-26T02:30:00")).to_string(),
+            "2023-03-26T03:00:00+02:00"
+        );
+        assert_eq!(
+            chrono_tz::Asia::Kolkata
+                .at_utc(&local("2023-01-01T00:00:00"))
+                .to_string(),
+            "2023-01-01T05:30:00+05:30"
+        );
+    }
+}
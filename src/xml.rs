@@ -0,0 +1,1679 @@
+use std::fmt;
+
+use crate::calendar::{DaysOfWeek, Months, WeeksOfMonth};
+use crate::enums::{DayOfMonth, DayOfWeek, Month, StateChange, WeekOfMonth};
+use crate::error::ParseError;
+use crate::schedule_builder::{parse_class_id, Action, ComHandlerAction, ExecAction};
+use crate::settings::{
+    is_service_account, Compatibility, Duration, IdleSettings, InstancesPolicy, LogonType,
+    MaintenanceSettings, NetworkSettings, PrincipalSettings, Priority, ProcessTokenSidType,
+    RunLevel, Settings,
+};
+use crate::task_definition::{
+    BootTrigger, DailyTrigger, EventTrigger, LogonTrigger, MonthlyDOWTrigger, MonthlyTrigger,
+    RegistrationInfo, RegistrationTrigger, RepetitionPattern, SessionStateChangeTrigger,
+    TaskDefinition, TimeTrigger, Trigger, TriggerKind, WeeklyTrigger,
+};
+
+/// The namespace of the Task Scheduler XML schema.
+pub const TASK_NAMESPACE: &str = "http://schemas.microsoft.com/windows/2004/02/mit/task";
+
+/// Versions of the Task Scheduler XML schema, written to the `version` attribute of the `Task` element.
+///
+/// See <https://docs.microsoft.com/en-us/windows/win32/taskschd/taskschedulerschema-task-element>
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SchemaVersion {
+    /// Task Scheduler 2.0 (Windows Vista, Windows Server 2008).
+    #[default]
+    V1_2,
+    /// Task Scheduler 2.1 (Windows 7, Windows Server 2008 R2).
+    V1_3,
+    /// Task Scheduler 2.2 (Windows 8, Windows Server 2012).
+    V1_4,
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaVersion::V1_2 => write!(f, "1.2"),
+            SchemaVersion::V1_3 => write!(f, "1.3"),
+            SchemaVersion::V1_4 => write!(f, "1.4"),
+        }
+    }
+}
+
+impl From<Compatibility> for SchemaVersion {
+    /// The schema version which expresses the compatibility, the AT, 1.0 and 2.0 levels use the
+    /// 1.2 schema and Task Scheduler 2.3 uses the 1.4 schema.
+    fn from(compatibility: Compatibility) -> Self {
+        match compatibility {
+            Compatibility::AT | Compatibility::V1 | Compatibility::V2 => SchemaVersion::V1_2,
+            Compatibility::V2_1 => SchemaVersion::V1_3,
+            Compatibility::V2_2 | Compatibility::V2_3 => SchemaVersion::V1_4,
+        }
+    }
+}
+
+/// Serializes a task definition to a Task Scheduler XML document encoded as UTF-8.
+///
+/// The [compatibility](crate::settings::Settings::compatibility) and
+/// [xml_text](crate::settings::Settings::xml_text) settings are not part of the document, the
+/// compatibility of the task is expressed by the schema `version`. The `version` is raised to
+/// the schema of the declared compatibility, or of the
+/// [minimum compatibility](TaskDefinition::minimum_compatibility) of the task, when it is newer,
+/// so that the document never contains elements its schema does not define.
+///
+/// # Example
+/// ```
+/// use planif::settings::Compatibility;
+/// use planif::task_definition::{TaskDefinition, Trigger, TriggerKind};
+/// use planif::schedule_builder::Action;
+/// use planif::xml::{self, SchemaVersion};
+///
+/// let mut definition = TaskDefinition::new();
+/// definition.triggers.push(Trigger::new("BootTrigger", TriggerKind::Boot(Default::default())));
+/// definition.actions.push(Action::new("", "notepad.exe", "", ""));
+///
+/// let document = xml::serialize(&definition, SchemaVersion::V1_2);
+/// assert!(document.contains("<BootTrigger id=\"BootTrigger\">"));
+///
+/// definition.settings.compatibility = Some(Compatibility::V2_2);
+/// let document = xml::serialize(&definition, SchemaVersion::V1_2);
+/// assert!(document.contains("<Task version=\"1.4\""));
+/// ```
+pub fn serialize(definition: &TaskDefinition, version: SchemaVersion) -> String {
+    write_document(definition, version, "UTF-8")
+}
+
+/// Serializes a task definition to a Task Scheduler XML document encoded as UTF-16 LE with a
+/// byte order mark, the encoding used by `schtasks /query /xml`.
+pub fn serialize_utf16(definition: &TaskDefinition, version: SchemaVersion) -> Vec<u8> {
+    let document = write_document(definition, version, "UTF-16");
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in document.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    bytes
+}
+
+fn write_document(definition: &TaskDefinition, version: SchemaVersion, encoding: &str) -> String {
+    let declared = definition.settings.compatibility.map(SchemaVersion::from);
+    let version = version
+        .max(declared.unwrap_or_default())
+        .max(definition.minimum_compatibility().into());
+
+    let mut w = XmlWriter::new();
+    w.line(&format!("<?xml version=\"1.0\" encoding=\"{}\"?>", encoding));
+    w.open_with(
+        "Task",
+        &[("version", &version.to_string()), ("xmlns", TASK_NAMESPACE)],
+    );
+
+    let info = &definition.registration_info;
+    if *info != RegistrationInfo::default() {
+        w.open("RegistrationInfo");
+        w.optional("Source", info.source.as_ref());
+        w.optional("Date", info.date.as_ref());
+        w.optional("Author", info.author.as_ref());
+        w.optional("Version", info.version.as_ref());
+        w.optional("Description", info.description.as_ref());
+        w.optional("URI", info.uri.as_ref());
+        w.optional("SecurityDescriptor", info.security_descriptor.as_ref());
+        w.optional("Documentation", info.documentation.as_ref());
+        w.close("RegistrationInfo");
+    }
+
+    if !definition.triggers.is_empty() {
+        w.open("Triggers");
+        for trigger in &definition.triggers {
+            write_trigger(&mut w, trigger);
+        }
+        w.close("Triggers");
+    }
+
+    if let Some(principal) = &definition.principal {
+        w.open("Principals");
+        write_principal(&mut w, principal);
+        w.close("Principals");
+    }
+
+    write_settings(&mut w, &definition.settings);
+
+    match &definition.principal {
+        Some(principal) => w.open_with("Actions", &[("Context", &principal.id)]),
+        None => w.open("Actions"),
+    }
+    for action in &definition.actions {
+        write_action(&mut w, action);
+    }
+    w.close("Actions");
+
+    w.close("Task");
+    w.out
+}
+
+fn write_trigger(w: &mut XmlWriter, trigger: &Trigger) {
+    let name = match trigger.kind {
+        TriggerKind::Boot(_) => "BootTrigger",
+        TriggerKind::Daily(_)
+        | TriggerKind::Monthly(_)
+        | TriggerKind::MonthlyDOW(_)
+        | TriggerKind::Weekly(_) => "CalendarTrigger",
+        TriggerKind::Event(_) => "EventTrigger",
+        TriggerKind::Idle => "IdleTrigger",
+        TriggerKind::Logon(_) => "LogonTrigger",
+        TriggerKind::Registration(_) => "RegistrationTrigger",
+        TriggerKind::SessionStateChange(_) => "SessionStateChangeTrigger",
+        TriggerKind::Time(_) => "TimeTrigger",
+    };
+
+    if trigger.id.is_empty() {
+        w.open(name);
+    } else {
+        w.open_with(name, &[("id", &trigger.id)]);
+    }
+
+    if let Some(repetition) = &trigger.repetition {
+        w.open("Repetition");
+        w.duration("Interval", Some(repetition.interval));
+        // An empty duration repeats the trigger indefinitely
+        w.duration("Duration", Some(repetition.duration));
+        w.element("StopAtDurationEnd", &repetition.stop_at_duration_end.to_string());
+        w.close("Repetition");
+    }
+    w.optional("StartBoundary", trigger.start_boundary.as_ref());
+    w.optional("EndBoundary", trigger.end_boundary.as_ref());
+    w.duration("ExecutionTimeLimit", trigger.execution_time_limit);
+    w.element("Enabled", &trigger.enabled.to_string());
+
+    match &trigger.kind {
+        TriggerKind::Boot(boot) => w.duration("Delay", boot.delay),
+        TriggerKind::Daily(daily) => {
+            w.duration("RandomDelay", daily.random_delay);
+            w.open("ScheduleByDay");
+            w.element("DaysInterval", &daily.days_interval.unwrap_or(1).to_string());
+            w.close("ScheduleByDay");
+        }
+        TriggerKind::Event(event) => {
+            w.optional("Subscription", event.subscription.as_ref());
+            w.duration("Delay", event.delay);
+            if !event.value_queries.is_empty() {
+                w.open("ValueQueries");
+                for (name, value) in &event.value_queries {
+                    w.element_with("Value", &[("name", name)], value);
+                }
+                w.close("ValueQueries");
+            }
+        }
+        TriggerKind::Idle => {}
+        TriggerKind::Logon(logon) => {
+            w.optional("UserId", logon.user_id.as_ref());
+            w.duration("Delay", logon.delay);
+        }
+        TriggerKind::Monthly(monthly) => {
+            w.duration("RandomDelay", monthly.random_delay);
+            w.open("ScheduleByMonth");
+            w.open("DaysOfMonth");
+            let days = monthly.days_of_month.unwrap_or(0) as u32;
+            for day in 1..=31 {
+                if days & (1 << (day - 1)) != 0 {
+                    w.element("Day", &day.to_string());
+                }
+            }
+            if days & 0x8000_0000 != 0 || monthly.run_on_last_day == Some(true) {
+                w.element("Day", "Last");
+            }
+            w.close("DaysOfMonth");
+            write_months(w, monthly.months_of_year);
+            w.close("ScheduleByMonth");
+        }
+        TriggerKind::MonthlyDOW(monthly_dow) => {
+            w.duration("RandomDelay", monthly_dow.random_delay);
+            w.open("ScheduleByMonthDayOfWeek");
+            w.open("Weeks");
+            let weeks = monthly_dow.weeks_of_month.unwrap_or_default();
+            for (week, name) in WEEKS {
+                if weeks.contains(week) {
+                    w.element("Week", name);
+                }
+            }
+            if monthly_dow.run_on_last_week == Some(true) {
+                w.element("Week", "Last");
+            }
+            w.close("Weeks");
+            write_days_of_week(w, monthly_dow.days_of_week);
+            write_months(w, monthly_dow.months_of_year);
+            w.close("ScheduleByMonthDayOfWeek");
+        }
+        TriggerKind::Registration(registration) => w.duration("Delay", registration.delay),
+        TriggerKind::SessionStateChange(session) => {
+            w.duration("Delay", session.delay);
+            if let Some(state_change) = session.state_change {
+                let (_, name) = STATE_CHANGES
+                    .iter()
+                    .find(|(s, _)| *s == state_change)
+                    .expect("every state change has a name");
+                w.element("StateChange", name);
+            }
+            w.optional("UserId", session.user_id.as_ref());
+        }
+        TriggerKind::Time(time) => w.duration("RandomDelay", time.random_delay),
+        TriggerKind::Weekly(weekly) => {
+            w.duration("RandomDelay", weekly.random_delay);
+            w.open("ScheduleByWeek");
+            w.element("WeeksInterval", &weekly.weeks_interval.unwrap_or(1).to_string());
+            write_days_of_week(w, weekly.days_of_week);
+            w.close("ScheduleByWeek");
+        }
+    }
+
+    w.close(name);
+}
+
+const DAYS_OF_WEEK: [(DayOfWeek, &str); 7] = [
+    (DayOfWeek::Sunday, "Sunday"),
+    (DayOfWeek::Monday, "Monday"),
+    (DayOfWeek::Tuesday, "Tuesday"),
+    (DayOfWeek::Wednesday, "Wednesday"),
+    (DayOfWeek::Thursday, "Thursday"),
+    (DayOfWeek::Friday, "Friday"),
+    (DayOfWeek::Saturday, "Saturday"),
+];
+
+const MONTHS: [(Month, &str); 12] = [
+    (Month::January, "January"),
+    (Month::February, "February"),
+    (Month::March, "March"),
+    (Month::April, "April"),
+    (Month::May, "May"),
+    (Month::June, "June"),
+    (Month::July, "July"),
+    (Month::August, "August"),
+    (Month::September, "September"),
+    (Month::October, "October"),
+    (Month::November, "November"),
+    (Month::December, "December"),
+];
+
+const STATE_CHANGES: [(StateChange, &str); 6] = [
+    (StateChange::ConsoleConnect, "ConsoleConnect"),
+    (StateChange::ConsoleDisconnect, "ConsoleDisconnect"),
+    (StateChange::RemoteConnect, "RemoteConnect"),
+    (StateChange::RemoteDisconnect, "RemoteDisconnect"),
+    (StateChange::SessionLock, "SessionLock"),
+    (StateChange::SessionUnlock, "SessionUnlock"),
+];
+
+const WEEKS: [(WeekOfMonth, &str); 4] = [
+    (WeekOfMonth::First, "1"),
+    (WeekOfMonth::Second, "2"),
+    (WeekOfMonth::Third, "3"),
+    (WeekOfMonth::Fourth, "4"),
+];
+
+fn write_days_of_week(w: &mut XmlWriter, days: Option<DaysOfWeek>) {
+    w.open("DaysOfWeek");
+    let days = days.unwrap_or_default();
+    for (day, name) in DAYS_OF_WEEK {
+        if days.contains(day) {
+            w.empty(name);
+        }
+    }
+    w.close("DaysOfWeek");
+}
+
+fn write_months(w: &mut XmlWriter, months: Option<Months>) {
+    if let Some(months) = months {
+        w.open("Months");
+        for (month, name) in MONTHS {
+            if months.contains(month) {
+                w.empty(name);
+            }
+        }
+        w.close("Months");
+    }
+}
+
+fn write_principal(w: &mut XmlWriter, principal: &PrincipalSettings) {
+    w.open_with("Principal", &[("id", &principal.id)]);
+    w.optional("UserId", principal.user_id.as_ref());
+    let logon_type = match principal.logon_type {
+        LogonType::Password => Some("Password"),
+        LogonType::S4U => Some("S4U"),
+        LogonType::InteractiveToken => Some("InteractiveToken"),
+        LogonType::InteractiveTokenOrPassword => Some("InteractiveTokenOrPassword"),
+        // Expressed by the group or the service account's user id
+        LogonType::None | LogonType::Group | LogonType::ServiceAccount => None,
+    };
+    w.optional("LogonType", logon_type);
+    w.optional("GroupId", principal.group_id.as_ref());
+    if !principal.display_name.is_empty() {
+        w.element("DisplayName", &principal.display_name);
+    }
+    w.element(
+        "RunLevel",
+        match principal.run_level {
+            RunLevel::Highest => "HighestAvailable",
+            RunLevel::LUA => "LeastPrivilege",
+        },
+    );
+    if let Some(sid_type) = principal.process_token_sid_type {
+        w.element(
+            "ProcessTokenSidType",
+            match sid_type {
+                ProcessTokenSidType::None => "None",
+                ProcessTokenSidType::Unrestricted => "Unrestricted",
+                ProcessTokenSidType::Default => "Default",
+            },
+        );
+    }
+    if !principal.required_privileges.is_empty() {
+        w.open("RequiredPrivileges");
+        for privilege in &principal.required_privileges {
+            w.element("Privilege", privilege.name());
+        }
+        w.close("RequiredPrivileges");
+    }
+    w.close("Principal");
+}
+
+fn write_settings(w: &mut XmlWriter, settings: &Settings) {
+    let mut s = XmlWriter {
+        out: String::new(),
+        depth: w.depth + 1,
+    };
+
+    let policy = settings.multiple_instances_policy.map(|p| match p {
+        InstancesPolicy::Parallel => "Parallel",
+        InstancesPolicy::Queue => "Queue",
+        InstancesPolicy::IgnoreNew => "IgnoreNew",
+        InstancesPolicy::StopExisting => "StopExisting",
+    });
+    s.optional("MultipleInstancesPolicy", policy);
+    s.optional("DisallowStartIfOnBatteries", settings.disallow_start_if_on_batteries);
+    s.optional("StopIfGoingOnBatteries", settings.stop_if_going_on_batteries);
+    s.optional("AllowHardTerminate", settings.allow_hard_terminate);
+    s.optional("StartWhenAvailable", settings.start_when_available);
+    s.optional("RunOnlyIfNetworkAvailable", settings.run_only_if_network_available);
+    if let Some(network) = &settings.network_settings {
+        s.open("NetworkSettings");
+        s.element("Name", &network.name);
+        s.element("Id", &network.id);
+        s.close("NetworkSettings");
+    }
+    if let Some(idle) = &settings.idle_settings {
+        s.open("IdleSettings");
+        #[allow(deprecated)]
+        {
+            s.duration("Duration", idle.idle_duration);
+            s.duration("WaitTimeout", idle.wait_timeout);
+        }
+        s.optional("StopOnIdleEnd", idle.stop_on_idle_end);
+        s.optional("RestartOnIdle", idle.restart_on_idle);
+        s.close("IdleSettings");
+    }
+    s.optional("AllowStartOnDemand", settings.allow_demand_start);
+    s.optional("Enabled", settings.enabled);
+    s.optional("Hidden", settings.hidden);
+    s.optional("RunOnlyIfIdle", settings.run_only_if_idle);
+    s.optional("WakeToRun", settings.wake_to_run);
+    s.duration("ExecutionTimeLimit", settings.execution_time_limit);
+    s.optional("Priority", settings.priority.map(|priority| priority as i32));
+    if settings.restart_interval.is_some() || settings.restart_count.is_some() {
+        s.open("RestartOnFailure");
+        s.duration("Interval", settings.restart_interval);
+        s.optional("Count", settings.restart_count);
+        s.close("RestartOnFailure");
+    }
+    s.duration("DeleteExpiredTaskAfter", settings.delete_expired_task_after);
+    s.optional("UseUnifiedSchedulingEngine", settings.use_unified_scheduling_engine);
+    s.optional(
+        "DisallowStartOnRemoteAppSession",
+        settings.disallow_start_on_remote_app_session,
+    );
+    if let Some(maintenance) = &settings.maintenance_settings {
+        s.open("MaintenanceSettings");
+        s.duration("Period", Some(maintenance.period));
+        s.duration("Deadline", maintenance.deadline);
+        s.optional("Exclusive", maintenance.exclusive);
+        s.close("MaintenanceSettings");
+    }
+    s.optional("Volatile", settings.volatile);
+
+    if !s.out.is_empty() {
+        w.open("Settings");
+        w.out.push_str(&s.out);
+        w.close("Settings");
+    }
+}
+
+fn write_action(w: &mut XmlWriter, action: &Action) {
+    let name = match action {
+        Action::Exec(_) => "Exec",
+        Action::ComHandler(_) => "ComHandler",
+    };
+    if action.id().is_empty() {
+        w.open(name);
+    } else {
+        w.open_with(name, &[("id", action.id())]);
+    }
+
+    match action {
+        Action::Exec(exec) => {
+            w.element("Command", &exec.path);
+            if !exec.args.is_empty() {
+                w.element("Arguments", &exec.args);
+            }
+            if !exec.working_dir.is_empty() {
+                w.element("WorkingDirectory", &exec.working_dir);
+            }
+        }
+        Action::ComHandler(com_handler) => {
+            w.element("ClassId", &com_handler.class_id);
+            if !com_handler.data.is_empty() {
+                w.element("Data", &com_handler.data);
+            }
+        }
+    }
+    w.close(name);
+}
+
+/// A task read from a Task Scheduler XML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedTask {
+    /// The value of the `version` attribute of the `Task` element.
+    pub version: Option<String>,
+    /// The definition of the task.
+    pub definition: TaskDefinition,
+    /// The elements of the document which planif does not support and were not read.
+    pub unknown_elements: Vec<UnknownElement>,
+}
+
+/// An element of a Task Scheduler XML document which was not read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownElement {
+    /// The path of the element from the root of the document, for example `Task/Settings/Volatile`.
+    pub path: String,
+    /// The line of the element in the document, starting at 1.
+    pub line: u32,
+}
+
+impl fmt::Display for UnknownElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (line {})", self.path, self.line)
+    }
+}
+
+/// Parses a Task Scheduler XML document.
+///
+/// The document may be encoded as UTF-8 or UTF-16 (LE or BE). The encoding is detected from the
+/// byte order mark, or from the first character when there is none, as documents exported by
+/// `schtasks /query /xml` are UTF-16 LE with a byte order mark.
+///
+/// Elements which cannot be represented by a [TaskDefinition] are listed in
+/// [unknown_elements](ParsedTask::unknown_elements), values which are not valid return a
+/// [ParseError].
+pub fn parse(bytes: &[u8]) -> Result<ParsedTask, ParseError> {
+    parse_str(&decode(bytes)?)
+}
+
+/// Parses a Task Scheduler XML document which was already decoded.
+///
+/// # Example
+/// ```
+/// use planif::xml;
+///
+/// let document = r#"<?xml version="1.0" encoding="UTF-16"?>
+/// <Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+///   <Triggers>
+///     <BootTrigger>
+///       <Enabled>true</Enabled>
+///     </BootTrigger>
+///   </Triggers>
+///   <Settings>
+///     <NetworkProfileName>Office</NetworkProfileName>
+///   </Settings>
+///   <Actions Context="Author">
+///     <Exec>
+///       <Command>notepad.exe</Command>
+///     </Exec>
+///   </Actions>
+/// </Task>"#;
+///
+/// let task = xml::parse_str(document).unwrap();
+/// assert_eq!(task.definition.triggers.len(), 1);
+/// assert_eq!(task.unknown_elements[0].path, "Task/Settings/NetworkProfileName");
+/// ```
+pub fn parse_str(text: &str) -> Result<ParsedTask, ParseError> {
+    let document = roxmltree::Document::parse(text).map_err(|e| ParseError {
+        message: format!("Malformed XML: {}", e),
+        position: None,
+    })?;
+
+    let mut reader = Reader {
+        document: &document,
+        unknown_elements: Vec::new(),
+    };
+    let root = document.root_element();
+    if root.tag_name().name() != "Task" {
+        return Err(reader.error(root, "the root element must be Task"));
+    }
+
+    let definition = reader.task(root)?;
+    Ok(ParsedTask {
+        version: root.attribute("version").map(str::to_string),
+        definition,
+        unknown_elements: reader.unknown_elements,
+    })
+}
+
+fn decode(bytes: &[u8]) -> Result<String, ParseError> {
+    match bytes {
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => decode_utf8(rest),
+        [b'<', 0, ..] => decode_utf16(bytes, u16::from_le_bytes),
+        [0, b'<', ..] => decode_utf16(bytes, u16::from_be_bytes),
+        _ => decode_utf8(bytes),
+    }
+}
+
+fn decode_utf8(bytes: &[u8]) -> Result<String, ParseError> {
+    String::from_utf8(bytes.to_vec()).map_err(|e| ParseError {
+        message: format!("Invalid UTF-8 document: {}", e),
+        position: Some(e.utf8_error().valid_up_to()),
+    })
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String, ParseError> {
+    if bytes.len() % 2 != 0 {
+        return Err(ParseError {
+            message: "Invalid UTF-16 document: odd number of bytes".to_string(),
+            position: None,
+        });
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| from_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).map_err(|e| ParseError {
+        message: format!("Invalid UTF-16 document: {}", e),
+        position: None,
+    })
+}
+
+type Node<'a, 'input> = roxmltree::Node<'a, 'input>;
+
+fn elements<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(|child| child.is_element())
+}
+
+fn text<'a>(node: Node<'a, '_>) -> &'a str {
+    node.text().unwrap_or("").trim()
+}
+
+struct Reader<'a, 'input> {
+    document: &'a roxmltree::Document<'input>,
+    unknown_elements: Vec<UnknownElement>,
+}
+
+impl Reader<'_, '_> {
+    fn path(node: Node) -> String {
+        let mut names: Vec<&str> = node
+            .ancestors()
+            .filter(|n| n.is_element())
+            .map(|n| n.tag_name().name())
+            .collect();
+        names.reverse();
+        names.join("/")
+    }
+
+    fn line(&self, node: Node) -> u32 {
+        self.document.text_pos_at(node.range().start).row
+    }
+
+    fn unknown(&mut self, node: Node) {
+        self.unknown_elements.push(UnknownElement {
+            path: Self::path(node),
+            line: self.line(node),
+        });
+    }
+
+    fn error(&self, node: Node, message: &str) -> ParseError {
+        ParseError {
+            message: format!("{} (line {}): {}", Self::path(node), self.line(node), message),
+            position: None,
+        }
+    }
+
+    fn string(&self, node: Node) -> Option<String> {
+        Some(text(node).to_string())
+    }
+
+    fn bool(&self, node: Node) -> Result<bool, ParseError> {
+        match text(node) {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            other => Err(self.error(node, &format!("'{}' is not a boolean", other))),
+        }
+    }
+
+    fn number<T: std::str::FromStr>(&self, node: Node) -> Result<T, ParseError> {
+        text(node)
+            .parse()
+            .map_err(|_| self.error(node, &format!("'{}' is not a valid number", text(node))))
+    }
+
+    fn duration(&self, node: Node) -> Result<Duration, ParseError> {
+        text(node)
+            .parse()
+            .map_err(|e: ParseError| self.error(node, &e.message))
+    }
+
+    fn task(&mut self, root: Node) -> Result<TaskDefinition, ParseError> {
+        let mut definition = TaskDefinition::new();
+        for child in elements(root) {
+            match child.tag_name().name() {
+                "RegistrationInfo" => {
+                    definition.registration_info = self.registration_info(child)?
+                }
+                "Triggers" => {
+                    for trigger in elements(child) {
+                        if let Some(trigger) = self.trigger(trigger)? {
+                            definition.triggers.push(trigger);
+                        }
+                    }
+                }
+                "Principals" => {
+                    for (i, principal) in elements(child).enumerate() {
+                        if i == 0 && principal.tag_name().name() == "Principal" {
+                            definition.principal = Some(self.principal(principal)?);
+                        } else {
+                            self.unknown(principal);
+                        }
+                    }
+                }
+                "Settings" => definition.settings = self.settings(child)?,
+                "Actions" => {
+                    for action in elements(child) {
+                        match action.tag_name().name() {
+                            "Exec" => definition.actions.push(self.exec_action(action)?),
+                            "ComHandler" => {
+                                definition.actions.push(self.com_handler_action(action)?)
+                            }
+                            _ => self.unknown(action),
+                        }
+                    }
+                }
+                _ => self.unknown(child),
+            }
+        }
+        Ok(definition)
+    }
+
+    fn registration_info(&mut self, node: Node) -> Result<RegistrationInfo, ParseError> {
+        let mut info = RegistrationInfo::default();
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "Author" => info.author = self.string(child),
+                "Date" => info.date = self.string(child),
+                "Description" => info.description = self.string(child),
+                "Documentation" => info.documentation = self.string(child),
+                "SecurityDescriptor" => match text(child).parse() {
+                    Ok(sddl) => info.security_descriptor = Some(sddl),
+                    Err(ParseError { message, .. }) => return Err(self.error(child, &message)),
+                },
+                "Source" => info.source = self.string(child),
+                "URI" => info.uri = self.string(child),
+                "Version" => info.version = self.string(child),
+                _ => self.unknown(child),
+            }
+        }
+        Ok(info)
+    }
+
+    fn trigger(&mut self, node: Node) -> Result<Option<Trigger>, ParseError> {
+        let mut kind = match node.tag_name().name() {
+            "BootTrigger" => TriggerKind::Boot(BootTrigger::default()),
+            // The schedule of a calendar trigger is found in its ScheduleBy* element
+            "CalendarTrigger" => TriggerKind::Time(TimeTrigger::default()),
+            "EventTrigger" => TriggerKind::Event(EventTrigger::default()),
+            "IdleTrigger" => TriggerKind::Idle,
+            "LogonTrigger" => TriggerKind::Logon(LogonTrigger::default()),
+            "RegistrationTrigger" => TriggerKind::Registration(RegistrationTrigger::default()),
+            "SessionStateChangeTrigger" => {
+                TriggerKind::SessionStateChange(SessionStateChangeTrigger::default())
+            }
+            "TimeTrigger" => TriggerKind::Time(TimeTrigger::default()),
+            _ => {
+                self.unknown(node);
+                return Ok(None);
+            }
+        };
+        let is_calendar = node.tag_name().name() == "CalendarTrigger";
+        let mut trigger = Trigger::new(node.attribute("id").unwrap_or(""), TriggerKind::Idle);
+        let mut random_delay = None;
+
+        for child in elements(node) {
+            match (child.tag_name().name(), &mut kind) {
+                ("Enabled", _) => trigger.enabled = self.bool(child)?,
+                ("StartBoundary", _) => trigger.start_boundary = self.string(child),
+                ("EndBoundary", _) => trigger.end_boundary = self.string(child),
+                ("ExecutionTimeLimit", _) => {
+                    trigger.execution_time_limit = Some(self.duration(child)?)
+                }
+                ("Repetition", _) => trigger.repetition = Some(self.repetition(child)?),
+                ("RandomDelay", TriggerKind::Time(_)) => {
+                    random_delay = Some(self.duration(child)?)
+                }
+                ("Delay", TriggerKind::Boot(boot)) => boot.delay = Some(self.duration(child)?),
+                ("Delay", TriggerKind::Event(event)) => event.delay = Some(self.duration(child)?),
+                ("Delay", TriggerKind::Logon(logon)) => logon.delay = Some(self.duration(child)?),
+                ("Delay", TriggerKind::Registration(registration)) => {
+                    registration.delay = Some(self.duration(child)?)
+                }
+                ("Delay", TriggerKind::SessionStateChange(session)) => {
+                    session.delay = Some(self.duration(child)?)
+                }
+                ("StateChange", TriggerKind::SessionStateChange(session)) => {
+                    match STATE_CHANGES.iter().find(|(_, name)| *name == text(child)) {
+                        Some((state_change, _)) => session.state_change = Some(*state_change),
+                        None => {
+                            return Err(self.error(
+                                child,
+                                &format!("'{}' is not a valid state change", text(child)),
+                            ))
+                        }
+                    }
+                }
+                ("UserId", TriggerKind::SessionStateChange(session)) => {
+                    session.user_id = self.string(child)
+                }
+                ("Subscription", TriggerKind::Event(event)) => {
+                    event.subscription = self.string(child)
+                }
+                ("ValueQueries", TriggerKind::Event(event)) => {
+                    for value in elements(child) {
+                        match (value.tag_name().name(), value.attribute("name")) {
+                            ("Value", Some(name)) => event
+                                .value_queries
+                                .push((name.to_string(), text(value).to_string())),
+                            _ => self.unknown(value),
+                        }
+                    }
+                }
+                ("UserId", TriggerKind::Logon(logon)) => logon.user_id = self.string(child),
+                ("ScheduleByDay", TriggerKind::Time(_)) if is_calendar => {
+                    kind = TriggerKind::Daily(self.schedule_by_day(child)?)
+                }
+                ("ScheduleByWeek", TriggerKind::Time(_)) if is_calendar => {
+                    kind = TriggerKind::Weekly(self.schedule_by_week(child)?)
+                }
+                ("ScheduleByMonth", TriggerKind::Time(_)) if is_calendar => {
+                    kind = TriggerKind::Monthly(self.schedule_by_month(child)?)
+                }
+                ("ScheduleByMonthDayOfWeek", TriggerKind::Time(_)) if is_calendar => {
+                    kind = TriggerKind::MonthlyDOW(self.schedule_by_month_dow(child)?)
+                }
+                _ => self.unknown(child),
+            }
+        }
+
+        if is_calendar && matches!(kind, TriggerKind::Time(_)) {
+            return Err(self.error(
+                node,
+                "CalendarTrigger requires ScheduleByDay/Week/Month/MonthDayOfWeek",
+            ));
+        }
+
+        // The random delay may appear before the schedule element of calendar triggers
+        match &mut kind {
+            TriggerKind::Daily(daily) => daily.random_delay = random_delay,
+            TriggerKind::Monthly(monthly) => monthly.random_delay = random_delay,
+            TriggerKind::MonthlyDOW(monthly_dow) => monthly_dow.random_delay = random_delay,
+            TriggerKind::Time(time) => time.random_delay = random_delay,
+            TriggerKind::Weekly(weekly) => weekly.random_delay = random_delay,
+            _ => {}
+        }
+        trigger.kind = kind;
+        Ok(Some(trigger))
+    }
+
+    fn repetition(&mut self, node: Node) -> Result<RepetitionPattern, ParseError> {
+        let mut repetition = RepetitionPattern {
+            duration: Duration::default(),
+            interval: Duration::default(),
+            stop_at_duration_end: false,
+        };
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "Duration" => repetition.duration = self.duration(child)?,
+                "Interval" => repetition.interval = self.duration(child)?,
+                "StopAtDurationEnd" => repetition.stop_at_duration_end = self.bool(child)?,
+                _ => self.unknown(child),
+            }
+        }
+        Ok(repetition)
+    }
+
+    fn schedule_by_day(&mut self, node: Node) -> Result<DailyTrigger, ParseError> {
+        let mut daily = DailyTrigger::default();
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "DaysInterval" => daily.days_interval = Some(self.number(child)?),
+                _ => self.unknown(child),
+            }
+        }
+        Ok(daily)
+    }
+
+    fn schedule_by_week(&mut self, node: Node) -> Result<WeeklyTrigger, ParseError> {
+        let mut weekly = WeeklyTrigger::default();
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "WeeksInterval" => weekly.weeks_interval = Some(self.number(child)?),
+                "DaysOfWeek" => weekly.days_of_week = Some(self.days_of_week(child)),
+                _ => self.unknown(child),
+            }
+        }
+        Ok(weekly)
+    }
+
+    fn schedule_by_month(&mut self, node: Node) -> Result<MonthlyTrigger, ParseError> {
+        let mut monthly = MonthlyTrigger::default();
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "DaysOfMonth" => {
+                    let mut days = 0;
+                    for day in elements(child) {
+                        match (day.tag_name().name(), text(day)) {
+                            ("Day", "Last") => monthly.run_on_last_day = Some(true),
+                            ("Day", _) => {
+                                let number: i32 = self.number(day)?;
+                                if !(1..=31).contains(&number) {
+                                    return Err(self.error(
+                                        day,
+                                        "days of month must be between 1 and 31 inclusively",
+                                    ));
+                                }
+                                days |= i32::from(DayOfMonth::Day(number));
+                            }
+                            _ => self.unknown(day),
+                        }
+                    }
+                    monthly.days_of_month = Some(days);
+                }
+                "Months" => monthly.months_of_year = Some(self.months(child)),
+                _ => self.unknown(child),
+            }
+        }
+        Ok(monthly)
+    }
+
+    fn schedule_by_month_dow(&mut self, node: Node) -> Result<MonthlyDOWTrigger, ParseError> {
+        let mut monthly_dow = MonthlyDOWTrigger::default();
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "Weeks" => {
+                    let mut weeks = WeeksOfMonth::empty();
+                    for week in elements(child) {
+                        match (week.tag_name().name(), text(week)) {
+                            ("Week", "Last") => monthly_dow.run_on_last_week = Some(true),
+                            ("Week", value) => match WEEKS.iter().find(|(_, name)| *name == value) {
+                                Some((week, _)) => weeks.insert(*week),
+                                None => {
+                                    return Err(self.error(
+                                        week,
+                                        &format!("'{}' is not a valid week of the month", value),
+                                    ))
+                                }
+                            },
+                            _ => self.unknown(week),
+                        }
+                    }
+                    monthly_dow.weeks_of_month = Some(weeks);
+                }
+                "DaysOfWeek" => monthly_dow.days_of_week = Some(self.days_of_week(child)),
+                "Months" => monthly_dow.months_of_year = Some(self.months(child)),
+                _ => self.unknown(child),
+            }
+        }
+        Ok(monthly_dow)
+    }
+
+    fn days_of_week(&mut self, node: Node) -> DaysOfWeek {
+        let mut days = DaysOfWeek::empty();
+        for child in elements(node) {
+            match DAYS_OF_WEEK
+                .iter()
+                .find(|(_, name)| *name == child.tag_name().name())
+            {
+                Some((day, _)) => days.insert(*day),
+                None => self.unknown(child),
+            }
+        }
+        days
+    }
+
+    fn months(&mut self, node: Node) -> Months {
+        let mut months = Months::empty();
+        for child in elements(node) {
+            match MONTHS
+                .iter()
+                .find(|(_, name)| *name == child.tag_name().name())
+            {
+                Some((month, _)) => months.insert(*month),
+                None => self.unknown(child),
+            }
+        }
+        months
+    }
+
+    fn principal(&mut self, node: Node) -> Result<PrincipalSettings, ParseError> {
+        let mut principal = PrincipalSettings {
+            display_name: String::new(),
+            group_id: None,
+            id: node.attribute("id").unwrap_or("").to_string(),
+            logon_type: LogonType::None,
+            process_token_sid_type: None,
+            required_privileges: Vec::new(),
+            run_level: RunLevel::LUA,
+            user_id: None,
+        };
+        let mut logon_type = None;
+
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "DisplayName" => principal.display_name = text(child).to_string(),
+                "GroupId" => principal.group_id = self.string(child),
+                "UserId" => principal.user_id = self.string(child),
+                "LogonType" => {
+                    logon_type = Some(match text(child) {
+                        "Password" => LogonType::Password,
+                        "S4U" => LogonType::S4U,
+                        "InteractiveToken" => LogonType::InteractiveToken,
+                        "InteractiveTokenOrPassword" => LogonType::InteractiveTokenOrPassword,
+                        "Group" => LogonType::Group,
+                        "ServiceAccount" => LogonType::ServiceAccount,
+                        "None" => LogonType::None,
+                        other => {
+                            return Err(
+                                self.error(child, &format!("'{}' is not a valid logon type", other))
+                            )
+                        }
+                    })
+                }
+                "RunLevel" => {
+                    principal.run_level = match text(child) {
+                        "HighestAvailable" => RunLevel::Highest,
+                        "LeastPrivilege" => RunLevel::LUA,
+                        other => {
+                            return Err(
+                                self.error(child, &format!("'{}' is not a valid run level", other))
+                            )
+                        }
+                    }
+                }
+                "ProcessTokenSidType" => {
+                    principal.process_token_sid_type = Some(match text(child) {
+                        "None" => ProcessTokenSidType::None,
+                        "Unrestricted" => ProcessTokenSidType::Unrestricted,
+                        "Default" => ProcessTokenSidType::Default,
+                        other => {
+                            return Err(self.error(
+                                child,
+                                &format!("'{}' is not a valid process token SID type", other),
+                            ))
+                        }
+                    })
+                }
+                "RequiredPrivileges" => {
+                    for privilege in elements(child) {
+                        if privilege.tag_name().name() != "Privilege" {
+                            self.unknown(privilege);
+                            continue;
+                        }
+                        match text(privilege).parse() {
+                            Ok(privilege) => principal.required_privileges.push(privilege),
+                            Err(ParseError { message, .. }) => {
+                                return Err(self.error(privilege, &message))
+                            }
+                        }
+                    }
+                }
+                _ => self.unknown(child),
+            }
+        }
+
+        // Group and service account principals are written without a logon type
+        principal.logon_type = match logon_type {
+            Some(logon_type) => logon_type,
+            None if principal.group_id.is_some() => LogonType::Group,
+            None if principal.user_id.as_deref().is_some_and(is_service_account) => {
+                LogonType::ServiceAccount
+            }
+            None => LogonType::None,
+        };
+        Ok(principal)
+    }
+
+    fn settings(&mut self, node: Node) -> Result<Settings, ParseError> {
+        let mut settings = Settings::new();
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "AllowHardTerminate" => settings.allow_hard_terminate = Some(self.bool(child)?),
+                "AllowStartOnDemand" => settings.allow_demand_start = Some(self.bool(child)?),
+                "DeleteExpiredTaskAfter" => {
+                    settings.delete_expired_task_after = Some(self.duration(child)?)
+                }
+                "DisallowStartIfOnBatteries" => {
+                    settings.disallow_start_if_on_batteries = Some(self.bool(child)?)
+                }
+                "DisallowStartOnRemoteAppSession" => {
+                    settings.disallow_start_on_remote_app_session = Some(self.bool(child)?)
+                }
+                "Enabled" => settings.enabled = Some(self.bool(child)?),
+                "ExecutionTimeLimit" => {
+                    settings.execution_time_limit = Some(self.duration(child)?)
+                }
+                "Hidden" => settings.hidden = Some(self.bool(child)?),
+                "IdleSettings" => settings.idle_settings = Some(self.idle_settings(child)?),
+                "MaintenanceSettings" => {
+                    settings.maintenance_settings = Some(self.maintenance_settings(child)?)
+                }
+                "MultipleInstancesPolicy" => {
+                    settings.multiple_instances_policy = Some(match text(child) {
+                        "Parallel" => InstancesPolicy::Parallel,
+                        "Queue" => InstancesPolicy::Queue,
+                        "IgnoreNew" => InstancesPolicy::IgnoreNew,
+                        "StopExisting" => InstancesPolicy::StopExisting,
+                        other => {
+                            return Err(self.error(
+                                child,
+                                &format!("'{}' is not a valid instances policy", other),
+                            ))
+                        }
+                    })
+                }
+                "NetworkSettings" => {
+                    let mut network = NetworkSettings {
+                        id: String::new(),
+                        name: String::new(),
+                    };
+                    for setting in elements(child) {
+                        match setting.tag_name().name() {
+                            "Id" => network.id = text(setting).to_string(),
+                            "Name" => network.name = text(setting).to_string(),
+                            _ => self.unknown(setting),
+                        }
+                    }
+                    settings.network_settings = Some(network);
+                }
+                "Priority" => match Priority::try_from(self.number::<i32>(child)?) {
+                    Ok(priority) => settings.priority = Some(priority),
+                    Err(e) => return Err(self.error(child, &e.message)),
+                },
+                "RestartOnFailure" => {
+                    for setting in elements(child) {
+                        match setting.tag_name().name() {
+                            "Count" => settings.restart_count = Some(self.number(setting)?),
+                            "Interval" => {
+                                settings.restart_interval = Some(self.duration(setting)?)
+                            }
+                            _ => self.unknown(setting),
+                        }
+                    }
+                }
+                "RunOnlyIfIdle" => settings.run_only_if_idle = Some(self.bool(child)?),
+                "RunOnlyIfNetworkAvailable" => {
+                    settings.run_only_if_network_available = Some(self.bool(child)?)
+                }
+                "StartWhenAvailable" => settings.start_when_available = Some(self.bool(child)?),
+                "StopIfGoingOnBatteries" => {
+                    settings.stop_if_going_on_batteries = Some(self.bool(child)?)
+                }
+                "UseUnifiedSchedulingEngine" => {
+                    settings.use_unified_scheduling_engine = Some(self.bool(child)?)
+                }
+                "Volatile" => settings.volatile = Some(self.bool(child)?),
+                "WakeToRun" => settings.wake_to_run = Some(self.bool(child)?),
+                _ => self.unknown(child),
+            }
+        }
+        Ok(settings)
+    }
+
+    #[allow(deprecated)]
+    fn idle_settings(&mut self, node: Node) -> Result<IdleSettings, ParseError> {
+        let mut idle = IdleSettings::new();
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "Duration" => idle.idle_duration = Some(self.duration(child)?),
+                "WaitTimeout" => idle.wait_timeout = Some(self.duration(child)?),
+                "RestartOnIdle" => idle.restart_on_idle = Some(self.bool(child)?),
+                "StopOnIdleEnd" => idle.stop_on_idle_end = Some(self.bool(child)?),
+                _ => self.unknown(child),
+            }
+        }
+        Ok(idle)
+    }
+
+    fn maintenance_settings(&mut self, node: Node) -> Result<MaintenanceSettings, ParseError> {
+        let mut period = None;
+        let mut deadline = None;
+        let mut exclusive = None;
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "Period" => period = Some(self.duration(child)?),
+                "Deadline" => deadline = Some(self.duration(child)?),
+                "Exclusive" => exclusive = Some(self.bool(child)?),
+                _ => self.unknown(child),
+            }
+        }
+        match period {
+            Some(period) => Ok(MaintenanceSettings {
+                period,
+                deadline,
+                exclusive,
+            }),
+            None => Err(self.error(node, "the maintenance period is missing")),
+        }
+    }
+
+    fn exec_action(&mut self, node: Node) -> Result<Action, ParseError> {
+        let mut exec = ExecAction {
+            id: node.attribute("id").unwrap_or("").to_string(),
+            path: String::new(),
+            working_dir: String::new(),
+            args: String::new(),
+        };
+        for child in elements(node) {
+            match child.tag_name().name() {
+                // The surrounding whitespace of the command line is meaningful
+                "Command" => exec.path = child.text().unwrap_or("").to_string(),
+                "Arguments" => exec.args = child.text().unwrap_or("").to_string(),
+                "WorkingDirectory" => exec.working_dir = child.text().unwrap_or("").to_string(),
+                _ => self.unknown(child),
+            }
+        }
+        if exec.path.is_empty() {
+            return Err(self.error(node, "an Exec action requires a Command"));
+        }
+        Ok(Action::Exec(exec))
+    }
+
+    fn com_handler_action(&mut self, node: Node) -> Result<Action, ParseError> {
+        let mut com_handler = ComHandlerAction {
+            id: node.attribute("id").unwrap_or("").to_string(),
+            class_id: String::new(),
+            data: String::new(),
+        };
+        for child in elements(node) {
+            match child.tag_name().name() {
+                "ClassId" => {
+                    com_handler.class_id = parse_class_id(text(child))
+                        .map_err(|e| self.error(child, &e.message))?
+                }
+                "Data" => com_handler.data = text(child).to_string(),
+                _ => self.unknown(child),
+            }
+        }
+        if com_handler.class_id.is_empty() {
+            return Err(self.error(node, "a ComHandler action requires a ClassId"));
+        }
+        Ok(Action::ComHandler(com_handler))
+    }
+}
+
+/// Escapes the characters which cannot appear in XML text or attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Minimal indenting writer, elements are indented by two spaces like the documents exported by
+// the Task Scheduler.
+struct XmlWriter {
+    out: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    fn new() -> Self {
+        Self {
+            out: String::new(),
+            depth: 0,
+        }
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.depth {
+            self.out.push_str("  ");
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn open(&mut self, name: &str) {
+        self.open_with(name, &[]);
+    }
+
+    fn open_with(&mut self, name: &str, attributes: &[(&str, &str)]) {
+        self.line(&format!("<{}{}>", name, attributes_to_string(attributes)));
+        self.depth += 1;
+    }
+
+    fn close(&mut self, name: &str) {
+        self.depth -= 1;
+        self.line(&format!("</{}>", name));
+    }
+
+    fn empty(&mut self, name: &str) {
+        self.line(&format!("<{} />", name));
+    }
+
+    fn element(&mut self, name: &str, text: &str) {
+        self.element_with(name, &[], text);
+    }
+
+    fn element_with(&mut self, name: &str, attributes: &[(&str, &str)], text: &str) {
+        self.line(&format!(
+            "<{}{}>{}</{}>",
+            name,
+            attributes_to_string(attributes),
+            escape(text),
+            name
+        ));
+    }
+
+    fn optional<T: ToString>(&mut self, name: &str, value: Option<T>) {
+        if let Some(value) = value {
+            self.element(name, &value.to_string());
+        }
+    }
+
+    // Empty durations are not valid values for the Task Scheduler, the element is skipped. A
+    // duration where every given value is zero, which `Display` formats as an empty string, is
+    // written as `PT0S`.
+    fn duration(&mut self, name: &str, value: Option<Duration>) {
+        match value.filter(|duration| !duration.is_empty()) {
+            Some(duration) if duration.to_string().is_empty() => self.element(name, "PT0S"),
+            duration => self.optional(name, duration),
+        }
+    }
+}
+
+fn attributes_to_string(attributes: &[(&str, &str)]) -> String {
+    attributes
+        .iter()
+        .map(|(name, value)| format!(" {}=\"{}\"", name, escape(value)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_special_characters() {
+        assert_eq!(escape("a<b>&\"c'"), "a&lt;b&gt;&amp;&quot;c&apos;");
+    }
+
+    #[test]
+    fn daily_document() {
+        let mut definition = TaskDefinition::new();
+        definition.registration_info.author = Some("Alice & Bob".to_string());
+        definition.triggers.push(Trigger {
+            start_boundary: Some("2007-01-01T08:00:00".to_string()),
+            repetition: Some(RepetitionPattern {
+                duration: Duration {
+                    hours: Some(1),
+                    ..Default::default()
+                },
+                interval: Duration {
+                    minutes: Some(5),
+                    ..Default::default()
+                },
+                stop_at_duration_end: true,
+            }),
+            ..Trigger::new(
+                "DailyTrigger",
+                TriggerKind::Daily(DailyTrigger {
+                    days_interval: Some(2),
+                    random_delay: None,
+                }),
+            )
+        });
+        definition.settings.allow_demand_start = Some(true);
+        definition
+            .actions
+            .push(Action::new("action", "notepad.exe", "", "file.txt"));
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <Author>Alice &amp; Bob</Author>
+  </RegistrationInfo>
+  <Triggers>
+    <CalendarTrigger id="DailyTrigger">
+      <Repetition>
+        <Interval>PT5M</Interval>
+        <Duration>PT1H</Duration>
+        <StopAtDurationEnd>true</StopAtDurationEnd>
+      </Repetition>
+      <StartBoundary>2007-01-01T08:00:00</StartBoundary>
+      <Enabled>true</Enabled>
+      <ScheduleByDay>
+        <DaysInterval>2</DaysInterval>
+      </ScheduleByDay>
+    </CalendarTrigger>
+  </Triggers>
+  <Settings>
+    <AllowStartOnDemand>true</AllowStartOnDemand>
+  </Settings>
+  <Actions>
+    <Exec id="action">
+      <Command>notepad.exe</Command>
+      <Arguments>file.txt</Arguments>
+    </Exec>
+  </Actions>
+</Task>
+"#;
+        assert_eq!(serialize(&definition, SchemaVersion::V1_2), expected);
+    }
+
+    #[test]
+    fn empty_durations_are_skipped() {
+        let minutes = |minutes| Duration {
+            minutes: Some(minutes),
+            ..Default::default()
+        };
+        let mut definition = TaskDefinition::new();
+        definition.triggers.push(Trigger {
+            repetition: Some(RepetitionPattern {
+                duration: Duration::default(),
+                interval: minutes(5),
+                stop_at_duration_end: false,
+            }),
+            ..Trigger::new(
+                "BootTrigger",
+                TriggerKind::Boot(BootTrigger {
+                    delay: Some(Duration::default()),
+                }),
+            )
+        });
+        definition.settings.execution_time_limit = Some(minutes(0));
+
+        let document = serialize(&definition, SchemaVersion::V1_2);
+        assert!(document.contains(
+            "        <Interval>PT5M</Interval>
+        <StopAtDurationEnd>false</StopAtDurationEnd>"
+        ));
+        assert!(!document.contains("<Delay>"));
+        assert!(document.contains("<ExecutionTimeLimit>PT0S</ExecutionTimeLimit>"));
+        let parsed = parse_str(&document).unwrap();
+        assert_eq!(parsed.definition.triggers[0].repetition, definition.triggers[0].repetition);
+    }
+
+    #[test]
+    fn zero_durations_are_written_as_pt0s() {
+        for zero in ["P0D", "PT0M", "P0Y0M", "PT0H0S"] {
+            let mut definition = TaskDefinition::new();
+            definition.settings.execution_time_limit = Some(zero.parse().unwrap());
+            let document = serialize(&definition, SchemaVersion::V1_2);
+            assert!(document.contains("<ExecutionTimeLimit>PT0S</ExecutionTimeLimit>"));
+        }
+    }
+
+    #[test]
+    fn version_follows_the_settings() {
+        let mut definition = TaskDefinition::new();
+        definition.settings.volatile = Some(true);
+        let document = serialize(&definition, SchemaVersion::V1_2);
+        assert!(document.contains("<Task version=\"1.4\""));
+
+        let mut definition = TaskDefinition::new();
+        definition.settings.use_unified_scheduling_engine = Some(true);
+        let document = serialize(&definition, SchemaVersion::V1_2);
+        assert!(document.contains("<Task version=\"1.3\""));
+    }
+
+    #[test]
+    fn version_follows_the_declared_compatibility() {
+        let version = |compatibility, requested| {
+            let mut definition = TaskDefinition::new();
+            definition.settings.compatibility = compatibility;
+            parse_str(&serialize(&definition, requested))
+                .unwrap()
+                .version
+                .unwrap()
+        };
+        assert_eq!(version(None, SchemaVersion::V1_3), "1.3");
+        assert_eq!(version(Some(Compatibility::V2), SchemaVersion::V1_2), "1.2");
+        assert_eq!(version(Some(Compatibility::V2_1), SchemaVersion::V1_2), "1.3");
+        assert_eq!(version(Some(Compatibility::V2_3), SchemaVersion::V1_2), "1.4");
+        assert_eq!(version(Some(Compatibility::V1), SchemaVersion::V1_4), "1.4");
+    }
+
+    #[test]
+    fn monthly_dow_schedule() {
+        let mut definition = TaskDefinition::new();
+        definition.triggers.push(Trigger::new(
+            "",
+            TriggerKind::MonthlyDOW(MonthlyDOWTrigger {
+                days_of_week: Some(DayOfWeek::Monday | DayOfWeek::Friday),
+                months_of_year: Some(Month::March.into()),
+                run_on_last_week: Some(true),
+                weeks_of_month: Some(WeekOfMonth::First.into()),
+                ..Default::default()
+            }),
+        ));
+
+        let document = serialize(&definition, SchemaVersion::V1_4);
+        assert!(document.contains("<Task version=\"1.4\""));
+        assert!(document.contains(
+            "        <Weeks>
+          <Week>1</Week>
+          <Week>Last</Week>
+        </Weeks>
+        <DaysOfWeek>
+          <Monday />
+          <Friday />
+        </DaysOfWeek>
+        <Months>
+          <March />
+        </Months>
+"
+        ));
+    }
+
+    #[test]
+    fn utf16_has_bom() {
+        let bytes = serialize_utf16(&TaskDefinition::new(), SchemaVersion::V1_2);
+        assert_eq!(&bytes[..4], &[0xFF, 0xFE, b'<', 0]);
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let mut definition = TaskDefinition::new();
+        definition.registration_info.description = Some("Runs <weekly>".to_string());
+        definition.registration_info.security_descriptor =
+            Some("O:BAG:SYD:P(A;;FA;;;BA)(A;;FRFX;;;AU)".parse().unwrap());
+        definition.triggers.push(Trigger {
+            start_boundary: Some("2007-01-01T08:00:00".to_string()),
+            ..Trigger::new(
+                "MonthlyDOW",
+                TriggerKind::MonthlyDOW(MonthlyDOWTrigger {
+                    days_of_week: Some(DayOfWeek::Monday | DayOfWeek::Friday),
+                    months_of_year: Some(Month::March.into()),
+                    run_on_last_week: Some(true),
+                    weeks_of_month: Some(WeekOfMonth::First.into()),
+                    random_delay: Some(Duration {
+                        minutes: Some(30),
+                        ..Default::default()
+                    }),
+                }),
+            )
+        });
+        definition.triggers.push(Trigger::new(
+            "Logon",
+            TriggerKind::Logon(LogonTrigger {
+                delay: None,
+                user_id: Some("DOMAIN\\user".to_string()),
+            }),
+        ));
+        definition.triggers.push(Trigger::new(
+            "Lock",
+            TriggerKind::SessionStateChange(SessionStateChangeTrigger {
+                delay: Some(Duration {
+                    minutes: Some(1),
+                    ..Default::default()
+                }),
+                state_change: Some(StateChange::SessionLock),
+                user_id: None,
+            }),
+        ));
+        definition.settings.multiple_instances_policy = Some(InstancesPolicy::Queue);
+        definition.settings.priority = Some(Priority::AboveNormal);
+        definition.settings.restart_count = Some(3);
+        definition.settings.restart_interval = Some("10m".parse().unwrap());
+        definition.settings.use_unified_scheduling_engine = Some(true);
+        definition.settings.disallow_start_on_remote_app_session = Some(true);
+        definition.settings.volatile = Some(false);
+        definition.settings.maintenance_settings = Some(MaintenanceSettings {
+            exclusive: Some(true),
+            ..MaintenanceSettings::new("P2D".parse().unwrap())
+        });
+        definition
+            .actions
+            .push(Action::new("action", "notepad.exe", "C:\\", "file.txt"));
+        definition.actions.push(
+            Action::com_handler("handler", "{0BE8D5D8-C4DF-4DE6-A4E7-FEE01D6F3C49}", "<data>")
+                .unwrap(),
+        );
+
+        // The maintenance and volatile settings require the 1.4 schema
+        let parsed = parse(&serialize_utf16(&definition, SchemaVersion::V1_2)).unwrap();
+        assert_eq!(parsed.version.as_deref(), Some("1.4"));
+        assert_eq!(parsed.definition, definition);
+        assert!(parsed.unknown_elements.is_empty());
+    }
+
+    #[test]
+    fn parse_reports_unknown_elements() {
+        let document = r#"<Task version="1.4" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <Triggers>
+    <WnfStateChangeTrigger>
+      <StateName>7508BCA3283BF141</StateName>
+    </WnfStateChangeTrigger>
+  </Triggers>
+  <Settings>
+    <NetworkProfileName>Office</NetworkProfileName>
+  </Settings>
+</Task>"#;
+
+        let parsed = parse_str(document).unwrap();
+        assert_eq!(
+            parsed.unknown_elements,
+            vec![
+                UnknownElement {
+                    path: "Task/Triggers/WnfStateChangeTrigger".to_string(),
+                    line: 3,
+                },
+                UnknownElement {
+                    path: "Task/Settings/NetworkProfileName".to_string(),
+                    line: 8,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_invalid_values() {
+        let document = r#"<Task xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <Settings>
+    <Enabled>yes</Enabled>
+  </Settings>
+</Task>"#;
+        let error = parse_str(document).unwrap_err();
+        assert_eq!(error.message, "Task/Settings/Enabled (line 3): 'yes' is not a boolean");
+
+        let document = r#"<Task xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo>
+    <SecurityDescriptor>D:(A;;FA;;;Operators)</SecurityDescriptor>
+  </RegistrationInfo>
+</Task>"#;
+        let error = parse_str(document).unwrap_err();
+        assert!(error
+            .message
+            .starts_with("Task/RegistrationInfo/SecurityDescriptor (line 3): Invalid security descriptor"));
+
+        let document = r#"<Task xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <Triggers>
+    <CalendarTrigger>
+      <StartBoundary>2023-01-01T08:00:00</StartBoundary>
+    </CalendarTrigger>
+  </Triggers>
+</Task>"#;
+        let error = parse_str(document).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Task/Triggers/CalendarTrigger (line 3): CalendarTrigger requires ScheduleByDay/Week/Month/MonthDayOfWeek"
+        );
+
+        assert!(parse_str("<Schedule />").is_err());
+        assert!(parse_str("<Task>").is_err());
+    }
+
+    #[test]
+    fn exec_actions_are_not_trimmed() {
+        let mut definition = TaskDefinition::new();
+        definition.actions.push(Action::new(
+            "",
+            "C:\\Program Files\\tool.exe ",
+            " C:\\Work ",
+            " --name \"a b\"  ",
+        ));
+        let parsed = parse_str(&serialize(&definition, SchemaVersion::V1_2)).unwrap();
+        assert_eq!(parsed.definition.actions, definition.actions);
+    }
+
+    #[test]
+    fn principal_privileges() {
+        use crate::settings::{Privilege, PrincipalBuilder};
+
+        let mut definition = TaskDefinition::new();
+        definition.principal = Some(
+            PrincipalBuilder::local_service()
+                .process_token_sid_type(ProcessTokenSidType::Unrestricted)
+                .required_privileges([Privilege::Backup, Privilege::ChangeNotify, Privilege::Backup])
+                .build()
+                .unwrap(),
+        );
+
+        let document = serialize(&definition, SchemaVersion::V1_3);
+        assert!(document.contains(
+            "      <RunLevel>LeastPrivilege</RunLevel>
+      <ProcessTokenSidType>Unrestricted</ProcessTokenSidType>
+      <RequiredPrivileges>
+        <Privilege>SeBackupPrivilege</Privilege>
+        <Privilege>SeChangeNotifyPrivilege</Privilege>
+      </RequiredPrivileges>
+"
+        ));
+        assert_eq!(parse_str(&document).unwrap().definition, definition);
+
+        let error = parse_str(&document.replace("SeBackupPrivilege", "SeBackup")).unwrap_err();
+        assert!(error.message.ends_with("'SeBackup' is not a privilege"));
+    }
+}